@@ -0,0 +1,645 @@
+use crate::logger::ConsoleLogger;
+use crate::save_logic::backup_manager::BACKUP_DIRECTORY;
+use crate::save_logic::bulk_editor::bulk_edit_items;
+use crate::save_logic::edit_plan::{fetch_edit_plan, get_edited_save_path, run_edit_plan};
+use crate::save_logic::file_analyser::load_save_from_path;
+use crate::save_logic::id_database::{get_current_id_path, ID_DATABASE_DIRECTORY};
+use crate::save_logic::item_catalog::fetch_item_catalog;
+use crate::save_logic::item_rules::{fetch_item_rules, ItemRuleEngine};
+use crate::save_logic::save_compression::compress_save_content;
+use crate::save_logic::save_converter::{
+    convert_save_file, fetch_save_fingerprint, CONSOLE_FORMAT, PC_FORMAT,
+};
+use crate::save_logic::save_discovery::discover_save_slots;
+use crate::save_logic::save_exporter::export_save_data;
+use crate::save_logic::save_importer::{
+    fetch_save_export, preview_save_import, rebuild_save_from_export,
+};
+use crate::save_logic::save_writer::write_file_atomically;
+use crate::save_logic::script_runner::run_save_script;
+use crate::save_logic::struct_data::{
+    BulkEditReport, EditPlan, EditPlanReport, ExportReport, InventoryTabMapping, ItemCatalog,
+    SaveConversionReport, SaveExport, SaveFile, SaveFingerprint, SaveImportReport, SaveMetadata,
+    ScriptReport,
+};
+use crate::save_logic::tab_mapping_fetcher::fetch_tab_mapping;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the folder that is used to check whether a directory contains the editor resources.
+static RESOURCE_MARKER: &str = "Mappings";
+
+// Defines the identifier of the editor, which is the name of its app data directory.
+static APP_IDENTIFIER: &str = "dev.marcel-to.savegame-editor";
+
+// Defines the usage of the command line interface.
+static USAGE: &str = "Usage: savegame-editor <command> [options]
+
+Commands:
+  export <save> <output_dir>   Exports inventory, skills and unlockables.
+      --format <csv|json|all>  The export format (default: all).
+  import <save> <json>         Rebuilds a save from its edited JSON export.
+      --output <file>          The edited save (default: <save>_edited.sav).
+      --dry-run                Only lists the changes without writing the save.
+  plan <plan> <save|dir>       Applies an edit plan (JSON or TOML) to a save or every save of a directory.
+      --dry-run                Only counts the changes without writing any file.
+  script <script> <save>       Runs a Rhai script on a save.
+      --output <file>          The edited save (default: <save>_edited.sav).
+      --sandbox <dir>          The directory the script can access (default: the script directory).
+      --dry-run                Only runs the script without writing the save.
+  bulk <save>                  Sets a field of every inventory item that matches a filter.
+      --filter <filter>        The filter, e.g. \"tab:Weapons id:wpn_* level:..10 has_mod:*\" (default: all items).
+      --set <field>=<value>    The field (level, seed, amount, durability) and a number or expression, e.g. level=value+2.
+      --output <file>          The edited save (default: <save>_edited.sav).
+      --dry-run                Only lists the changes without writing the save.
+  convert <save> <output>      Converts a save between the PC (compressed) and the console (raw) format.
+      --to <pc|console>        The target format (default: the other format).
+  fingerprint <save>           Shows the format, the headers and the game version of a save.
+  discover [<dir>...]          Lists the save slots of the Steam installations and the given directories.
+  help                         Shows this message.
+
+Options:
+  --resources <dir>            The directory with the IDs, Mappings and Patched_Items folders.
+  --ids <dir>                  The directory with the ID files (default: the ID database of the editor).
+  --debug                      Enables the debugging output of the file analyser.";
+
+/// The positional arguments and options passed to a CLI command.
+struct CliArguments {
+    positional: Vec<String>,
+    options: HashMap<String, String>,
+    flags: Vec<String>,
+}
+
+/// Represents a method for running the command line interface.
+///
+/// ### Parameter
+/// - `args`: The command line arguments (without the executable).
+///
+/// ### Returns `Option<i32>`
+/// The exit code of the command, `None` if the arguments are not a CLI command and the editor should start.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    let command: &str = args.first()?.as_str();
+    let arguments: CliArguments = parse_arguments(&args[1..]);
+
+    let result: Result<()> = match command {
+        "export" => run_export(&arguments),
+        "import" => run_import(&arguments),
+        "plan" => run_plan(&arguments),
+        "script" => run_script(&arguments),
+        "bulk" => run_bulk_edit(&arguments),
+        "convert" => run_convert(&arguments),
+        "fingerprint" => run_fingerprint(&arguments),
+        "discover" => run_discover(&arguments),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => return None,
+    };
+
+    match result {
+        Ok(_) => Some(0),
+        Err(err) => {
+            eprintln!("[ERROR]: {}", err);
+            Some(1)
+        }
+    }
+}
+
+/// Represents a method for exporting a save to CSV and/or JSON files.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+fn run_export(arguments: &CliArguments) -> Result<()> {
+    let (save_path, output_dir) = match arguments.positional.as_slice() {
+        [save_path, output_dir] => (save_path, output_dir),
+        _ => return Err(format!("Expected <save> and <output_dir>.\n\n{}", USAGE).into()),
+    };
+    let format: &str = arguments
+        .options
+        .get("format")
+        .map(|f| f.as_str())
+        .unwrap_or("all");
+
+    let save_file: SaveFile = load_save(save_path, arguments)?;
+    let report: ExportReport = export_save_data(&save_file, output_dir, format)?;
+
+    println!(
+        "Exported {} items, {} skills and {} unlockables:",
+        report.item_count, report.skill_count, report.unlockable_count
+    );
+    for file in report.files.iter() {
+        println!("  {}", file);
+    }
+
+    Ok(())
+}
+
+/// Represents a method for rebuilding a save from its edited JSON export.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+fn run_import(arguments: &CliArguments) -> Result<()> {
+    let (save_path, json_path) = match arguments.positional.as_slice() {
+        [save_path, json_path] => (save_path, json_path),
+        _ => return Err(format!("Expected <save> and <json>.\n\n{}", USAGE).into()),
+    };
+    let dry_run: bool = arguments.flags.iter().any(|f| f == "dry-run");
+
+    let save_file: SaveFile = load_save(save_path, arguments)?;
+    let export: SaveExport = fetch_save_export(json_path)?;
+    let report: SaveImportReport = preview_save_import(&save_file, &export);
+
+    for change in report.changes.iter() {
+        println!(
+            "  [{}] {} {} ({}): {} -> {}",
+            change.index,
+            change.section,
+            change.name,
+            change.field,
+            change.old_value,
+            change.new_value
+        );
+    }
+    for issue in report.issues.iter() {
+        println!("  [INVALID] {}", issue);
+    }
+    println!(
+        "{} changes, {} invalid entries ({} byte ranges)",
+        report.changes.len(),
+        report.issues.len(),
+        report.byte_changes.len()
+    );
+
+    if !report.issues.is_empty() {
+        return Err("The save export contains invalid entries, nothing was written.".into());
+    }
+
+    if dry_run || report.byte_changes.is_empty() {
+        return Ok(());
+    }
+
+    let (catalog, tab_mapping, item_rules) = load_resources(arguments)?;
+    let mut logger: ConsoleLogger = ConsoleLogger::new();
+    let (_, new_save_file) = rebuild_save_from_export(
+        &save_file,
+        &export,
+        catalog,
+        tab_mapping,
+        item_rules,
+        &mut logger,
+    )?;
+
+    write_edited_save(save_path, &save_file, new_save_file.file_content, arguments)
+}
+
+/// Represents a method for applying an edit plan to a save or every save of a directory.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+fn run_plan(arguments: &CliArguments) -> Result<()> {
+    let (plan_path, target_path) = match arguments.positional.as_slice() {
+        [plan_path, target_path] => (plan_path, target_path),
+        _ => return Err(format!("Expected <plan> and <save|dir>.\n\n{}", USAGE).into()),
+    };
+    let dry_run: bool = arguments.flags.iter().any(|f| f == "dry-run");
+
+    let plan: EditPlan = fetch_edit_plan(plan_path)?;
+    let (catalog, tab_mapping, item_rules) = load_resources(arguments)?;
+    let mut logger: ConsoleLogger = ConsoleLogger::new();
+
+    // The originals are backed up into the same store as in the editor.
+    let store_path: String = get_app_data_path()
+        .ok_or("The app data directory is unknown, no backups can be created.")?
+        .join(BACKUP_DIRECTORY)
+        .display()
+        .to_string();
+
+    let report: EditPlanReport = run_edit_plan(
+        &plan,
+        target_path,
+        &catalog,
+        &tab_mapping,
+        &item_rules,
+        &store_path,
+        &mut logger,
+        dry_run,
+    )?;
+
+    let mut has_errors: bool = false;
+    for result in report.results.iter() {
+        match &result.error {
+            Some(err) => {
+                has_errors = true;
+                println!("{}: {}", result.path, err);
+            }
+            None => println!(
+                "{}: {} items changed, {} removed, {} skills changed ({} byte ranges){}",
+                result.path,
+                result.changed_items,
+                result.removed_items,
+                result.changed_skills,
+                result.byte_changes,
+                match dry_run || result.byte_changes == 0 {
+                    true => String::new(),
+                    false => format!(" -> {}", result.output_path),
+                }
+            ),
+        }
+    }
+
+    match has_errors {
+        true => Err("The edit plan could not be applied to every save.".into()),
+        false => Ok(()),
+    }
+}
+
+/// Represents a method for running a script on a save.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+fn run_script(arguments: &CliArguments) -> Result<()> {
+    let (script_path, save_path) = match arguments.positional.as_slice() {
+        [script_path, save_path] => (script_path, save_path),
+        _ => return Err(format!("Expected <script> and <save>.\n\n{}", USAGE).into()),
+    };
+    let dry_run: bool = arguments.flags.iter().any(|f| f == "dry-run");
+
+    // The script can only access the files next to it, unless another sandbox is passed.
+    let sandbox_path: String = match arguments.options.get("sandbox") {
+        Some(sandbox_path) => sandbox_path.clone(),
+        None => Path::new(script_path)
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .display()
+            .to_string(),
+    };
+
+    let save_file: SaveFile = load_save(save_path, arguments)?;
+    let (report, content): (ScriptReport, Vec<u8>) =
+        run_save_script(&save_file, script_path, Some(&sandbox_path))?;
+
+    for line in report.output.iter() {
+        println!("{}", line);
+    }
+    println!(
+        "{} items changed, {} removed, {} skills changed ({} byte ranges)",
+        report.changed_items, report.removed_items, report.changed_skills, report.byte_changes
+    );
+
+    if dry_run || report.byte_changes == 0 {
+        return Ok(());
+    }
+
+    write_edited_save(save_path, &save_file, content, arguments)
+}
+
+/// Represents a method for setting a field of every inventory item that matches a filter.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+fn run_bulk_edit(arguments: &CliArguments) -> Result<()> {
+    let save_path: &String = match arguments.positional.as_slice() {
+        [save_path] => save_path,
+        _ => return Err(format!("Expected <save>.\n\n{}", USAGE).into()),
+    };
+    let (field, expression) = arguments
+        .options
+        .get("set")
+        .and_then(|set| set.split_once('='))
+        .ok_or(format!("Expected --set <field>=<value>.\n\n{}", USAGE))?;
+    let filter: &str = arguments
+        .options
+        .get("filter")
+        .map(|f| f.as_str())
+        .unwrap_or_default();
+    let dry_run: bool = arguments.flags.iter().any(|f| f == "dry-run");
+
+    let save_file: SaveFile = load_save(save_path, arguments)?;
+    let (report, content): (BulkEditReport, Vec<u8>) =
+        bulk_edit_items(&save_file, filter, field, expression)?;
+
+    for change in report.changes.iter() {
+        println!(
+            "  [{}] {} ({}): {} -> {}",
+            change.index, change.id, change.tab, change.old_value, change.new_value
+        );
+    }
+    println!(
+        "{} items matched, {} changed ({} byte ranges)",
+        report.matched_items,
+        report.changes.len(),
+        report.byte_changes.len()
+    );
+
+    if dry_run || report.byte_changes.is_empty() {
+        return Ok(());
+    }
+
+    write_edited_save(save_path, &save_file, content, arguments)
+}
+
+/// Represents a method for converting a save between the PC and the console format.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+fn run_convert(arguments: &CliArguments) -> Result<()> {
+    let (save_path, output_path) = match arguments.positional.as_slice() {
+        [save_path, output_path] => (save_path, output_path),
+        _ => return Err(format!("Expected <save> and <output>.\n\n{}", USAGE).into()),
+    };
+    let format: String = match arguments.options.get("to") {
+        Some(format) => format.to_lowercase(),
+        None => match fetch_save_fingerprint(save_path)?.is_compressed {
+            true => CONSOLE_FORMAT.to_string(),
+            false => PC_FORMAT.to_string(),
+        },
+    };
+
+    let report: SaveConversionReport = convert_save_file(save_path, output_path, &format)?;
+
+    print_save_fingerprint(&report.source);
+    print_save_fingerprint(&report.target);
+    println!(
+        "Converted the {} save to the {} save {}",
+        report.source.format, report.target.format, report.output_path
+    );
+
+    Ok(())
+}
+
+/// Represents a method for showing the fingerprint of a save.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+fn run_fingerprint(arguments: &CliArguments) -> Result<()> {
+    let save_path: &String = match arguments.positional.as_slice() {
+        [save_path] => save_path,
+        _ => return Err(format!("Expected <save>.\n\n{}", USAGE).into()),
+    };
+
+    print_save_fingerprint(&fetch_save_fingerprint(save_path)?);
+
+    Ok(())
+}
+
+/// Represents a method for listing the save slots of the Steam installations and the given directories.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+fn run_discover(arguments: &CliArguments) -> Result<()> {
+    let slots: Vec<SaveMetadata> = discover_save_slots(&arguments.positional);
+
+    for slot in slots.iter() {
+        println!(
+            "[{}] {} ({}, {}, {} bytes)",
+            slot.source,
+            slot.path,
+            match slot.is_compressed {
+                true => PC_FORMAT,
+                false => CONSOLE_FORMAT,
+            },
+            slot.game_version,
+            slot.size
+        );
+    }
+    println!("Found {} save slots", slots.len());
+
+    Ok(())
+}
+
+/// Represents a method for printing the fingerprint of a save.
+///
+/// ### Parameter
+/// - `fingerprint`: The fingerprint of the save.
+fn print_save_fingerprint(fingerprint: &SaveFingerprint) {
+    println!("{} ({})", fingerprint.path, fingerprint.format);
+    println!("  game version: {}", fingerprint.game_version);
+    println!(
+        "  size: {} bytes ({} bytes content)",
+        fingerprint.file_size, fingerprint.content_size
+    );
+    println!("  content hash: {}", fingerprint.content_hash);
+    println!("  save header: {}", fingerprint.save_header);
+
+    if let Some(profile) = fingerprint.gzip_profile.as_ref() {
+        println!(
+            "  gzip: level {} ({}), flags {:#04x}, mtime {}, os {}, name \"{}\"",
+            profile.level,
+            match profile.is_reproducible {
+                true => "reproducible",
+                false => "estimated",
+            },
+            profile.flags,
+            profile.mtime,
+            profile.operating_system,
+            profile.filename
+        );
+    }
+}
+
+/// Represents a method for writing the edited content of a save next to the original (or to `--output`).
+///
+/// ### Parameter
+/// - `save_path`: The path of the original save.
+/// - `save_file`: The parsed original save.
+/// - `content`: The edited (uncompressed) content.
+/// - `arguments`: The arguments of the command.
+fn write_edited_save(
+    save_path: &str,
+    save_file: &SaveFile,
+    content: Vec<u8>,
+    arguments: &CliArguments,
+) -> Result<()> {
+    let output_path: PathBuf = match arguments.options.get("output") {
+        Some(output_path) => PathBuf::from(output_path),
+        None => get_edited_save_path(save_path),
+    };
+
+    if output_path == Path::new(save_path) {
+        return Err("The edited save cannot overwrite the original save.".into());
+    }
+
+    // Compressed PC saves are written compressed again.
+    let output_content: Vec<u8> = match save_file.is_compressed {
+        true => compress_save_content(&content, save_file.gzip_profile.as_ref())?,
+        false => content,
+    };
+    write_file_atomically(&output_path, &output_content)?;
+    println!("Saved the edited save to {}", output_path.display());
+
+    Ok(())
+}
+
+/// Represents a method for loading the resources of the editor that are required for analysing saves.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command (for the resource paths).
+///
+/// ### Returns `(ItemCatalog, InventoryTabMapping, ItemRuleEngine)`
+/// The item catalog, the inventory tab mapping and the classification rules.
+fn load_resources(
+    arguments: &CliArguments,
+) -> Result<(ItemCatalog, InventoryTabMapping, ItemRuleEngine)> {
+    let resource_path: PathBuf = find_resource_path(arguments)?;
+    let mapping_path: String = resource_path.join("Mappings").display().to_string();
+    let patched_path: String = resource_path.join("Patched_Items").display().to_string();
+    let bundled_path: String = resource_path.join("IDs").display().to_string();
+
+    // The same ID database as in the editor is used, so both classify a save the same way.
+    let id_path: String = match (arguments.options.get("ids"), get_app_data_path()) {
+        (Some(id_path), _) => id_path.clone(),
+        (None, Some(app_data_path)) => get_current_id_path(
+            &app_data_path
+                .join(ID_DATABASE_DIRECTORY)
+                .display()
+                .to_string(),
+            &bundled_path,
+        )?,
+        (None, None) => bundled_path,
+    };
+
+    Ok((
+        fetch_item_catalog(&id_path, &patched_path)?,
+        fetch_tab_mapping(&mapping_path)?,
+        fetch_item_rules(&mapping_path)?,
+    ))
+}
+
+/// Represents a method for loading a save with the resources of the editor.
+///
+/// ### Parameter
+/// - `file_path`: The path of the save.
+/// - `arguments`: The arguments of the command (for the resource paths and the debugging mode).
+///
+/// ### Returns `SaveFile`
+/// The parsed save.
+fn load_save(file_path: &str, arguments: &CliArguments) -> Result<SaveFile> {
+    let (catalog, tab_mapping, item_rules) = load_resources(arguments)?;
+    let mut logger: ConsoleLogger = ConsoleLogger::new();
+
+    load_save_from_path(
+        file_path,
+        catalog,
+        tab_mapping,
+        item_rules,
+        &mut logger,
+        is_debugging(arguments),
+    )
+}
+
+/// Represents a method for checking whether the debugging output is enabled.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+///
+/// ### Returns `bool`
+/// Indicates whether the `--debug` flag is set.
+fn is_debugging(arguments: &CliArguments) -> bool {
+    arguments.flags.iter().any(|f| f == "debug")
+}
+
+/// Represents a method for finding the directory with the resources of the editor.
+/// Without `--resources` the directory of the executable and the working directory are checked.
+///
+/// ### Parameter
+/// - `arguments`: The arguments of the command.
+///
+/// ### Returns `PathBuf`
+/// The resource directory.
+fn find_resource_path(arguments: &CliArguments) -> Result<PathBuf> {
+    if let Some(resource_path) = arguments.options.get("resources") {
+        let path: PathBuf = PathBuf::from(resource_path);
+
+        if !path.join(RESOURCE_MARKER).is_dir() {
+            return Err(format!(
+                "The resource directory [{}] does not contain a {} folder.",
+                resource_path, RESOURCE_MARKER
+            )
+            .into());
+        }
+
+        return Ok(path);
+    }
+
+    let mut candidates: Vec<PathBuf> = Vec::new();
+
+    if let Some(executable_dir) = env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+    {
+        candidates.push(executable_dir);
+    }
+
+    if let Ok(current_dir) = env::current_dir() {
+        candidates.push(current_dir);
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|p| fs::canonicalize(p).ok())
+        .find(|p| p.join(RESOURCE_MARKER).is_dir())
+        .ok_or("Could not find the editor resources, please pass --resources <dir>.".into())
+}
+
+/// Represents a method for getting the app data directory of the editor (the directory Tauri resolves for the app).
+///
+/// ### Returns `Option<PathBuf>`
+/// The app data directory (`None` if the home directory is unknown).
+fn get_app_data_path() -> Option<PathBuf> {
+    let get_path = |name: &str| {
+        env::var_os(name)
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let data_path: PathBuf = if cfg!(windows) {
+        get_path("APPDATA")?
+    } else if cfg!(target_os = "macos") {
+        get_path("HOME")?.join("Library/Application Support")
+    } else {
+        get_path("XDG_DATA_HOME")
+            .or_else(|| get_path("HOME").map(|home| home.join(".local/share")))?
+    };
+
+    Some(data_path.join(APP_IDENTIFIER))
+}
+
+/// Represents a method for splitting the arguments into positional arguments, options and flags.
+/// Options are written as `--name value`, flags as `--name`.
+///
+/// ### Parameter
+/// - `args`: The arguments of the command.
+///
+/// ### Returns `CliArguments`
+/// The parsed arguments.
+fn parse_arguments(args: &[String]) -> CliArguments {
+    // Defines the options that do not expect a value.
+    let flag_names: [&str; 2] = ["debug", "dry-run"];
+
+    let mut arguments: CliArguments = CliArguments {
+        positional: Vec::new(),
+        options: HashMap::new(),
+        flags: Vec::new(),
+    };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.strip_prefix("--") {
+            Some(name) if flag_names.contains(&name) => arguments.flags.push(name.to_string()),
+            Some(name) => {
+                let value: String = iter.next().cloned().unwrap_or_default();
+                arguments.options.insert(name.to_string(), value);
+            }
+            None => arguments.positional.push(arg.clone()),
+        }
+    }
+
+    arguments
+}
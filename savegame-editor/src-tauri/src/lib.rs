@@ -1,31 +1,74 @@
+mod cli;
 mod logger;
 mod save_logic;
 
-use std::error::Error;
+pub use cli::run_cli;
 
 use logger::ConsoleLogger;
+use save_logic::backup_manager::{
+    create_save_backup, fetch_save_backups, restore_save_backup, BACKUP_DIRECTORY,
+    BACKUP_RETENTION, LOAD_REASON, MANUAL_REASON,
+};
+use save_logic::bulk_editor::bulk_edit_items;
 use save_logic::bypass_crc::get_files_and_copy_to_destination;
+use save_logic::coop_checker::{find_coop_issues, fix_coop_issues};
+use save_logic::edit_plan::{fetch_edit_plan, run_edit_plan};
 use save_logic::file_analyser::{
-    change_items_amount, change_items_durability, create_backup_from_file,
-    edit_inventory_item_chunk, edit_skill, export_save_for_pc, get_contents_from_file,
-    load_save_file, load_save_file_pc, remove_inventory_item,
+    change_items_amount, change_items_durability, decompress_save_content,
+    edit_inventory_item_chunk, edit_skill, get_content_hash, get_contents_from_file,
+    is_compressed_content, load_save_file, load_save_file_pc, load_save_from_path,
+    remove_inventory_item,
+};
+use save_logic::id_database::{
+    fetch_database_ids, fetch_id_snapshots, get_current_id_path, merge_ids, rollback_ids,
+    ID_DATABASE_DIRECTORY,
 };
-use save_logic::id_fetcher::{fetch_ids, update_ids};
+use save_logic::id_harvester::create_harvest_import;
+use save_logic::id_importer::read_id_import;
+use save_logic::id_search::search_catalog;
+use save_logic::inventory_importer::{apply_inventory_csv, preview_inventory_csv};
+use save_logic::item_catalog::fetch_item_catalog;
+use save_logic::item_rules::{fetch_item_rules, ItemRuleEngine};
+use save_logic::item_transfer::transfer_item_between_saves;
+use save_logic::layout_discovery::discover_layout;
+use save_logic::pak_extractor::extract_game_ids;
 use save_logic::patched_items_fetcher::fetch_patched_ids;
-use save_logic::save_outpost::fetch_outpost_saves;
-use save_logic::struct_data::{IdData, InventoryChunk, OutpostSave, PatchedItems, SaveFile};
+use save_logic::progression_merger::{merge_progression_between_saves, preview_progression_merge};
+use save_logic::save_compression::compress_save_content;
+use save_logic::save_converter::{convert_save_file, fetch_save_fingerprint};
+use save_logic::save_diff::diff_saves;
+use save_logic::save_discovery::discover_save_slots;
+use save_logic::save_exporter::export_save_data;
+use save_logic::save_importer::{fetch_save_export, preview_save_import, rebuild_save_from_export};
+use save_logic::save_outpost::{fetch_outpost_saves, get_outpost_save_path};
+use save_logic::save_watcher::{SaveWatcher, WATCH_INTERVAL};
+use save_logic::save_writer::{has_save_conflict, write_save_file};
+use save_logic::script_runner::run_save_script;
+use save_logic::struct_data::{
+    BulkEditReport, CatalogItem, CoopSafetyReport, EditPlan, EditPlanReport, ExportReport,
+    GzipProfile, IdData, IdDatabaseManifest, IdExtractionReport, IdImport, IdMergeReport,
+    IdSearchPage, InventoryChunk, InventoryEditPreview, InventoryTabMapping, ItemCatalog,
+    ItemTransferReport, LayoutReport, OutpostSave, PatchedItems, ProgressionMergePreview,
+    SaveBackup, SaveChangeEvent, SaveConversionReport, SaveDiffReport, SaveExport, SaveFile,
+    SaveFingerprint, SaveImportReport, SaveMetadata, SaveWriteReport, ScriptReport,
+};
+use save_logic::tab_mapping_fetcher::fetch_tab_mapping;
+use std::sync::{Arc, Mutex};
 use tauri::path::BaseDirectory;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+// Defines the event that is emitted when the watched save was changed by another program.
+static SAVE_CHANGED_EVENT: &str = "save-changed";
+
+// Defines the cached item catalog, which is cleared whenever the ID database changes.
+type CatalogCache = Mutex<Option<Arc<ItemCatalog>>>;
 
 #[tauri::command(rename_all = "snake_case")]
 async fn get_ids(app_handle: AppHandle) -> Result<Vec<IdData>, String> {
-    // Initializes resource path where IDs are stored.
-    let resource_path = app_handle
-        .path()
-        .resolve("./IDs/", BaseDirectory::Resource)
-        .unwrap();
+    // Initializes the paths of the ID database.
+    let (store_path, bundled_path) = get_id_database_paths(&app_handle)?;
 
-    match fetch_ids(&resource_path.display().to_string()) {
+    match fetch_database_ids(&store_path, &bundled_path) {
         Ok(id_datas) => Ok(id_datas),
         Err(_) => {
             let empty_vectory: Vec<IdData> = Vec::new();
@@ -49,17 +92,252 @@ async fn get_patched_items(app_handle: AppHandle) -> Result<PatchedItems, String
 }
 
 #[tauri::command(rename_all = "snake_case")]
-fn update_id_folder(app_handle: AppHandle, file_path: &str) {
-    // Initializes resource path where IDs are stored.
-    let resource_path = app_handle
+async fn get_item_catalog(app_handle: AppHandle) -> Result<ItemCatalog, String> {
+    get_catalog(&app_handle)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn search_ids(
+    app_handle: AppHandle,
+    query: &str,
+    categories: Vec<String>,
+    page: usize,
+    page_size: usize,
+) -> Result<IdSearchPage, String> {
+    let catalog: Arc<ItemCatalog> = get_cached_catalog(&app_handle)?;
+
+    Ok(search_catalog(
+        &catalog,
+        query,
+        &categories,
+        page,
+        page_size,
+    ))
+}
+
+fn get_catalog(app_handle: &AppHandle) -> Result<ItemCatalog, String> {
+    get_cached_catalog(app_handle).map(|catalog| catalog.as_ref().clone())
+}
+
+fn get_cached_catalog(app_handle: &AppHandle) -> Result<Arc<ItemCatalog>, String> {
+    let cache = app_handle.state::<CatalogCache>();
+    let mut cached_catalog = cache.lock().map_err(|err| err.to_string())?;
+
+    if let Some(catalog) = cached_catalog.as_ref() {
+        return Ok(catalog.clone());
+    }
+
+    let catalog: Arc<ItemCatalog> = Arc::new(load_catalog(app_handle)?);
+    *cached_catalog = Some(catalog.clone());
+
+    Ok(catalog)
+}
+
+fn clear_catalog_cache(app_handle: &AppHandle) {
+    if let Ok(mut cached_catalog) = app_handle.state::<CatalogCache>().lock() {
+        *cached_catalog = None;
+    }
+}
+
+fn load_catalog(app_handle: &AppHandle) -> Result<ItemCatalog, String> {
+    // Initializes the paths of the ID database and the patched items.
+    let (store_path, bundled_path) = get_id_database_paths(app_handle)?;
+    let id_path: String =
+        get_current_id_path(&store_path, &bundled_path).map_err(|err| err.to_string())?;
+    let patched_path = app_handle
+        .path()
+        .resolve("./Patched_Items/", BaseDirectory::Resource)
+        .map_err(|err| err.to_string())?;
+
+    fetch_item_catalog(&id_path, &patched_path.display().to_string()).map_err(|err| err.to_string())
+}
+
+fn get_id_database_paths(app_handle: &AppHandle) -> Result<(String, String), String> {
+    // Initializes the path of the ID database (app data) and the bundled IDs (resources).
+    let store_path = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|err| err.to_string())?
+        .join(ID_DATABASE_DIRECTORY);
+    let bundled_path = app_handle
         .path()
         .resolve("./IDs/", BaseDirectory::Resource)
-        .unwrap();
+        .map_err(|err| err.to_string())?;
+
+    Ok((
+        store_path.display().to_string(),
+        bundled_path.display().to_string(),
+    ))
+}
+
+fn get_backup_store_path(app_handle: &AppHandle) -> Result<String, String> {
+    // Initializes the path of the backup store (app data).
+    let store_path = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|err| err.to_string())?
+        .join(BACKUP_DIRECTORY);
+
+    Ok(store_path.display().to_string())
+}
 
-    match update_ids(file_path, &resource_path.display().to_string()) {
-        Ok(()) => println!("Successfully replaced directory contents."),
-        Err(err) => eprintln!("Error: {}", err),
+fn get_outpost_path(app_handle: &AppHandle) -> Result<String, String> {
+    // Initializes the resource path where the outpost saves are stored.
+    let outpost_path = app_handle
+        .path()
+        .resolve("./Hawks_Outpost/", BaseDirectory::Resource)
+        .map_err(|err| err.to_string())?;
+
+    Ok(outpost_path.display().to_string())
+}
+
+fn backup_loaded_save(
+    app_handle: &AppHandle,
+    file_path: &str,
+    file_content: &[u8],
+) -> Result<SaveBackup, String> {
+    let store_path: String = get_backup_store_path(app_handle)?;
+
+    create_save_backup(
+        &store_path,
+        file_path,
+        file_content,
+        LOAD_REASON,
+        BACKUP_RETENTION,
+    )
+    .map_err(|err| format!("Error creating the backup of [{}]: {}", file_path, err))
+}
+
+fn get_tab_mapping(app_handle: &AppHandle) -> Result<InventoryTabMapping, String> {
+    // Initializes resource path where the mappings are stored.
+    let resource_path = app_handle
+        .path()
+        .resolve("./Mappings/", BaseDirectory::Resource)
+        .map_err(|err| err.to_string())?;
+
+    fetch_tab_mapping(&resource_path.display().to_string()).map_err(|err| err.to_string())
+}
+
+fn get_item_rules(app_handle: &AppHandle) -> Result<ItemRuleEngine, String> {
+    // Initializes resource path where the mappings are stored.
+    let resource_path = app_handle
+        .path()
+        .resolve("./Mappings/", BaseDirectory::Resource)
+        .map_err(|err| err.to_string())?;
+
+    fetch_item_rules(&resource_path.display().to_string()).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn update_id_folder(app_handle: AppHandle, file_path: &str) -> Result<IdMergeReport, String> {
+    // Initializes the paths of the ID database.
+    let (store_path, bundled_path) = get_id_database_paths(&app_handle)?;
+
+    let import: IdImport = read_id_import(file_path).map_err(|err| err.to_string())?;
+    let report: IdMergeReport =
+        merge_ids(&store_path, &bundled_path, import).map_err(|err| err.to_string())?;
+    clear_catalog_cache(&app_handle);
+
+    Ok(report)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn add_unknown_ids(
+    app_handle: AppHandle,
+    ids: Vec<String>,
+    category: String,
+) -> Result<IdMergeReport, String> {
+    // Initializes the paths of the ID database.
+    let (store_path, bundled_path) = get_id_database_paths(&app_handle)?;
+
+    let import: IdImport = create_harvest_import(ids, &category).map_err(|err| err.to_string())?;
+    let report: IdMergeReport =
+        merge_ids(&store_path, &bundled_path, import).map_err(|err| err.to_string())?;
+    clear_catalog_cache(&app_handle);
+
+    Ok(report)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn import_game_ids(
+    app_handle: AppHandle,
+    install_path: &str,
+    apply: bool,
+) -> Result<IdExtractionReport, String> {
+    let (import, mut report) = extract_game_ids(install_path).map_err(|err| err.to_string())?;
+
+    // Only merge the IDs if the preview was accepted.
+    if apply {
+        // Initializes the paths of the ID database.
+        let (store_path, bundled_path) = get_id_database_paths(&app_handle)?;
+
+        report.merge_report =
+            Some(merge_ids(&store_path, &bundled_path, import).map_err(|err| err.to_string())?);
+        clear_catalog_cache(&app_handle);
     }
+
+    Ok(report)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn get_id_snapshots(app_handle: AppHandle) -> Result<IdDatabaseManifest, String> {
+    // Initializes the paths of the ID database.
+    let (store_path, bundled_path) = get_id_database_paths(&app_handle)?;
+
+    fetch_id_snapshots(&store_path, &bundled_path).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn rollback_id_database(app_handle: AppHandle, version: u32) -> Result<IdDatabaseManifest, String> {
+    // Initializes the paths of the ID database.
+    let (store_path, bundled_path) = get_id_database_paths(&app_handle)?;
+
+    let manifest: IdDatabaseManifest =
+        rollback_ids(&store_path, &bundled_path, version).map_err(|err| err.to_string())?;
+    clear_catalog_cache(&app_handle);
+
+    Ok(manifest)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn discover_saves(save_roots: Vec<String>) -> Result<Vec<SaveMetadata>, String> {
+    Ok(discover_save_slots(&save_roots))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn get_save_backups(
+    app_handle: AppHandle,
+    file_path: Option<String>,
+) -> Result<Vec<SaveBackup>, String> {
+    // Initializes the path of the backup store.
+    let store_path: String = get_backup_store_path(&app_handle)?;
+
+    fetch_save_backups(&store_path, file_path.as_deref()).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn backup_save(app_handle: AppHandle, file_path: &str) -> Result<SaveBackup, String> {
+    // Initializes the path of the backup store.
+    let store_path: String = get_backup_store_path(&app_handle)?;
+
+    let file_content: Vec<u8> = get_contents_from_file(file_path).map_err(|err| err.to_string())?;
+
+    create_save_backup(
+        &store_path,
+        file_path,
+        &file_content,
+        MANUAL_REASON,
+        BACKUP_RETENTION,
+    )
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn restore_backup(app_handle: AppHandle, backup_id: u32) -> Result<SaveBackup, String> {
+    // Initializes the path of the backup store.
+    let store_path: String = get_backup_store_path(&app_handle)?;
+
+    restore_save_backup(&store_path, backup_id).map_err(|err| err.to_string())
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -71,19 +349,17 @@ fn load_save(
 ) -> Result<SaveFile, String> {
     // Initializes the logger.
     let mut logger: ConsoleLogger = ConsoleLogger::new();
-    // Initializes resource path where IDs are stored.
-    let resource_path: std::path::PathBuf = app_handle
-        .path()
-        .resolve("./IDs/", BaseDirectory::Resource)
-        .unwrap();
-
-    // Initializes IDs
-    let ids: Result<Vec<IdData>, Box<dyn Error>> = fetch_ids(&resource_path.display().to_string());
+    // Initializes the item catalog.
+    let catalog: ItemCatalog = get_catalog(&app_handle)?;
+    // Initializes the inventory tab mapping.
+    let tab_mapping: InventoryTabMapping = get_tab_mapping(&app_handle)?;
+    // Initializes the item classification rules.
+    let item_rules: ItemRuleEngine = get_item_rules(&app_handle)?;
 
     let file_content: Vec<u8> = get_contents_from_file(&file_path).unwrap();
 
     // Checks if the file is compressed.
-    if file_content[0] == 31 && file_content[1] == 139 {
+    if is_compressed_content(&file_content) {
         let save_file = load_save_pc(app_handle, file_path, is_debugging, has_automatic_backup);
         match save_file {
             Ok(save) => return Ok(save),
@@ -93,20 +369,26 @@ fn load_save(
 
     // Creates a backup file if the settings are set to true.
     if has_automatic_backup {
-        create_backup_from_file(&file_path, &file_content);
+        backup_loaded_save(&app_handle, file_path, &file_content)?;
     }
 
+    let file_hash: String = get_content_hash(&file_content);
     let save_file = load_save_file(
         &file_path,
         file_content,
-        ids.unwrap(),
+        catalog,
+        tab_mapping,
+        item_rules,
         &mut logger,
         is_debugging,
         false,
     );
 
     match save_file {
-        Ok(save) => return Ok(save),
+        Ok(mut save) => {
+            save.file_hash = file_hash;
+            return Ok(save);
+        }
         Err(err) => return Err(err.to_string()),
     }
 }
@@ -120,26 +402,26 @@ fn load_save_pc(
 ) -> Result<SaveFile, String> {
     // Initializes the logger.
     let mut logger: ConsoleLogger = ConsoleLogger::new();
-    // Initializes resource path where IDs are stored.
-    let resource_path = app_handle
-        .path()
-        .resolve("./IDs/", BaseDirectory::Resource)
-        .unwrap();
-
-    // Initializes IDs
-    let ids = fetch_ids(&resource_path.display().to_string()).unwrap();
+    // Initializes the item catalog.
+    let catalog: ItemCatalog = get_catalog(&app_handle)?;
+    // Initializes the inventory tab mapping.
+    let tab_mapping: InventoryTabMapping = get_tab_mapping(&app_handle)?;
+    // Initializes the item classification rules.
+    let item_rules: ItemRuleEngine = get_item_rules(&app_handle)?;
 
     let file_content: Vec<u8> = get_contents_from_file(&file_path).unwrap();
 
     // Creates a backup file if the settings are set to true.
     if has_automatic_backup {
-        create_backup_from_file(&file_path, &file_content);
+        backup_loaded_save(&app_handle, file_path, &file_content)?;
     }
 
     let save_file = load_save_file_pc(
         &file_path,
         file_content,
-        ids,
+        catalog,
+        tab_mapping,
+        item_rules,
         &mut logger,
         is_debugging,
         true,
@@ -152,10 +434,356 @@ fn load_save_pc(
 }
 
 #[tauri::command(rename_all = "snake_case")]
-async fn compress_save(data: Vec<u8>) -> Result<Vec<u8>, String> {
-    let compressed: Vec<u8> = export_save_for_pc(&data);
+fn discover_save_layout(
+    app_handle: AppHandle,
+    file_path: &str,
+    is_debugging: bool,
+) -> Result<LayoutReport, String> {
+    // Initializes the logger.
+    let mut logger: ConsoleLogger = ConsoleLogger::new();
+    // Initializes the paths of the ID database.
+    let (store_path, bundled_path) = get_id_database_paths(&app_handle)?;
+
+    // Initializes IDs
+    let ids: Vec<IdData> = fetch_database_ids(&store_path, &bundled_path).unwrap_or_default();
+
+    let mut file_content: Vec<u8> =
+        get_contents_from_file(&file_path).map_err(|err| err.to_string())?;
+
+    // Decompresses the file if it is a PC save.
+    if is_compressed_content(&file_content) {
+        file_content = decompress_save_content(&file_content).map_err(|err| err.to_string())?;
+    }
+
+    match discover_layout(file_path, &file_content, &ids, &mut logger, is_debugging) {
+        Ok(report) => Ok(report),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn check_coop_safety(save_file: SaveFile) -> Result<CoopSafetyReport, String> {
+    Ok(find_coop_issues(&save_file))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn fix_coop_safety(
+    app_handle: AppHandle,
+    save_file: SaveFile,
+    item_indices: Vec<usize>,
+    replacement_id: Option<String>,
+) -> Result<Vec<u8>, String> {
+    // Initializes the replacement (if the items should not be removed).
+    let replacement: Option<CatalogItem> = match replacement_id {
+        Some(id) => {
+            let catalog: ItemCatalog = get_catalog(&app_handle)?;
+
+            match catalog.items.get(&id.to_lowercase()) {
+                Some(item) => Some(item.clone()),
+                None => return Err(format!("The replacement [{}] is not a known ID.", id)),
+            }
+        }
+        None => None,
+    };
+
+    fix_coop_issues(&save_file, &item_indices, replacement.as_ref()).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn export_save(
+    save_file: SaveFile,
+    output_dir: &str,
+    format: &str,
+) -> Result<ExportReport, String> {
+    export_save_data(&save_file, output_dir, format).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn preview_csv_edits(
+    save_file: SaveFile,
+    csv_path: &str,
+) -> Result<InventoryEditPreview, String> {
+    preview_inventory_csv(&save_file, csv_path).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn apply_csv_edits(save_file: SaveFile, csv_path: &str) -> Result<Vec<u8>, String> {
+    apply_inventory_csv(&save_file, csv_path).map_err(|err| err.to_string())
+}
 
-    Ok(compressed)
+#[tauri::command(rename_all = "snake_case")]
+fn get_save_export(json_path: &str) -> Result<SaveExport, String> {
+    fetch_save_export(json_path).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn preview_json_edits(
+    save_file: SaveFile,
+    export: SaveExport,
+) -> Result<SaveImportReport, String> {
+    Ok(preview_save_import(&save_file, &export))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn apply_json_edits(
+    app_handle: AppHandle,
+    save_file: SaveFile,
+    export: SaveExport,
+) -> Result<(SaveImportReport, SaveFile), String> {
+    // Initializes the logger.
+    let mut logger: ConsoleLogger = ConsoleLogger::new();
+    // Initializes the item catalog.
+    let catalog: ItemCatalog = get_catalog(&app_handle)?;
+    // Initializes the inventory tab mapping.
+    let tab_mapping: InventoryTabMapping = get_tab_mapping(&app_handle)?;
+    // Initializes the item classification rules.
+    let item_rules: ItemRuleEngine = get_item_rules(&app_handle)?;
+
+    rebuild_save_from_export(
+        &save_file,
+        &export,
+        catalog,
+        tab_mapping,
+        item_rules,
+        &mut logger,
+    )
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn get_edit_plan(plan_path: &str) -> Result<EditPlan, String> {
+    fetch_edit_plan(plan_path).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn run_plan(
+    app_handle: AppHandle,
+    plan_path: &str,
+    target_path: &str,
+    dry_run: bool,
+) -> Result<EditPlanReport, String> {
+    // Initializes the logger.
+    let mut logger: ConsoleLogger = ConsoleLogger::new();
+    // Initializes the item catalog.
+    let catalog: ItemCatalog = get_catalog(&app_handle)?;
+    // Initializes the inventory tab mapping.
+    let tab_mapping: InventoryTabMapping = get_tab_mapping(&app_handle)?;
+    // Initializes the item classification rules.
+    let item_rules: ItemRuleEngine = get_item_rules(&app_handle)?;
+    // Initializes the path of the backup store.
+    let store_path: String = get_backup_store_path(&app_handle)?;
+
+    let plan: EditPlan = fetch_edit_plan(plan_path).map_err(|err| err.to_string())?;
+
+    run_edit_plan(
+        &plan,
+        target_path,
+        &catalog,
+        &tab_mapping,
+        &item_rules,
+        &store_path,
+        &mut logger,
+        dry_run,
+    )
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn run_script(
+    save_file: SaveFile,
+    script_path: &str,
+    sandbox_path: Option<String>,
+) -> Result<(ScriptReport, Vec<u8>), String> {
+    run_save_script(&save_file, script_path, sandbox_path.as_deref()).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn bulk_edit(
+    save_file: SaveFile,
+    filter: &str,
+    field: &str,
+    expression: &str,
+) -> Result<(BulkEditReport, Vec<u8>), String> {
+    bulk_edit_items(&save_file, filter, field, expression).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn transfer_item(
+    app_handle: AppHandle,
+    source_save: SaveFile,
+    target_save: SaveFile,
+    source_index: usize,
+    target_tab: &str,
+) -> Result<(ItemTransferReport, SaveFile), String> {
+    // Initializes the logger.
+    let mut logger: ConsoleLogger = ConsoleLogger::new();
+    // Initializes the item catalog.
+    let catalog: ItemCatalog = get_catalog(&app_handle)?;
+    // Initializes the inventory tab mapping.
+    let tab_mapping: InventoryTabMapping = get_tab_mapping(&app_handle)?;
+    // Initializes the item classification rules.
+    let item_rules: ItemRuleEngine = get_item_rules(&app_handle)?;
+
+    transfer_item_between_saves(
+        &source_save,
+        &target_save,
+        source_index,
+        target_tab,
+        catalog,
+        tab_mapping,
+        item_rules,
+        &mut logger,
+    )
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn preview_merge_progression(
+    source_save: SaveFile,
+    target_save: SaveFile,
+) -> Result<ProgressionMergePreview, String> {
+    preview_progression_merge(&source_save, &target_save).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn merge_progression(
+    app_handle: AppHandle,
+    source_save: SaveFile,
+    target_save: SaveFile,
+) -> Result<(ProgressionMergePreview, SaveFile), String> {
+    // Initializes the logger.
+    let mut logger: ConsoleLogger = ConsoleLogger::new();
+    // Initializes the item catalog.
+    let catalog: ItemCatalog = get_catalog(&app_handle)?;
+    // Initializes the inventory tab mapping.
+    let tab_mapping: InventoryTabMapping = get_tab_mapping(&app_handle)?;
+    // Initializes the item classification rules.
+    let item_rules: ItemRuleEngine = get_item_rules(&app_handle)?;
+
+    merge_progression_between_saves(
+        &source_save,
+        &target_save,
+        catalog,
+        tab_mapping,
+        item_rules,
+        &mut logger,
+    )
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn compress_save(
+    data: Vec<u8>,
+    gzip_profile: Option<GzipProfile>,
+) -> Result<Vec<u8>, String> {
+    compress_save_content(&data, gzip_profile.as_ref()).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn write_save(
+    watcher: State<Mutex<SaveWatcher>>,
+    save_file: SaveFile,
+    file_path: &str,
+    is_forced: bool,
+) -> Result<SaveWriteReport, String> {
+    let report: SaveWriteReport =
+        write_save_file(&save_file, file_path, is_forced).map_err(|err| err.to_string())?;
+
+    // The written save is not reported as external change.
+    if let Ok(watcher) = watcher.lock() {
+        watcher.acknowledge(file_path, &report.file_hash);
+    }
+
+    Ok(report)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn check_save_conflict(save_file: SaveFile, file_path: &str) -> Result<bool, String> {
+    has_save_conflict(&save_file, file_path).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn watch_save(
+    app_handle: AppHandle,
+    watcher: State<Mutex<SaveWatcher>>,
+    file_path: &str,
+    file_hash: &str,
+) -> Result<(), String> {
+    let mut watcher = watcher.lock().map_err(|err| err.to_string())?;
+
+    watcher
+        .watch(
+            file_path,
+            file_hash,
+            WATCH_INTERVAL,
+            move |event: SaveChangeEvent| {
+                let _ = app_handle.emit(SAVE_CHANGED_EVENT, event);
+            },
+        )
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn unwatch_save(watcher: State<Mutex<SaveWatcher>>) -> Result<(), String> {
+    watcher.lock().map_err(|err| err.to_string())?.stop();
+
+    Ok(())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+async fn diff_save_with_disk(
+    app_handle: AppHandle,
+    save_file: SaveFile,
+) -> Result<SaveDiffReport, String> {
+    // Initializes the logger.
+    let mut logger: ConsoleLogger = ConsoleLogger::new();
+    // Initializes the item catalog.
+    let catalog: ItemCatalog = get_catalog(&app_handle)?;
+    // Initializes the inventory tab mapping.
+    let tab_mapping: InventoryTabMapping = get_tab_mapping(&app_handle)?;
+    // Initializes the item classification rules.
+    let item_rules: ItemRuleEngine = get_item_rules(&app_handle)?;
+
+    let disk_save: SaveFile = load_save_from_path(
+        &save_file.path,
+        catalog.clone(),
+        tab_mapping.clone(),
+        item_rules.clone(),
+        &mut logger,
+        false,
+    )
+    .map_err(|err| err.to_string())?;
+
+    // The session is analysed again, because the edits only change the content of the save.
+    let mut session_save: SaveFile = load_save_file(
+        &save_file.path,
+        save_file.file_content.clone(),
+        catalog,
+        tab_mapping,
+        item_rules,
+        &mut logger,
+        false,
+        save_file.is_compressed,
+    )
+    .map_err(|err| err.to_string())?;
+    session_save.file_hash = save_file.file_hash;
+    session_save.gzip_profile = save_file.gzip_profile;
+
+    Ok(diff_saves(&session_save, &disk_save))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn get_save_fingerprint(file_path: &str) -> Result<SaveFingerprint, String> {
+    fetch_save_fingerprint(file_path).map_err(|err| err.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+fn convert_save(
+    file_path: &str,
+    output_path: &str,
+    format: &str,
+) -> Result<SaveConversionReport, String> {
+    convert_save_file(file_path, output_path, format).map_err(|err| err.to_string())
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -308,20 +936,10 @@ async fn add_crc_bypass_files(app_handle: AppHandle, file_path: &str) -> Result<
 
 #[tauri::command(rename_all = "snake_case")]
 async fn get_outpost_saves(app_handle: AppHandle) -> Result<Vec<OutpostSave>, String> {
-    // Initializes resource path where IDs are stored.
-    let resource_path_result = app_handle
-        .path()
-        .resolve("./Hawks_Outpost/", BaseDirectory::Resource);
-
-    let resource_path = match resource_path_result {
-        Ok(path) => path,
-        Err(err) => {
-            println!("Error: {}", err);
-            return Err(err.to_string());
-        }
-    };
+    // Initializes the path of the outpost saves.
+    let outpost_path: String = get_outpost_path(&app_handle)?;
 
-    match fetch_outpost_saves(app_handle, &resource_path.display().to_string()) {
+    match fetch_outpost_saves(&outpost_path) {
         Ok(result) => Ok(result),
         Err(err) => {
             println!("Error: {}", err);
@@ -330,6 +948,18 @@ async fn get_outpost_saves(app_handle: AppHandle) -> Result<Vec<OutpostSave>, St
     }
 }
 
+#[tauri::command(rename_all = "snake_case")]
+async fn load_outpost_save(app_handle: AppHandle, save_path: &str) -> Result<SaveFile, String> {
+    // Initializes the path of the outpost saves.
+    let outpost_path: String = get_outpost_path(&app_handle)?;
+
+    let file_path: String =
+        get_outpost_save_path(&outpost_path, save_path).map_err(|err| err.to_string())?;
+
+    // The bundled saves are never changed, therefore no backup is created.
+    load_save(app_handle, &file_path, false, false)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -337,12 +967,47 @@ pub fn run() {
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
+        .manage(Mutex::new(SaveWatcher::new()))
+        .manage(CatalogCache::new(None))
         .invoke_handler(tauri::generate_handler![
             get_ids,
+            get_item_catalog,
+            search_ids,
             update_id_folder,
+            add_unknown_ids,
+            import_game_ids,
+            get_id_snapshots,
+            rollback_id_database,
+            discover_saves,
+            get_save_backups,
+            backup_save,
+            restore_backup,
             load_save,
             load_save_pc,
+            discover_save_layout,
+            check_coop_safety,
+            fix_coop_safety,
+            export_save,
+            preview_csv_edits,
+            apply_csv_edits,
+            get_edit_plan,
+            run_plan,
+            run_script,
+            bulk_edit,
+            transfer_item,
+            preview_merge_progression,
+            merge_progression,
+            get_save_export,
+            preview_json_edits,
+            apply_json_edits,
             compress_save,
+            write_save,
+            check_save_conflict,
+            watch_save,
+            unwatch_save,
+            diff_save_with_disk,
+            get_save_fingerprint,
+            convert_save,
             handle_edit_skill,
             handle_edit_item_chunk,
             remove_item,
@@ -354,6 +1019,7 @@ pub fn run() {
             open_knowledge_window,
             add_crc_bypass_files,
             get_outpost_saves,
+            load_outpost_save,
             get_patched_items
         ])
         .run(tauri::generate_context!())
@@ -2,5 +2,36 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // Runs the command line interface if a command is passed, otherwise starts the editor.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        attach_parent_console();
+    }
+
+    if let Some(exit_code) = savegame_editor_lib::run_cli(&args) {
+        std::process::exit(exit_code);
+    }
+
     savegame_editor_lib::run()
 }
+
+/// Represents a method for attaching the output to the console the editor was started from.
+/// Release builds on Windows have no console of their own, so the output of the CLI would be lost.
+#[cfg(windows)]
+fn attach_parent_console() {
+    // Defines the process ID that stands for the parent process.
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // Fails if the editor was not started from a console (or already has one), which is fine.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
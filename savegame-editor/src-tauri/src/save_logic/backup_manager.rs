@@ -0,0 +1,316 @@
+//! Stores backups of saves inside the app data directory.
+//!
+//! Every backup is listed in the manifest of the store with the path of the save, the time it was created and the
+//! SHA-256 hash of its content. The content itself is stored once per hash (`files/<hash>.sav`) and a save that is
+//! loaded again without changes does not create a new backup. For each save only the newest backups are kept, files
+//! that are no longer referenced by any backup are removed. Restoring a backup creates a backup of the current save
+//! first, so every restore can be undone.
+
+use crate::save_logic::file_analyser::{get_content_hash, get_contents_from_file};
+use crate::save_logic::save_writer::write_file_atomically;
+use crate::save_logic::struct_data::{BackupManifest, SaveBackup};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the directory of the backup store inside the app data directory.
+pub static BACKUP_DIRECTORY: &str = "Save_Backups";
+
+// Defines the file and directory names inside the backup store.
+static MANIFEST_FILE: &str = "manifest.json";
+static FILE_DIRECTORY: &str = "files";
+static FILE_EXTENSION: &str = "sav";
+
+// Defines the reasons a backup is created for.
+pub static LOAD_REASON: &str = "load";
+pub static MANUAL_REASON: &str = "manual";
+pub static RESTORE_REASON: &str = "restore";
+
+// Defines the amount of backups that is kept for each save.
+pub const BACKUP_RETENTION: usize = 10;
+
+/// Represents a method for creating a backup of a save.
+/// If the latest backup of the save has the same content, no new backup is created.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the backup store (inside the app data directory).
+/// - `save_path`: The path of the save.
+/// - `file_content`: The content of the save (as stored on disk).
+/// - `reason`: The reason the backup is created for.
+/// - `retention`: The amount of backups that is kept for the save.
+///
+/// ### Returns `SaveBackup`
+/// The new backup or the existing backup with the same content.
+pub fn create_save_backup(
+    store_path: &str,
+    save_path: &str,
+    file_content: &[u8],
+    reason: &str,
+    retention: usize,
+) -> Result<SaveBackup> {
+    store_save_backup(store_path, save_path, file_content, reason, retention, None)
+}
+
+/// Represents a method for storing a backup of a save while keeping a backup that is in use.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the backup store.
+/// - `save_path`: The path of the save.
+/// - `file_content`: The content of the save (as stored on disk).
+/// - `reason`: The reason the backup is created for.
+/// - `retention`: The amount of backups that is kept for the save.
+/// - `kept_id`: The ID of a backup that is never removed by the retention (`None` keeps no extra backup).
+///
+/// ### Returns `SaveBackup`
+/// The new backup or the existing backup with the same content.
+fn store_save_backup(
+    store_path: &str,
+    save_path: &str,
+    file_content: &[u8],
+    reason: &str,
+    retention: usize,
+    kept_id: Option<u32>,
+) -> Result<SaveBackup> {
+    let mut manifest: BackupManifest = load_manifest(store_path)?;
+    let save_path: String = normalize_save_path(save_path);
+    let file_hash: String = get_content_hash(file_content);
+
+    // The content is stored once per hash, so only the latest backup of the save is checked.
+    if let Some(backup) = manifest
+        .backups
+        .iter()
+        .filter(|b| b.save_path == save_path)
+        .max_by_key(|b| b.id)
+    {
+        if backup.file_hash == file_hash {
+            return Ok(backup.clone());
+        }
+    }
+
+    let file_path: PathBuf = get_backup_file_path(store_path, &file_hash);
+
+    if !file_path.is_file() {
+        fs::create_dir_all(Path::new(store_path).join(FILE_DIRECTORY))?;
+        write_file_atomically(&file_path, file_content)?;
+    }
+
+    let backup: SaveBackup = SaveBackup::new(
+        get_next_id(&manifest),
+        save_path.clone(),
+        get_timestamp(),
+        file_hash,
+        file_content.len(),
+        reason.to_string(),
+    );
+    manifest.backups.push(backup.clone());
+
+    let unused_hashes: Vec<String> =
+        apply_retention(&mut manifest, &save_path, retention.max(1), kept_id);
+    write_manifest(store_path, &manifest)?;
+
+    // The files are only removed once the manifest no longer references them.
+    for file_hash in unused_hashes {
+        let _ = fs::remove_file(get_backup_file_path(store_path, &file_hash));
+    }
+
+    Ok(backup)
+}
+
+/// Represents a method for fetching the backups of the store (newest first).
+///
+/// ### Parameter
+/// - `store_path`: The directory of the backup store.
+/// - `save_path`: The path of a save to only list its backups (`None` lists all backups).
+///
+/// ### Returns `Vec<SaveBackup>`
+/// The backups.
+pub fn fetch_save_backups(store_path: &str, save_path: Option<&str>) -> Result<Vec<SaveBackup>> {
+    let save_path: Option<String> = save_path.map(normalize_save_path);
+    let mut backups: Vec<SaveBackup> = load_manifest(store_path)?
+        .backups
+        .into_iter()
+        .filter(|b| save_path.as_ref().is_none_or(|path| b.save_path == *path))
+        .collect();
+
+    backups.sort_by_key(|b| Reverse(b.id));
+    Ok(backups)
+}
+
+/// Represents a method for restoring a backup to the path of its save.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the backup store.
+/// - `backup_id`: The ID of the backup.
+///
+/// ### Returns `SaveBackup`
+/// The restored backup.
+pub fn restore_save_backup(store_path: &str, backup_id: u32) -> Result<SaveBackup> {
+    let backup: SaveBackup = load_manifest(store_path)?
+        .backups
+        .into_iter()
+        .find(|b| b.id == backup_id)
+        .ok_or(format!("There is no backup with the ID [{}].", backup_id))?;
+
+    let file_path: PathBuf = get_backup_file_path(store_path, &backup.file_hash);
+    let file_content: Vec<u8> = get_contents_from_file(&file_path.display().to_string())
+        .map_err(|e| format!("Error reading the backup [{}]: {}", backup_id, e))?;
+
+    if get_content_hash(&file_content) != backup.file_hash {
+        return Err(format!("The file of the backup [{}] is damaged.", backup_id).into());
+    }
+
+    // The current save is backed up first, so the restore can be undone.
+    // The restored backup is kept, even if it is the oldest backup of the save.
+    if let Ok(current_content) = get_contents_from_file(&backup.save_path) {
+        store_save_backup(
+            store_path,
+            &backup.save_path,
+            &current_content,
+            RESTORE_REASON,
+            BACKUP_RETENTION,
+            Some(backup_id),
+        )?;
+    }
+
+    write_file_atomically(Path::new(&backup.save_path), &file_content)?;
+
+    Ok(backup)
+}
+
+/// Represents a method for removing the oldest backups of a save from the manifest.
+///
+/// ### Parameter
+/// - `manifest`: The manifest of the backup store.
+/// - `save_path`: The path of the save.
+/// - `retention`: The amount of backups that is kept for the save.
+/// - `kept_id`: The ID of a backup that is kept in addition to the newest backups.
+///
+/// ### Returns `Vec<String>`
+/// The hashes of the files that are no longer used by any backup.
+fn apply_retention(
+    manifest: &mut BackupManifest,
+    save_path: &str,
+    retention: usize,
+    kept_id: Option<u32>,
+) -> Vec<String> {
+    let mut save_ids: Vec<u32> = manifest
+        .backups
+        .iter()
+        .filter(|b| b.save_path == save_path && Some(b.id) != kept_id)
+        .map(|b| b.id)
+        .collect();
+
+    if save_ids.len() <= retention {
+        return Vec::new();
+    }
+
+    save_ids.sort_unstable();
+    let removed_ids: HashSet<u32> = save_ids[..save_ids.len() - retention]
+        .iter()
+        .copied()
+        .collect();
+    let removed_hashes: Vec<String> = manifest
+        .backups
+        .iter()
+        .filter(|b| removed_ids.contains(&b.id))
+        .map(|b| b.file_hash.clone())
+        .collect();
+
+    manifest.backups.retain(|b| !removed_ids.contains(&b.id));
+
+    // Files can be shared by backups of different saves.
+    let unused_hashes: HashSet<String> = removed_hashes
+        .into_iter()
+        .filter(|file_hash| !manifest.backups.iter().any(|b| b.file_hash == *file_hash))
+        .collect();
+
+    unused_hashes.into_iter().collect()
+}
+
+/// Represents a method for loading the manifest of the backup store.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the backup store.
+///
+/// ### Returns `BackupManifest`
+/// The manifest (empty if the store does not exist yet).
+fn load_manifest(store_path: &str) -> Result<BackupManifest> {
+    let manifest_path: PathBuf = Path::new(store_path).join(MANIFEST_FILE);
+
+    if !manifest_path.is_file() {
+        return Ok(BackupManifest::new(Vec::new()));
+    }
+
+    let file_content = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Error reading the backup manifest: {}", e))?;
+
+    serde_json::from_str(&file_content)
+        .map_err(|e| format!("Error parsing the backup manifest: {}", e).into())
+}
+
+/// Represents a method for writing the manifest of the backup store.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the backup store.
+/// - `manifest`: The manifest that should be written.
+fn write_manifest(store_path: &str, manifest: &BackupManifest) -> Result<()> {
+    fs::create_dir_all(store_path)?;
+    write_file_atomically(
+        &Path::new(store_path).join(MANIFEST_FILE),
+        serde_json::to_string_pretty(manifest)?.as_bytes(),
+    )
+}
+
+/// Represents a method for getting the file of a backup.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the backup store.
+/// - `file_hash`: The hash of the content of the backup.
+///
+/// ### Returns `PathBuf`
+/// The path of the file.
+fn get_backup_file_path(store_path: &str, file_hash: &str) -> PathBuf {
+    Path::new(store_path)
+        .join(FILE_DIRECTORY)
+        .join(format!("{}.{}", file_hash, FILE_EXTENSION))
+}
+
+/// Represents a method for normalizing the path of a save, so every path of the same save has the same backups.
+///
+/// ### Parameter
+/// - `save_path`: The path of the save.
+///
+/// ### Returns `String`
+/// The absolute path of the save (or the given path if the save does not exist).
+fn normalize_save_path(save_path: &str) -> String {
+    fs::canonicalize(save_path)
+        .map(|path| path.display().to_string())
+        .unwrap_or(save_path.to_string())
+}
+
+/// Represents a method for getting the next free backup ID.
+///
+/// ### Parameter
+/// - `manifest`: The manifest of the backup store.
+///
+/// ### Returns `u32`
+/// The next backup ID.
+fn get_next_id(manifest: &BackupManifest) -> u32 {
+    manifest.backups.iter().map(|b| b.id).max().unwrap_or(0) + 1
+}
+
+/// Represents a method for getting the current unix timestamp.
+///
+/// ### Returns `u64`
+/// The seconds since the unix epoch.
+fn get_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,418 @@
+//! Applies a single edit to every inventory item that matches a filter expression.
+//!
+//! A filter is a list of terms that all have to match, every term can be negated with `!`:
+//!
+//! ```text
+//! tab:Weapons category:Melee,Firearm id:wpn_* level:5..16 has_mod:*fire* !has_mod:*
+//! ```
+//!
+//! - `tab`, `category`: One of the comma separated names (case-insensitive, values with spaces in double quotes).
+//! - `id`: One of the comma separated ID patterns (`*` and `?` wildcards).
+//! - `level`: A single level or an inclusive range (`5..16`, `5..`, `..16`).
+//! - `has_mod`: At least one attached mod matches one of the comma separated patterns.
+//!
+//! The new value of the edited field (level, seed, amount or durability) is either a number or an expression
+//! (Rhai) over the current values of the item, for example `value * 2`, `level + 5` or `max(amount, 100)`.
+//! The variables `value` (the edited field), `level`, `seed`, `amount` and `durability` are available.
+
+use crate::save_logic::edit_plan::compile_id_pattern;
+use crate::save_logic::file_analyser::{
+    clean_sgd_name, edit_inventory_item_chunk, find_byte_changes,
+};
+use crate::save_logic::struct_data::{BulkEditChange, BulkEditReport, InventoryItem, SaveFile};
+use regex::Regex;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, Scope, AST};
+use std::error::Error;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the fields that can be edited in bulk.
+static LEVEL_FIELD: &str = "level";
+static SEED_FIELD: &str = "seed";
+static AMOUNT_FIELD: &str = "amount";
+static DURABILITY_FIELD: &str = "durability";
+
+// Defines the limits of a single expression evaluation.
+const MAX_OPERATIONS: u64 = 10_000;
+const MAX_EXPRESSION_DEPTH: usize = 32;
+
+/// A single term of a filter expression.
+enum FilterTerm {
+    Tab(Vec<String>),
+    Category(Vec<String>),
+    Id(Vec<Regex>),
+    Level(u16, u16),
+    HasMod(Vec<Regex>),
+}
+
+/// A parsed filter expression (every term has to match, negated terms must not match).
+pub struct ItemFilter {
+    terms: Vec<(FilterTerm, bool)>,
+}
+
+impl ItemFilter {
+    /// Represents a method for checking whether an inventory item matches the filter.
+    ///
+    /// ### Parameter
+    /// - `tab`: The inventory tab of the item.
+    /// - `item`: The inventory item.
+    ///
+    /// ### Returns `bool`
+    /// Indicates whether the item is selected.
+    pub fn matches(&self, tab: &str, item: &InventoryItem) -> bool {
+        self.terms.iter().all(|(term, negated)| {
+            let is_match: bool = match term {
+                FilterTerm::Tab(names) => names.iter().any(|name| name.eq_ignore_ascii_case(tab)),
+                FilterTerm::Category(names) => names
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(&item.catalog.category)),
+                FilterTerm::Id(patterns) => patterns.iter().any(|p| p.is_match(&item.name)),
+                FilterTerm::Level(min, max) => (*min..=*max).contains(&item.chunk_data.level_value),
+                FilterTerm::HasMod(patterns) => item.mod_data.iter().any(|mod_item| {
+                    let mod_name: String = clean_sgd_name(&mod_item.name);
+                    patterns.iter().any(|p| p.is_match(&mod_name))
+                }),
+            };
+
+            is_match != *negated
+        })
+    }
+}
+
+/// Represents a method for parsing a filter expression.
+/// An empty filter selects every inventory item.
+///
+/// ### Parameter
+/// - `filter`: The filter expression.
+///
+/// ### Returns `ItemFilter`
+/// The parsed filter.
+pub fn parse_item_filter(filter: &str) -> Result<ItemFilter> {
+    let mut terms: Vec<(FilterTerm, bool)> = Vec::new();
+
+    for token in split_filter_terms(filter)? {
+        let (token, negated): (&str, bool) = match token.strip_prefix('!') {
+            Some(token) => (token, true),
+            None => (token.as_str(), false),
+        };
+        let (key, value) = token.split_once(':').ok_or(format!(
+            "The filter term [{}] is not written as <key>:<value>.",
+            token
+        ))?;
+        let values: Vec<String> = value
+            .split(',')
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+
+        if values.is_empty() {
+            return Err(format!("The filter term [{}] has no value.", token).into());
+        }
+
+        let term: FilterTerm = match key.trim().to_lowercase().as_str() {
+            "tab" => FilterTerm::Tab(values),
+            "category" => FilterTerm::Category(values),
+            "id" => FilterTerm::Id(compile_patterns(&values)?),
+            "has_mod" => FilterTerm::HasMod(compile_patterns(&values)?),
+            "level" => {
+                let (min, max) = parse_level_range(value.trim())?;
+                FilterTerm::Level(min, max)
+            }
+            _ => {
+                return Err(format!(
+                    "The filter key [{}] is unknown (use tab, category, id, level or has_mod).",
+                    key
+                )
+                .into())
+            }
+        };
+
+        terms.push((term, negated));
+    }
+
+    Ok(ItemFilter { terms })
+}
+
+/// Represents a method for setting a field of every inventory item that matches a filter.
+/// Nothing is changed if the value of any matched item is invalid.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+/// - `filter`: The filter expression.
+/// - `field`: The edited field (`level`, `seed`, `amount` or `durability`).
+/// - `expression`: The new value (a number or an expression over the current values).
+///
+/// ### Returns `(BulkEditReport, Vec<u8>)`
+/// The changes of the edit and the new content of the save file.
+pub fn bulk_edit_items(
+    save_file: &SaveFile,
+    filter: &str,
+    field: &str,
+    expression: &str,
+) -> Result<(BulkEditReport, Vec<u8>)> {
+    let field: String = field.trim().to_lowercase();
+
+    if ![LEVEL_FIELD, SEED_FIELD, AMOUNT_FIELD, DURABILITY_FIELD].contains(&field.as_str()) {
+        return Err(format!(
+            "The field [{}] cannot be edited (use level, seed, amount or durability).",
+            field
+        )
+        .into());
+    }
+
+    let item_filter: ItemFilter = parse_item_filter(filter)?;
+    let engine: Engine = create_expression_engine();
+    let ast: AST = engine
+        .compile_expression(expression.trim())
+        .map_err(|e| format!("The expression [{}] is invalid: {}", expression.trim(), e))?;
+
+    let mut report: BulkEditReport = BulkEditReport::new(
+        filter.to_string(),
+        field.clone(),
+        expression.trim().to_string(),
+    );
+    let mut content: Vec<u8> = save_file.file_content.clone();
+
+    for row in save_file.items.iter() {
+        for item in row.inventory_items.iter() {
+            if !item_filter.matches(&row.name, item) {
+                continue;
+            }
+
+            report.matched_items += 1;
+
+            let chunk = &item.chunk_data;
+            let durability: f32 =
+                f32::from_le_bytes(chunk.durability.clone().try_into().unwrap_or_default());
+            let (mut level, mut seed, mut amount, mut new_durability) = (
+                chunk.level_value,
+                chunk.seed_value,
+                chunk.amount_value,
+                durability,
+            );
+
+            let current: Dynamic = match field.as_str() {
+                "level" => Dynamic::from_int(level as i64),
+                "seed" => Dynamic::from_int(seed as i64),
+                "amount" => Dynamic::from_int(amount as i64),
+                _ => Dynamic::from_float(durability as f64),
+            };
+
+            let mut scope: Scope = Scope::new();
+            scope.push_constant("value", current);
+            scope.push_constant("level", level as i64);
+            scope.push_constant("seed", seed as i64);
+            scope.push_constant("amount", amount as i64);
+            scope.push_constant("durability", durability as f64);
+
+            let result: Dynamic = engine
+                .eval_ast_with_scope::<Dynamic>(&mut scope, &ast)
+                .map_err(|e| {
+                    format!(
+                        "The expression [{}] failed for the item [{}] at offset [{}]: {}",
+                        report.expression, item.name, item.index, e
+                    )
+                })?;
+            let out_of_range = || {
+                format!(
+                    "The expression [{}] produced [{}] for the item [{}] at offset [{}], which is not a valid {}.",
+                    report.expression, result, item.name, item.index, field
+                )
+            };
+
+            let (old_value, new_value): (String, String) = match field.as_str() {
+                "level" => {
+                    level = read_integer(&result).ok_or_else(out_of_range)?;
+                    (chunk.level_value.to_string(), level.to_string())
+                }
+                "seed" => {
+                    seed = read_integer(&result).ok_or_else(out_of_range)?;
+                    (chunk.seed_value.to_string(), seed.to_string())
+                }
+                "amount" => {
+                    amount = read_integer(&result).ok_or_else(out_of_range)?;
+                    (chunk.amount_value.to_string(), amount.to_string())
+                }
+                _ => {
+                    new_durability = read_float(&result).ok_or_else(out_of_range)?;
+                    (
+                        chunk.durability_value.clone(),
+                        format!("{:.1}", new_durability),
+                    )
+                }
+            };
+
+            if new_durability.to_bits() == durability.to_bits() && old_value == new_value {
+                continue;
+            }
+
+            report.changes.push(BulkEditChange::new(
+                item.index,
+                item.name.clone(),
+                row.name.clone(),
+                old_value,
+                new_value,
+            ));
+
+            content = edit_inventory_item_chunk(
+                item.index,
+                item.name.clone(),
+                chunk.index,
+                item.size,
+                level,
+                seed,
+                amount,
+                new_durability,
+                content,
+            );
+        }
+    }
+
+    report.byte_changes = find_byte_changes(&save_file.file_content, &content);
+
+    Ok((report, content))
+}
+
+/// Represents a method for creating the engine that evaluates the value expressions.
+/// Expressions cannot import modules, call `eval` or run for long.
+///
+/// ### Returns `Engine`
+/// The limited engine.
+fn create_expression_engine() -> Engine {
+    let mut engine: Engine = Engine::new();
+
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_expr_depths(MAX_EXPRESSION_DEPTH, MAX_EXPRESSION_DEPTH)
+        .set_module_resolver(DummyModuleResolver::new())
+        .disable_symbol("eval");
+
+    engine
+}
+
+/// Represents a method for converting the result of an expression into an integer field.
+///
+/// ### Parameter
+/// - `value`: The result of the expression.
+///
+/// ### Returns `Option<T>`
+/// The value, `None` if it is not a whole number in the range of the field.
+fn read_integer<T: TryFrom<i64>>(value: &Dynamic) -> Option<T> {
+    let integer: i64 = match value.as_int() {
+        Ok(integer) => integer,
+        Err(_) => {
+            let float: f64 = value.as_float().ok()?;
+            if float.fract() != 0.0 || !float.is_finite() {
+                return None;
+            }
+            float as i64
+        }
+    };
+
+    T::try_from(integer).ok()
+}
+
+/// Represents a method for converting the result of an expression into the durability.
+///
+/// ### Parameter
+/// - `value`: The result of the expression.
+///
+/// ### Returns `Option<f32>`
+/// The value, `None` if it is not a finite number.
+fn read_float(value: &Dynamic) -> Option<f32> {
+    let float: f64 = value
+        .as_float()
+        .ok()
+        .or_else(|| value.as_int().ok().map(|integer| integer as f64))?;
+
+    Some(float as f32).filter(|float| float.is_finite())
+}
+
+/// Represents a method for parsing the level term of a filter.
+///
+/// ### Parameter
+/// - `value`: A single level or an inclusive range (`5..16`, `5..`, `..16`).
+///
+/// ### Returns `(u16, u16)`
+/// The lowest and highest selected level.
+fn parse_level_range(value: &str) -> Result<(u16, u16)> {
+    let parse = |level: &str, default: u16| -> Result<u16> {
+        match level.trim() {
+            "" => Ok(default),
+            level => level.parse::<u16>().map_err(|_| {
+                format!("The level [{}] is not a number or out of range.", level).into()
+            }),
+        }
+    };
+
+    let (min, max): (u16, u16) = match value.split_once("..") {
+        Some((min, max)) => (parse(min, u16::MIN)?, parse(max, u16::MAX)?),
+        None if value.contains(',') => {
+            return Err(
+                format!("The level [{}] has to be a single level or a range.", value).into(),
+            )
+        }
+        None => {
+            let level: u16 = parse(value, 0)?;
+            (level, level)
+        }
+    };
+
+    if min > max {
+        return Err(format!("The level range [{}] is empty.", value).into());
+    }
+
+    Ok((min, max))
+}
+
+/// Represents a method for compiling the ID patterns of a filter term.
+///
+/// ### Parameter
+/// - `patterns`: The patterns with `*` and `?` wildcards.
+///
+/// ### Returns `Vec<Regex>`
+/// The compiled patterns.
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| compile_id_pattern(pattern))
+        .collect()
+}
+
+/// Represents a method for splitting a filter expression into its terms.
+/// Whitespace inside double quotes is part of the term, the quotes are removed.
+///
+/// ### Parameter
+/// - `filter`: The filter expression.
+///
+/// ### Returns `Vec<String>`
+/// The terms of the filter.
+fn split_filter_terms(filter: &str) -> Result<Vec<String>> {
+    let mut terms: Vec<String> = Vec::new();
+    let mut current: String = String::new();
+    let mut is_quoted: bool = false;
+
+    for c in filter.chars() {
+        match c {
+            '"' => is_quoted = !is_quoted,
+            c if c.is_whitespace() && !is_quoted => {
+                if !current.is_empty() {
+                    terms.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+
+    if is_quoted {
+        return Err(format!("The filter [{}] has an unclosed quote.", filter).into());
+    }
+
+    if !current.is_empty() {
+        terms.push(current);
+    }
+
+    Ok(terms)
+}
@@ -0,0 +1,164 @@
+use crate::save_logic::file_analyser::{edit_inventory_item_chunk, remove_inventory_item};
+use crate::save_logic::struct_data::{
+    CatalogItem, CoopSafetyIssue, CoopSafetyReport, InventoryItem, SaveFile,
+};
+use std::error::Error;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the risk descriptions for patched items.
+static NOT_DROPABLE_RISK: &str =
+    "The item is not dropable. It will be removed from the inventory when joining a co-op session.";
+static NOT_SHAREABLE_RISK: &str =
+    "The item is not shareable. It will be patched (reset or removed) when it is shared or used in co-op.";
+
+/// Represents a method for finding every inventory item that is flagged by the patched items lists.
+///
+/// ### Parameter
+/// - `save_file`: The loaded save file.
+///
+/// ### Returns `CoopSafetyReport`
+/// The report with all flagged items and the risk for each of them.
+pub fn find_coop_issues(save_file: &SaveFile) -> CoopSafetyReport {
+    let mut issues: Vec<CoopSafetyIssue> = Vec::new();
+    let mut checked_items: usize = 0;
+
+    for row in save_file.items.iter() {
+        for item in row.inventory_items.iter() {
+            checked_items += 1;
+
+            if item.catalog.dropable && item.catalog.shareable {
+                continue;
+            }
+
+            issues.push(CoopSafetyIssue::new(
+                row.name.clone(),
+                item.name.clone(),
+                item.catalog.display_name.clone(),
+                item.index,
+                item.chunk_data.index,
+                item.catalog.dropable,
+                item.catalog.shareable,
+                describe_risk(&item.catalog),
+            ));
+        }
+    }
+
+    CoopSafetyReport::new(save_file.path.clone(), checked_items, issues)
+}
+
+/// Represents a method for fixing flagged items by removing or replacing them.
+///
+/// ### Parameter
+/// - `save_file`: The loaded save file.
+/// - `item_indices`: The indices of the items that should be fixed.
+/// - `replacement`: The item that replaces the flagged items. If `None`, the items are removed.
+///
+/// ### Returns `Result<Vec<u8>>`
+/// The new content of the save file.
+pub fn fix_coop_issues(
+    save_file: &SaveFile,
+    item_indices: &[usize],
+    replacement: Option<&CatalogItem>,
+) -> Result<Vec<u8>> {
+    // Check if the replacement itself is safe for co-op.
+    if let Some(replacement_item) = replacement {
+        if !replacement_item.dropable || !replacement_item.shareable {
+            return Err(format!(
+                "The replacement [{}] is not safe for co-op: {}",
+                replacement_item.id,
+                describe_risk(replacement_item)
+            )
+            .into());
+        }
+    }
+
+    let mut content: Vec<u8> = save_file.file_content.clone();
+
+    for item_index in item_indices.iter() {
+        let item: &InventoryItem = save_file
+            .items
+            .iter()
+            .flat_map(|row| row.inventory_items.iter())
+            .find(|item| item.index == *item_index)
+            .ok_or(format!("There is no item at index [{}].", item_index))?;
+
+        content = match replacement {
+            Some(replacement_item) => replace_item(item, replacement_item, content)?,
+            None => remove_inventory_item(
+                item.index,
+                item.index + item.size,
+                item.chunk_data.index,
+                content,
+            ),
+        };
+    }
+
+    Ok(content)
+}
+
+/// Represents a method for replacing an item with another ID while keeping its chunk values.
+///
+/// ### Parameter
+/// - `item`: The item that is replaced.
+/// - `replacement`: The item that replaces the current item.
+/// - `content`: The content of the save file.
+///
+/// ### Returns `Result<Vec<u8>>`
+/// The new content of the save file.
+fn replace_item(
+    item: &InventoryItem,
+    replacement: &CatalogItem,
+    content: Vec<u8>,
+) -> Result<Vec<u8>> {
+    // The ID is written in front of the existing "SGDs", therefore it has to fit into the old ID.
+    if replacement.id.len() > item.size.saturating_sub(4) {
+        return Err(format!(
+            "The replacement [{}] is longer than the item [{}].",
+            replacement.id, item.name
+        )
+        .into());
+    }
+
+    let durability: f32 = f32::from_le_bytes(
+        item.chunk_data
+            .durability
+            .clone()
+            .try_into()
+            .map_err(|_| "Invalid durability bytes.")?,
+    );
+
+    Ok(edit_inventory_item_chunk(
+        item.index,
+        replacement.id.clone(),
+        item.chunk_data.index,
+        item.size,
+        item.chunk_data.level_value,
+        item.chunk_data.seed_value,
+        item.chunk_data.amount_value,
+        durability,
+        content,
+    ))
+}
+
+/// Represents a method for describing the co-op risk of an item.
+///
+/// ### Parameter
+/// - `catalog_item`: The catalog record of the item.
+///
+/// ### Returns `String`
+/// The description of all risks.
+fn describe_risk(catalog_item: &CatalogItem) -> String {
+    let mut risks: Vec<&str> = Vec::new();
+
+    if !catalog_item.dropable {
+        risks.push(NOT_DROPABLE_RISK);
+    }
+
+    if !catalog_item.shareable {
+        risks.push(NOT_SHAREABLE_RISK);
+    }
+
+    risks.join(" ")
+}
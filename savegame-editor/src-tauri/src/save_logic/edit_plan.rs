@@ -0,0 +1,510 @@
+//! Applies declarative edit plans to one or many saves.
+//!
+//! An edit plan is a versioned JSON or TOML file with a list of steps. Every step selects
+//! inventory items (by tab, ID pattern and category) or skills (by tree and ID pattern)
+//! and applies a single action to them, for example:
+//!
+//! ```json
+//! {
+//!     "version": 1,
+//!     "name": "Endgame weapons",
+//!     "steps": [
+//!         { "selector": { "tabs": ["Weapons"], "id_pattern": "wpn_*" }, "action": { "type": "set_level", "value": 9216 } },
+//!         { "selector": { "categories": ["Quest"] }, "action": { "type": "remove" } },
+//!         { "selector": { "target": "skills" }, "action": { "type": "set_skill_points", "value": 1 } }
+//!     ]
+//! }
+//! ```
+//!
+//! Edited saves are written next to the originals (`<name>_edited.sav`) after the original was backed up into the
+//! backup store.
+//! An edited save that already exists is never replaced, the save is reported as failed instead.
+
+use crate::logger::ConsoleLogger;
+use crate::save_logic::backup_manager::{create_save_backup, BACKUP_RETENTION, MANUAL_REASON};
+use crate::save_logic::file_analyser::{
+    clean_sgd_name, edit_inventory_item_chunk, edit_skill, find_byte_changes,
+    get_contents_from_file, load_save_from_path, remove_inventory_item,
+};
+use crate::save_logic::item_rules::ItemRuleEngine;
+use crate::save_logic::save_compression::compress_save_content;
+use crate::save_logic::save_writer::write_file_atomically;
+use crate::save_logic::struct_data::{
+    EditAction, EditPlan, EditPlanReport, EditPlanSaveResult, EditSelector, InventoryItem,
+    InventoryTabMapping, ItemCatalog, SaveBackup, SaveFile,
+};
+use regex::{Regex, RegexBuilder};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the highest edit plan version this editor understands.
+pub const SUPPORTED_PLAN_VERSION: u32 = 1;
+
+// Defines the targets a selector can choose from.
+static INVENTORY_TARGET: &str = "inventory";
+static SKILLS_TARGET: &str = "skills";
+
+// Defines the skill trees that can be selected by a skill selector.
+static BASE_SKILL_TREE: &str = "base";
+static LEGEND_SKILL_TREE: &str = "legend";
+
+// Defines the suffix of the edited saves.
+static OUTPUT_SUFFIX: &str = "_edited";
+
+// Defines the extension of saves inside a target directory.
+static SAVE_EXTENSION: &str = "sav";
+
+/// Represents a method for reading and validating an edit plan.
+///
+/// ### Parameter
+/// - `plan_path`: The path of the plan (`.json` or `.toml`).
+///
+/// ### Returns `EditPlan`
+/// The validated edit plan.
+pub fn fetch_edit_plan(plan_path: &str) -> Result<EditPlan> {
+    let content: String = fs::read_to_string(plan_path)
+        .map_err(|e| format!("Error reading the edit plan [{}]: {}", plan_path, e))?;
+
+    let plan: EditPlan = if plan_path.to_lowercase().ends_with(".toml") {
+        toml::from_str(&content).map_err(|e| format!("Error parsing the edit plan: {}", e))?
+    } else {
+        serde_json::from_str(&content).map_err(|e| format!("Error parsing the edit plan: {}", e))?
+    };
+
+    validate_edit_plan(&plan)?;
+
+    Ok(plan)
+}
+
+/// Represents a method for validating the version, selectors and actions of an edit plan.
+///
+/// ### Parameter
+/// - `plan`: The edit plan.
+pub fn validate_edit_plan(plan: &EditPlan) -> Result<()> {
+    if plan.version == 0 || plan.version > SUPPORTED_PLAN_VERSION {
+        return Err(format!(
+            "The edit plan has version [{}], but only version [{}] is supported.",
+            plan.version, SUPPORTED_PLAN_VERSION
+        )
+        .into());
+    }
+
+    if plan.steps.is_empty() {
+        return Err("The edit plan does not contain any steps.".into());
+    }
+
+    for (i, step) in plan.steps.iter().enumerate() {
+        let target: &str = step.selector.target.as_str();
+        let is_skill_action: bool = matches!(step.action, EditAction::SetSkillPoints { .. });
+
+        let error: Option<String> = if target != INVENTORY_TARGET && target != SKILLS_TARGET {
+            Some(format!("The target [{}] is unknown.", target))
+        } else if (target == SKILLS_TARGET) != is_skill_action {
+            Some(format!(
+                "The action cannot be applied to the target [{}].",
+                target
+            ))
+        } else if target == SKILLS_TARGET && !step.selector.categories.is_empty() {
+            Some("Skills cannot be selected by category.".to_string())
+        } else if target == SKILLS_TARGET
+            && step.selector.tabs.iter().any(|tree| {
+                !tree.eq_ignore_ascii_case(BASE_SKILL_TREE)
+                    && !tree.eq_ignore_ascii_case(LEGEND_SKILL_TREE)
+            })
+        {
+            Some("Skills can only be selected by the trees [base] and [legend].".to_string())
+        } else if matches!(step.action, EditAction::SetDurability { value } if !value.is_finite()) {
+            Some("The durability is not a valid number.".to_string())
+        } else {
+            step.selector
+                .id_pattern
+                .as_ref()
+                .and_then(|pattern| compile_id_pattern(pattern).err())
+                .map(|err| err.to_string())
+        };
+
+        if let Some(message) = error {
+            return Err(format!("Step {} of the edit plan is invalid: {}", i + 1, message).into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Represents a method for compiling an ID pattern into a regex.
+/// The pattern is matched against the whole ID (case-insensitive), `*` matches any text and `?` a single character.
+///
+/// ### Parameter
+/// - `pattern`: The ID pattern, for example `wpn_*_Legendary`.
+///
+/// ### Returns `Regex`
+/// The compiled pattern.
+pub fn compile_id_pattern(pattern: &str) -> Result<Regex> {
+    let expression: String = regex::escape(pattern.trim())
+        .replace("\\*", ".*")
+        .replace("\\?", ".");
+
+    RegexBuilder::new(&format!("^{}$", expression))
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("The ID pattern [{}] is invalid: {}", pattern, e).into())
+}
+
+/// Represents a method for applying an edit plan to the content of a save.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+/// - `plan`: The validated edit plan.
+///
+/// ### Returns `(Vec<u8>, EditPlanSaveResult)`
+/// The edited content and the amount of changed entries.
+pub fn apply_edit_plan(
+    save_file: &SaveFile,
+    plan: &EditPlan,
+) -> Result<(Vec<u8>, EditPlanSaveResult)> {
+    let mut content: Vec<u8> = save_file.file_content.clone();
+    let mut result: EditPlanSaveResult = EditPlanSaveResult::new(save_file.path.clone());
+
+    // Tracks the chunk values of every edited item (level, seed, amount, durability), so later steps keep earlier edits.
+    let mut item_values: HashMap<usize, (u16, u16, u32, f32)> = HashMap::new();
+    let mut removed_items: HashSet<usize> = HashSet::new();
+    let mut changed_skills: HashSet<usize> = HashSet::new();
+
+    for step in plan.steps.iter() {
+        let id_pattern: Option<Regex> = match &step.selector.id_pattern {
+            Some(pattern) => Some(compile_id_pattern(pattern)?),
+            None => None,
+        };
+
+        if step.selector.target == SKILLS_TARGET {
+            let value: u16 = match step.action {
+                EditAction::SetSkillPoints { value } => value,
+                _ => continue,
+            };
+
+            for (tree, skill) in save_file
+                .skills
+                .base_skills
+                .iter()
+                .map(|skill| (BASE_SKILL_TREE, skill))
+                .chain(
+                    save_file
+                        .skills
+                        .legend_skills
+                        .iter()
+                        .map(|skill| (LEGEND_SKILL_TREE, skill)),
+                )
+            {
+                if !matches_list(&step.selector.tabs, tree)
+                    || !matches_pattern(&id_pattern, &clean_sgd_name(&skill.name))
+                {
+                    continue;
+                }
+
+                content = edit_skill(skill.size, skill.index, value, content);
+                changed_skills.insert(skill.index);
+            }
+
+            continue;
+        }
+
+        for row in save_file.items.iter() {
+            for item in row.inventory_items.iter() {
+                if removed_items.contains(&item.index)
+                    || !matches_selector(&step.selector, &id_pattern, &row.name, item)
+                {
+                    continue;
+                }
+
+                if let EditAction::Remove = step.action {
+                    content = remove_inventory_item(
+                        item.index,
+                        item.index + item.size,
+                        item.chunk_data.index,
+                        content,
+                    );
+                    removed_items.insert(item.index);
+                    continue;
+                }
+
+                let chunk = &item.chunk_data;
+                let values: &mut (u16, u16, u32, f32) =
+                    item_values.entry(item.index).or_insert_with(|| {
+                        (
+                            chunk.level_value,
+                            chunk.seed_value,
+                            chunk.amount_value,
+                            f32::from_le_bytes(
+                                chunk.durability.clone().try_into().unwrap_or_default(),
+                            ),
+                        )
+                    });
+
+                match step.action {
+                    EditAction::SetLevel { value } => values.0 = value,
+                    EditAction::SetSeed { value } => values.1 = value,
+                    EditAction::SetAmount { value } => values.2 = value,
+                    EditAction::SetDurability { value } => values.3 = value,
+                    _ => continue,
+                }
+
+                content = edit_inventory_item_chunk(
+                    item.index,
+                    item.name.clone(),
+                    chunk.index,
+                    item.size,
+                    values.0,
+                    values.1,
+                    values.2,
+                    values.3,
+                    content,
+                );
+            }
+        }
+    }
+
+    result.changed_items = item_values
+        .keys()
+        .filter(|index| !removed_items.contains(index))
+        .count();
+    result.removed_items = removed_items.len();
+    result.changed_skills = changed_skills.len();
+    result.byte_changes = find_byte_changes(&save_file.file_content, &content).len();
+
+    Ok((content, result))
+}
+
+/// Represents a method for applying an edit plan to a single save or every save of a directory.
+/// A failing save is reported and does not stop the other saves.
+///
+/// ### Parameter
+/// - `plan`: The validated edit plan.
+/// - `target_path`: The path of the save or of the directory with saves.
+/// - `catalog`: The item catalog with a record for every known ID.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
+/// - `store_path`: The directory of the backup store.
+/// - `logger`: The console logger that logs every event.
+/// - `dry_run`: Indicates whether the changes are only counted without writing any file.
+///
+/// ### Returns `EditPlanReport`
+/// The result of every save.
+pub fn run_edit_plan(
+    plan: &EditPlan,
+    target_path: &str,
+    catalog: &ItemCatalog,
+    tab_mapping: &InventoryTabMapping,
+    item_rules: &ItemRuleEngine,
+    store_path: &str,
+    logger: &mut ConsoleLogger,
+    dry_run: bool,
+) -> Result<EditPlanReport> {
+    let mut results: Vec<EditPlanSaveResult> = Vec::new();
+
+    for save_path in find_plan_targets(target_path)? {
+        let path: String = save_path.display().to_string();
+
+        let result: EditPlanSaveResult = load_save_from_path(
+            &path,
+            catalog.clone(),
+            tab_mapping.clone(),
+            item_rules.clone(),
+            logger,
+            false,
+        )
+        .and_then(|save_file| write_edited_save(&save_file, plan, store_path, dry_run))
+        .unwrap_or_else(|err| {
+            let mut result: EditPlanSaveResult = EditPlanSaveResult::new(path.clone());
+            result.error = Some(err.to_string());
+            result
+        });
+
+        results.push(result);
+    }
+
+    Ok(EditPlanReport::new(
+        plan.name.clone(),
+        plan.version,
+        dry_run,
+        results,
+    ))
+}
+
+/// Represents a method for applying an edit plan to a save and writing the edited save next to the original.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+/// - `plan`: The validated edit plan.
+/// - `store_path`: The directory of the backup store.
+/// - `dry_run`: Indicates whether the changes are only counted without writing any file.
+///
+/// ### Returns `EditPlanSaveResult`
+/// The amount of changed entries, the written file and the backup of the original.
+fn write_edited_save(
+    save_file: &SaveFile,
+    plan: &EditPlan,
+    store_path: &str,
+    dry_run: bool,
+) -> Result<EditPlanSaveResult> {
+    let (content, mut result) = apply_edit_plan(save_file, plan)?;
+    let output_path: PathBuf = get_edited_save_path(&save_file.path);
+
+    result.output_path = output_path.display().to_string();
+
+    if dry_run || result.byte_changes == 0 {
+        return Ok(result);
+    }
+
+    // The output of an earlier run is never replaced without notice.
+    if output_path.exists() {
+        return Err(format!(
+            "The edited save [{}] already exists, move or remove it before running the plan again.",
+            output_path.display()
+        )
+        .into());
+    }
+
+    // The backup contains the original as stored on disk (compressed PC saves stay compressed).
+    let original_content: Vec<u8> = get_contents_from_file(&save_file.path)?;
+    let backup: SaveBackup = create_save_backup(
+        store_path,
+        &save_file.path,
+        &original_content,
+        MANUAL_REASON,
+        BACKUP_RETENTION,
+    )
+    .map_err(|e| format!("Error creating the backup: {}", e))?;
+    result.backup_id = Some(backup.id);
+
+    // Compressed PC saves are written compressed again.
+    let output_content: Vec<u8> = match save_file.is_compressed {
+        true => compress_save_content(&content, save_file.gzip_profile.as_ref())?,
+        false => content,
+    };
+
+    write_file_atomically(&output_path, &output_content)?;
+
+    Ok(result)
+}
+
+/// Represents a method for creating the path of an edited save next to the original (`<name>_edited.sav`).
+///
+/// ### Parameter
+/// - `save_path`: The path of the original save.
+///
+/// ### Returns `PathBuf`
+/// The path of the edited save.
+pub fn get_edited_save_path(save_path: &str) -> PathBuf {
+    let path: &Path = Path::new(save_path);
+    let file_stem: String = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    match path.extension() {
+        Some(extension) => path.with_file_name(format!(
+            "{}{}.{}",
+            file_stem,
+            OUTPUT_SUFFIX,
+            extension.to_string_lossy()
+        )),
+        None => path.with_file_name(format!("{}{}", file_stem, OUTPUT_SUFFIX)),
+    }
+}
+
+/// Represents a method for finding the saves an edit plan is applied to.
+/// Inside a directory, every `.sav` file is used except the outputs of earlier runs.
+///
+/// ### Parameter
+/// - `target_path`: The path of the save or of the directory with saves.
+///
+/// ### Returns `Vec<PathBuf>`
+/// The paths of all saves (sorted).
+fn find_plan_targets(target_path: &str) -> Result<Vec<PathBuf>> {
+    let path: &Path = Path::new(target_path);
+
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    if !path.is_dir() {
+        return Err(format!("The target [{}] does not exist.", target_path).into());
+    }
+
+    let mut targets: Vec<PathBuf> = fs::read_dir(path)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| {
+            p.is_file()
+                && p.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case(SAVE_EXTENSION))
+                && !p
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|s| s.ends_with(OUTPUT_SUFFIX))
+        })
+        .collect();
+
+    if targets.is_empty() {
+        return Err(format!(
+            "The directory [{}] does not contain any saves.",
+            target_path
+        )
+        .into());
+    }
+
+    targets.sort();
+    Ok(targets)
+}
+
+/// Represents a method for checking whether an inventory item is selected.
+///
+/// ### Parameter
+/// - `selector`: The selector of the step.
+/// - `id_pattern`: The compiled ID pattern of the selector.
+/// - `tab`: The inventory tab of the item.
+/// - `item`: The inventory item.
+///
+/// ### Returns `bool`
+/// Indicates whether the item is selected.
+fn matches_selector(
+    selector: &EditSelector,
+    id_pattern: &Option<Regex>,
+    tab: &str,
+    item: &InventoryItem,
+) -> bool {
+    matches_list(&selector.tabs, tab)
+        && matches_list(&selector.categories, &item.catalog.category)
+        && matches_pattern(id_pattern, &item.name)
+}
+
+/// Represents a method for checking whether a value is part of a selector list (an empty list selects everything).
+///
+/// ### Parameter
+/// - `list`: The values of the selector.
+/// - `value`: The value of the entry.
+///
+/// ### Returns `bool`
+/// Indicates whether the value is selected.
+fn matches_list(list: &[String], value: &str) -> bool {
+    list.is_empty() || list.iter().any(|entry| entry.eq_ignore_ascii_case(value))
+}
+
+/// Represents a method for checking whether an ID matches the pattern of a selector (no pattern selects everything).
+///
+/// ### Parameter
+/// - `id_pattern`: The compiled ID pattern.
+/// - `id_name`: The ID of the entry.
+///
+/// ### Returns `bool`
+/// Indicates whether the ID is selected.
+fn matches_pattern(id_pattern: &Option<Regex>, id_name: &str) -> bool {
+    id_pattern
+        .as_ref()
+        .is_none_or(|pattern| pattern.is_match(id_name))
+}
@@ -6,17 +6,25 @@
 //! - Inventory items (weapons, gear, accesssories, etc)
 
 use crate::logger::{ConsoleLogger, LoggerFunctions};
+use crate::save_logic::id_harvester::harvest_unknown_ids;
+use crate::save_logic::item_catalog::find_catalog_item;
+use crate::save_logic::item_rules::{
+    ItemRuleEngine, AMMO_MOD_RULE, MOD_RULE, OUTFIT_RULE, RANGED_WEAPON_RULE, SGD_ENTRY_RULE,
+};
+use crate::save_logic::save_compression::read_gzip_profile;
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use regex::Regex;
+use sha2::{Digest, Sha256};
 use std::error::Error;
 use std::io::Write;
 use std::{fs, io::Read};
 // Import all struct datas.
 use crate::save_logic::struct_data::{
-    IdData, InventoryChunk, InventoryItem, InventoryItemRow, ItemTypeEnum, SaveFile, SkillItem,
-    Skills, UnlockableItem,
+    ByteChange, CatalogItem, ClassificationEntry, InventoryChunk, InventoryContainer,
+    InventoryItem, InventoryItemRow, InventoryTabMapping, ItemCatalog, ItemTypeEnum, SaveFile,
+    SkillItem, Skills, UnknownId, UnlockableItem,
 };
 
 use super::struct_data::Mod;
@@ -38,12 +46,25 @@ static START_INVENTORY: [u8; 15] = [
     0x4D, 0x61, 0x69, 0x6E, 0x01, 0x00, 0x00, 0x00, 0x05, 0x00, 0x4F, 0x74, 0x68, 0x65, 0x72,
 ];
 
+// Defines the byte sizes of each field inside an inventory chunk (in the order they are stored).
+pub const CHUNK_LEVEL_SIZE: usize = 2;
+pub const CHUNK_SEED_SIZE: usize = 2;
+pub const CHUNK_AMOUNT_SIZE: usize = 4;
+pub const CHUNK_DURABILITY_SIZE: usize = 4;
+pub const CHUNK_COUNTER_STATS_SIZE: usize = 4;
+pub const CHUNK_SPACE_SIZE: usize = 25;
+
+// Defines how many bytes in front of the inventory sequence are searched for the container list.
+const CONTAINER_SEARCH_OFFSET: usize = 4096;
+
 /// Represents a method for loading a savefile and preparing all necessary information.
 ///
 /// ### Parameter
 /// - `file_path`: The filepath of the current selected save.
 /// - `file_content`: The content of the current file.
-/// - `ids`: The list of all IDs.
+/// - `catalog`: The item catalog with a record for every known ID.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
 /// - `logger`: The console logger that logs every event.
 /// - `is_debugging`: Indicates whether the file analyser is in debugging mode or not.
 ///
@@ -52,7 +73,9 @@ static START_INVENTORY: [u8; 15] = [
 pub fn load_save_file(
     file_path: &str,
     file_content: Vec<u8>,
-    ids: Vec<IdData>,
+    catalog: ItemCatalog,
+    tab_mapping: InventoryTabMapping,
+    item_rules: ItemRuleEngine,
     logger: &mut ConsoleLogger,
     is_debugging: bool,
     is_compressed: bool,
@@ -123,11 +146,20 @@ pub fn load_save_file(
 
     let index_inventory_items: usize = index_inventory_items_result.unwrap();
 
+    // Find the containers of the inventory to identify the tabs.
+    let containers: Vec<InventoryContainer> =
+        find_inventory_containers(&file_content, &unlockable_items, logger, is_debugging);
+
     // Get all items within the inventory.
+    let mut classification_report: Vec<ClassificationEntry> = Vec::new();
     let items_result: Result<Vec<InventoryItemRow>> = get_all_items(
         &file_content,
         index_inventory_items,
-        ids,
+        &catalog,
+        &tab_mapping,
+        &item_rules,
+        &containers,
+        &mut classification_report,
         logger,
         is_debugging,
     );
@@ -140,13 +172,24 @@ pub fn load_save_file(
         Err(err) => return Err(err.to_string().into()),
     }
 
+    let items: Vec<InventoryItemRow> = items_result?;
+
+    // Collect all IDs that are not part of the ID database.
+    let unknown_ids: Vec<UnknownId> = harvest_unknown_ids(&items, &catalog, &tab_mapping);
+    logger.log_message(&format!(
+        "{} IDs of the save are not part of the ID database.",
+        unknown_ids.len()
+    ));
+
     Ok(SaveFile::new(
         file_path.to_string(),
         file_content,
         skills.unwrap().clone(),
         unlockable_items,
-        items_result?,
+        items,
         logger.log_histroy.clone(),
+        classification_report,
+        unknown_ids,
         is_compressed,
         game_version,
     ))
@@ -157,7 +200,9 @@ pub fn load_save_file(
 /// ### Parameter
 /// - `file_path`: The filepath of the current selected save.
 /// - `compressed`: The compressed content of the current file.
-/// - `ids`: The list of all IDs.
+/// - `catalog`: The item catalog with a record for every known ID.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
 /// - `logger`: The console logger that logs every event.
 /// - `is_debugging`: Indicates whether the file analyser is in debugging mode or not.
 ///
@@ -166,12 +211,93 @@ pub fn load_save_file(
 pub fn load_save_file_pc(
     file_path: &str,
     compressed: Vec<u8>,
-    ids: Vec<IdData>,
+    catalog: ItemCatalog,
+    tab_mapping: InventoryTabMapping,
+    item_rules: ItemRuleEngine,
     logger: &mut ConsoleLogger,
     is_debugging: bool,
     is_compressed: bool,
 ) -> Result<SaveFile> {
-    let mut gz = GzDecoder::new(&compressed[..]);
+    let file_content: Vec<u8> = decompress_save_content(&compressed)?;
+
+    let mut save_file: SaveFile = load_save_file(
+        file_path,
+        file_content,
+        catalog,
+        tab_mapping,
+        item_rules,
+        logger,
+        is_debugging,
+        is_compressed,
+    )?;
+    save_file.file_hash = get_content_hash(&compressed);
+    // The header and the compression level are kept to compress the save the same way again.
+    save_file.gzip_profile = read_gzip_profile(&compressed, &save_file.file_content).ok();
+
+    Ok(save_file)
+}
+
+/// Represents a method for reading and analysing a save from disk (compressed PC saves are decompressed).
+///
+/// ### Parameter
+/// - `file_path`: The path of the save.
+/// - `catalog`: The item catalog with a record for every known ID.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
+/// - `logger`: The console logger that logs every event.
+/// - `is_debugging`: Indicates whether the file analyser is in debugging mode or not.
+///
+/// ### Returns `SaveFile`
+/// The save file with all collected data.
+pub fn load_save_from_path(
+    file_path: &str,
+    catalog: ItemCatalog,
+    tab_mapping: InventoryTabMapping,
+    item_rules: ItemRuleEngine,
+    logger: &mut ConsoleLogger,
+    is_debugging: bool,
+) -> Result<SaveFile> {
+    let file_content: Vec<u8> = get_contents_from_file(file_path)
+        .map_err(|e| format!("Error reading the save [{}]: {}", file_path, e))?;
+
+    if is_compressed_content(&file_content) {
+        return load_save_file_pc(
+            file_path,
+            file_content,
+            catalog,
+            tab_mapping,
+            item_rules,
+            logger,
+            is_debugging,
+            true,
+        );
+    }
+
+    let file_hash: String = get_content_hash(&file_content);
+    let mut save_file: SaveFile = load_save_file(
+        file_path,
+        file_content,
+        catalog,
+        tab_mapping,
+        item_rules,
+        logger,
+        is_debugging,
+        false,
+    )?;
+    save_file.file_hash = file_hash;
+
+    Ok(save_file)
+}
+
+/// Represents a method for decompressing the content of a PC save.
+///
+/// ### Parameter
+/// - `compressed`: The compressed content of the current file.
+///
+/// ### Returns `Result<Vec<u8>>`
+/// The decompressed content of the save.
+pub fn decompress_save_content(compressed: &[u8]) -> Result<Vec<u8>> {
+    let mut gz = GzDecoder::new(compressed);
     let mut file_content = Vec::new();
     if let Err(error) = gz.read_to_end(&mut file_content) {
         return Err(format!(
@@ -181,14 +307,18 @@ pub fn load_save_file_pc(
         .into());
     }
 
-    load_save_file(
-        file_path,
-        file_content,
-        ids,
-        logger,
-        is_debugging,
-        is_compressed,
-    )
+    Ok(file_content)
+}
+
+/// Represents a method for checking whether the content of a save is gzip compressed (PC save).
+///
+/// ### Parameter
+/// - `file_content`: The content of the current file.
+///
+/// ### Returns `bool`
+/// Indicates whether the content starts with the gzip magic bytes.
+pub fn is_compressed_content(file_content: &[u8]) -> bool {
+    file_content.len() > 1 && file_content[0] == 31 && file_content[1] == 139
 }
 
 /// Represents a method for exporting the save for PC (compressing).
@@ -304,6 +434,33 @@ pub fn edit_inventory_item_chunk(
     save_file_content
 }
 
+/// Represents a method for replacing the ID of a mod that is attached to an inventory item.
+///
+/// ### Parameter
+/// - `current_mod_index`: The index of the current mod ID.
+/// - `current_mod_size`: The size of the current mod ID (including the `SGDs`).
+/// - `new_id`: The new mod ID.
+/// - `save_file_content`: The content of the save file.
+///
+/// ### Returns `Vec<u8>`
+/// The new content of the save file.
+pub fn edit_inventory_mod(
+    current_mod_index: usize,
+    current_mod_size: usize,
+    new_id: String,
+    mut save_file_content: Vec<u8>,
+) -> Vec<u8> {
+    let zero_bytes: Vec<u8> = vec![0; current_mod_size - 4]; // -4 because of leaving the old SGDs
+    let new_id_bytes: Vec<u8> = new_id.as_bytes().to_vec();
+
+    // First empty
+    save_file_content = replace_content_of_file(current_mod_index, zero_bytes, save_file_content);
+    save_file_content = replace_content_of_file(current_mod_index, new_id_bytes, save_file_content);
+
+    // Returns content
+    save_file_content
+}
+
 /// Represents a method for editing the durability for all items in a section at once.
 ///
 /// ### Parameter
@@ -407,6 +564,41 @@ pub fn remove_inventory_item(
     save_file_content
 }
 
+/// Represents a method for comparing two versions of the save content.
+///
+/// ### Parameter
+/// - `old_content`: The content before the edits.
+/// - `new_content`: The content after the edits.
+///
+/// ### Returns `Vec<ByteChange>`
+/// Every range of changed bytes with the old and the new values.
+pub fn find_byte_changes(old_content: &[u8], new_content: &[u8]) -> Vec<ByteChange> {
+    let mut byte_changes: Vec<ByteChange> = Vec::new();
+    let length: usize = old_content.len().max(new_content.len());
+    let mut i: usize = 0;
+
+    while i < length {
+        if old_content.get(i) == new_content.get(i) {
+            i += 1;
+            continue;
+        }
+
+        // Collects the range of consecutive changed bytes.
+        let start: usize = i;
+        while i < length && old_content.get(i) != new_content.get(i) {
+            i += 1;
+        }
+
+        byte_changes.push(ByteChange::new(
+            start,
+            old_content[start.min(old_content.len())..i.min(old_content.len())].to_vec(),
+            new_content[start.min(new_content.len())..i.min(new_content.len())].to_vec(),
+        ));
+    }
+
+    byte_changes
+}
+
 /// Represents a method for replacing the file content.
 ///
 /// ### Parameter
@@ -706,12 +898,169 @@ fn get_index_for_inventory_items(
     }
 }
 
+/// Represents a method for finding the containers (tabs) of the inventory.
+/// The container list is stored in front of the unlockables, each entry looks like:
+/// `[name size][name][u32][type size][type][slot count][slot handles (8 bytes each)]`
+///
+/// ### Parameter
+/// - `content`: The byte data of the current selected save.
+/// - `unlockable_items`: The unlockable items, which follow right after the container list.
+/// - `logger`: The console logger that logs every event.
+/// - `is_debugging`: Indicates whether the file analyser is in debugging mode or not.
+///
+/// ### Returns `Vec<InventoryContainer>`
+/// All containers in the order they are listed inside the save.
+fn find_inventory_containers(
+    content: &[u8],
+    unlockable_items: &[UnlockableItem],
+    logger: &mut ConsoleLogger,
+    is_debugging: bool,
+) -> Vec<InventoryContainer> {
+    // Prepare data.
+    let mut containers: Vec<InventoryContainer> = Vec::new();
+    let indices: Vec<usize> = get_all_indices_from_sequence(content, &0, &START_INVENTORY, false);
+
+    if indices.is_empty() || unlockable_items.is_empty() {
+        return containers;
+    }
+
+    let end_index: usize = unlockable_items[0].index.min(content.len());
+    let mut index: usize = indices[indices.len() - 1].saturating_sub(CONTAINER_SEARCH_OFFSET);
+
+    while index < end_index {
+        match parse_inventory_container(content, index) {
+            Some(container) => {
+                if is_debugging {
+                    logger.log_message(&format!(
+                        "Found the inventory container [{}] with [{}] slots at offset: [{}]",
+                        container.marker(),
+                        container.slot_count,
+                        container.index
+                    ));
+                }
+
+                // Skip the slot handles of the container.
+                index = container.index
+                    + 2
+                    + container.name.len()
+                    + 4
+                    + 2
+                    + container.container_type.len()
+                    + 4
+                    + container.slot_count * 8;
+                containers.push(container);
+            }
+            None => index += 1,
+        }
+    }
+
+    containers
+}
+
+/// Represents a method for parsing a single container entry.
+///
+/// ### Parameter
+/// - `content`: The byte data of the current selected save.
+/// - `index`: The index of the size in front of the container name.
+///
+/// ### Returns `Option<InventoryContainer>`
+/// The container if the bytes match the container structure.
+fn parse_inventory_container(content: &[u8], index: usize) -> Option<InventoryContainer> {
+    let name: String = read_sized_name(content, index)?;
+    let type_index: usize = index + 2 + name.len() + 4;
+    let container_type: String = read_sized_name(content, type_index)?;
+    let count_index: usize = type_index + 2 + container_type.len();
+
+    if count_index + 4 > content.len() {
+        return None;
+    }
+
+    let slot_count: usize =
+        u32::from_le_bytes(content[count_index..count_index + 4].try_into().unwrap()) as usize;
+
+    // The slot handles have to fit into the save.
+    if count_index + 4 + slot_count * 8 > content.len() {
+        return None;
+    }
+
+    Some(InventoryContainer::new(
+        name,
+        container_type,
+        slot_count,
+        index,
+    ))
+}
+
+/// Represents a method for reading a name that is prefixed with its size (u16).
+///
+/// ### Parameter
+/// - `content`: The byte data of the current selected save.
+/// - `index`: The index of the size.
+///
+/// ### Returns `Option<String>`
+/// The name if it only contains letters.
+fn read_sized_name(content: &[u8], index: usize) -> Option<String> {
+    if index + 2 > content.len() {
+        return None;
+    }
+
+    let size: usize = u16::from_le_bytes([content[index], content[index + 1]]) as usize;
+
+    if size < 2 || size > 64 || index + 2 + size > content.len() {
+        return None;
+    }
+
+    let name_bytes: &[u8] = &content[index + 2..index + 2 + size];
+
+    if !name_bytes.iter().all(|byte| byte.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(name_bytes).to_string())
+}
+
+/// Represents a method for matching the next item row to its container.
+/// The rows are stored in the reversed order of the container list. Rows can be cut short by the chunk search,
+/// which is why a container with the same amount of slots is preferred over the next container in order.
+///
+/// ### Parameter
+/// - `containers`: The containers in the order of the item rows.
+/// - `cursor`: The position of the next unused container.
+/// - `chunk_count`: The amount of chunks found for the current row.
+///
+/// ### Returns `Option<InventoryContainer>`
+/// The container of the current row.
+fn match_row_container(
+    containers: &[InventoryContainer],
+    cursor: &mut usize,
+    chunk_count: usize,
+) -> Option<InventoryContainer> {
+    let exact_match: Option<usize> = containers[*cursor..]
+        .iter()
+        .position(|container| container.slot_count == chunk_count)
+        .map(|position| *cursor + position);
+
+    let next_match: Option<usize> = containers[*cursor..]
+        .iter()
+        .position(|container| container.slot_count >= chunk_count)
+        .map(|position| *cursor + position);
+
+    let position: usize = exact_match.or(next_match)?;
+    *cursor = position + 1;
+
+    Some(containers[position].clone())
+}
+
 /// Represents the method for finding all items inside the inventory.
 ///
 /// ### Parameter
 /// - `content`: The byte data of the current selected save.
 /// - `start_index`: The start index of the inventory data.
-/// - `ids`: The list of all IDs.
+/// - `catalog`: The item catalog with a record for every known ID.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
+/// - `containers`: The containers of the inventory.
+/// - `classification_report`: Collects which rule classified each match (only in debugging mode).
 /// - `logger`: The console logger that logs every event.
 /// - `is_debugging`: Indicates whether the file analyser is in debugging mode or not.
 ///
@@ -721,13 +1070,19 @@ fn get_index_for_inventory_items(
 fn get_all_items(
     content: &[u8],
     start_index: usize,
-    ids: Vec<IdData>,
+    catalog: &ItemCatalog,
+    tab_mapping: &InventoryTabMapping,
+    item_rules: &ItemRuleEngine,
+    containers: &[InventoryContainer],
+    classification_report: &mut Vec<ClassificationEntry>,
     logger: &mut ConsoleLogger,
     is_debugging: bool,
 ) -> Result<Vec<InventoryItemRow>> {
     // Prepare data.
     let mut items: Vec<InventoryItemRow> = Vec::new();
     let mut index: usize = start_index;
+    let row_containers: Vec<InventoryContainer> = containers.iter().rev().cloned().collect();
+    let mut container_cursor: usize = 0;
 
     loop {
         // Prepare the inner item section.
@@ -752,8 +1107,14 @@ fn get_all_items(
         let (chunks, new_index) = find_result.unwrap();
 
         // Find the corresponding matches to each chunk (Including Mod data).
-        let (current_item_ids, current_item_indices) =
-            find_amount_of_matches(content, new_index, chunks.len(), logger, is_debugging);
+        let (current_item_ids, current_item_indices) = find_amount_of_matches(
+            content,
+            new_index,
+            chunks.len(),
+            item_rules,
+            logger,
+            is_debugging,
+        );
 
         // Preparing iteration data.
         let mut current_item_id: String = String::new();
@@ -766,11 +1127,25 @@ fn get_all_items(
         // iterate through each found match and validate the position of the match.
         for i in 0..current_item_ids.len() {
             // Check if the match is an item or a mod.
-            if validate_item_or_mod(&current_item_ids[i]) {
+            if validate_item_or_mod(&current_item_ids[i], item_rules) {
                 // Check if the bullet acts as item or mod or if there is a transmog item.
-                if validate_item_or_transmog(&current_item_ids[i], &current_item_id) {
+                if validate_item_or_transmog(&current_item_ids[i], &current_item_id, item_rules) {
                     if is_debugging {
                         logger.log_message(&format!("Since this item can be item and mod, the editor validated it as a mod: [{}]", current_item_ids[i].to_string()));
+                        classification_report.push(ClassificationEntry::new(
+                            current_item_ids[i].clone(),
+                            current_item_indices[i],
+                            "mod".to_string(),
+                            format!(
+                                "{} after {}",
+                                item_rules
+                                    .evaluate(AMMO_MOD_RULE, &current_item_ids[i])
+                                    .unwrap_or_default(),
+                                item_rules
+                                    .evaluate(RANGED_WEAPON_RULE, &current_item_id)
+                                    .unwrap_or_default()
+                            ),
+                        ));
                     }
 
                     mods.push(Mod::new(
@@ -780,12 +1155,20 @@ fn get_all_items(
                     ));
 
                     continue;
-                } else if validate_outfit(&current_item_ids[i])
-                    && validate_outfit(&current_item_id)
+                } else if validate_outfit(&current_item_ids[i], item_rules)
+                    && validate_outfit(&current_item_id, item_rules)
                     && current_item_indices[i] - (current_item_index + current_item_id.len()) <= 35
                 {
                     if is_debugging {
                         logger.log_message(&format!("Since this item can be item and transmog, the editor validated it as a transmog: [{}]", current_item_ids[i].to_string()));
+                        classification_report.push(ClassificationEntry::new(
+                            current_item_ids[i].clone(),
+                            current_item_indices[i],
+                            "transmog".to_string(),
+                            item_rules
+                                .evaluate(OUTFIT_RULE, &current_item_ids[i])
+                                .unwrap_or_default(),
+                        ));
                     }
 
                     mods.push(Mod::new(
@@ -810,13 +1193,17 @@ fn get_all_items(
 
                 // Add the previous item (if exists) to the list.
                 if current_item_id != String::new() {
+                    let item_name: String = current_item_id.replace("\x00", "").replace("\x01", "");
+                    let catalog_item: CatalogItem = find_catalog_item(catalog, &item_name);
+
                     inner_item_list.push(InventoryItem::new(
-                        current_item_id.replace("\x00", "").replace("\x01", ""),
+                        item_name,
                         current_item_index,
                         _match_bytes.len(),
                         _match_bytes.to_vec(),
                         _current_inv_chunk.clone(),
                         mods.clone(),
+                        catalog_item,
                     ));
 
                     if is_debugging {
@@ -825,6 +1212,14 @@ fn get_all_items(
                             current_item_index,
                             current_item_id.replace("\x00", "").replace("\x01", "")
                         ));
+                        classification_report.push(ClassificationEntry::new(
+                            current_item_ids[i].clone(),
+                            current_item_index,
+                            "item".to_string(),
+                            item_rules
+                                .evaluate(SGD_ENTRY_RULE, &current_item_ids[i])
+                                .unwrap_or_default(),
+                        ));
                     }
 
                     mods.clear();
@@ -837,6 +1232,14 @@ fn get_all_items(
                         "Validated mod: [{}]",
                         current_item_ids[i].to_string()
                     ));
+                    classification_report.push(ClassificationEntry::new(
+                        current_item_ids[i].clone(),
+                        current_item_indices[i],
+                        "mod".to_string(),
+                        item_rules
+                            .evaluate(MOD_RULE, &current_item_ids[i])
+                            .unwrap_or_default(),
+                    ));
                 }
 
                 mods.push(Mod::new(
@@ -848,7 +1251,24 @@ fn get_all_items(
         }
 
         // Add the inner section to the item list.
-        let item_row = create_item_row(inner_item_list.clone(), ids.clone());
+        let container: Option<InventoryContainer> =
+            match_row_container(&row_containers, &mut container_cursor, chunks.len());
+
+        if is_debugging {
+            match &container {
+                Some(container) => logger.log_message(&format!(
+                    "The row with [{}] chunks belongs to the container: [{}]",
+                    chunks.len(),
+                    container.marker()
+                )),
+                None => logger.log_message(&format!(
+                    "No container found for the row with [{}] chunks, the tab is chosen by the item categories.",
+                    chunks.len()
+                )),
+            }
+        }
+
+        let item_row = create_item_row(inner_item_list.clone(), container.as_ref(), tab_mapping);
         items.push(item_row);
         let last_inner_item: &InventoryItem = &inner_item_list.last().unwrap();
         index = last_inner_item.index;
@@ -870,160 +1290,108 @@ fn get_all_items(
     Ok(items)
 }
 
-fn validate_item_or_mod(current_match: &str) -> bool {
-    if !current_match.to_lowercase().contains("mod")
-        && !current_match.to_lowercase().contains("charm")
-    {
-        return true;
-    }
-
-    false
+/// Represents a method for checking whether a match is an item (and not a mod).
+///
+/// ### Parameter
+/// - `current_match`: The current SGD match.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
+///
+/// ### Returns `bool`
+/// Indicates whether the match is an item.
+fn validate_item_or_mod(current_match: &str, item_rules: &ItemRuleEngine) -> bool {
+    !item_rules.matches(MOD_RULE, current_match)
 }
 
-fn validate_item_or_transmog(current_match: &str, last_match: &str) -> bool {
-    if (current_match.to_lowercase().contains("bullet")
-        || current_match.to_lowercase().contains("craftplan"))
-        && (last_match.to_lowercase().contains("bow")
-            || (last_match.to_lowercase().contains("firearm")
-                && !last_match.to_lowercase().contains("bullet"))
-            || last_match.to_lowercase().contains("gun")
-            || last_match.to_lowercase().contains("harpoon"))
-    {
-        return true;
-    }
-
-    false
+/// Represents a method for checking whether a match acts as mod of the previous ranged weapon (for example bullets).
+///
+/// ### Parameter
+/// - `current_match`: The current SGD match.
+/// - `last_match`: The previous SGD match.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
+///
+/// ### Returns `bool`
+/// Indicates whether the match acts as mod.
+fn validate_item_or_transmog(
+    current_match: &str,
+    last_match: &str,
+    item_rules: &ItemRuleEngine,
+) -> bool {
+    item_rules.matches(AMMO_MOD_RULE, current_match)
+        && item_rules.matches(RANGED_WEAPON_RULE, last_match)
 }
 
-fn validate_outfit(current_match: &str) -> bool {
-    if current_match.to_lowercase().contains("outfit")
-        || current_match.to_lowercase().contains("shoes")
-        || current_match.to_lowercase().contains("torso")
-        || current_match.to_lowercase().contains("jacket")
-        || current_match.to_lowercase().contains("legs")
-        || current_match.to_lowercase().contains("pants")
-        || current_match.to_lowercase().contains("bracelets")
-        || current_match.to_lowercase().contains("head")
-        || current_match.to_lowercase().contains("pumpkin")
-        || current_match.to_lowercase().contains("skin")
-        || current_match.to_lowercase().contains("gloves")
-        || current_match.to_lowercase().contains("mask")
-        || current_match.to_lowercase().contains("helmet")
-    {
-        return true;
-    }
-
-    false
+/// Represents a method for checking whether a match is an outfit part.
+///
+/// ### Parameter
+/// - `current_match`: The current SGD match.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
+///
+/// ### Returns `bool`
+/// Indicates whether the match is an outfit part.
+fn validate_outfit(current_match: &str, item_rules: &ItemRuleEngine) -> bool {
+    item_rules.matches(OUTFIT_RULE, current_match)
 }
 
 /// Represents the method for matching the item of each section to its dedicated row.
+/// The tab is identified by the container of the row. If the container is unknown or not part of the mapping,
+/// the tab that covers the most item categories is chosen instead.
 ///
 /// ### Parameter
 /// - `items`: The item of a specific section.
-/// - `ids`: The list of all IDs.
+/// - `container`: The container of the section.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
 ///
 /// ### Returns `InventoryItemRow`
 /// A specific itemrow with name and items inside.
-fn create_item_row(items: Vec<InventoryItem>, ids: Vec<IdData>) -> InventoryItemRow {
-    let mut tab_tokens = 0;
-    let mut tab_equipment = 0;
-    let mut tab_craftresources = 0;
-    let mut tab_consumables = 0;
-    let mut tab_accessories = 0;
-    let mut tab_quest = 0;
-    let mut tab_ammunition = 0;
-    let mut tab_weapons = 0;
-    let mut tab_item = 0;
-
-    for item in items.iter() {
-        let mut is_matched = false;
-        for id_section in ids.iter() {
-            if is_matched {
-                break;
-            }
-
-            for id_name in id_section.ids.iter() {
-                if is_matched {
-                    break;
-                }
+fn create_item_row(
+    items: Vec<InventoryItem>,
+    container: Option<&InventoryContainer>,
+    tab_mapping: &InventoryTabMapping,
+) -> InventoryItemRow {
+    let marker: String = container.map_or(String::new(), |container| container.marker());
+
+    // Identify the tab by the container marker.
+    let mapped_tab = tab_mapping.tabs.iter().find(|tab| {
+        !marker.is_empty()
+            && tab
+                .containers
+                .iter()
+                .any(|tab_container| tab_container.eq_ignore_ascii_case(&marker))
+    });
+
+    if let Some(tab) = mapped_tab {
+        return InventoryItemRow::new(tab.name.clone(), marker, items);
+    }
 
-                if item.name.contains(id_name) {
-                    match id_section.filename.to_lowercase().as_str() {
-                        "ammo" => tab_ammunition += 1,
-                        "cash" => tab_item += 1,
-                        "collectable" => tab_item += 1,
-                        "craftcomponent" => tab_craftresources += 1,
-                        "craftpart" => tab_craftresources += 1,
-                        "equipment" => tab_equipment += 1,
-                        "evolvingitem" => tab_item += 1,
-                        "firearm" => tab_weapons += 1,
-                        "flashlight" => tab_accessories += 1,
-                        "inventoryitem" => tab_accessories += 1,
-                        "itembundle" => tab_item += 1,
-                        "lockpick" => tab_equipment += 1,
-                        "lootpack" => tab_craftresources += 1,
-                        "medkit" => tab_consumables += 1,
-                        "melee" => tab_weapons += 1,
-                        "other" => tab_quest += 1,
-                        "outfitpart" => tab_craftresources += 1,
-                        "powerup" => tab_consumables += 1,
-                        "survivorpack" => tab_item += 1,
-                        "syringeantizin" => tab_consumables += 1,
-                        "throwable" => tab_accessories += 1,
-                        "throwableliquid" => tab_accessories += 1,
-                        "token" => tab_tokens += 1,
-                        "uncategorized" => tab_item += 1,
-                        "valuable" => tab_craftresources += 1,
-                        "vehicleupgrade" => tab_item += 1,
-                        "voucher" => tab_item += 1,
-                        _ => tab_item += 1,
-                    }
+    // Count the items for each tab by their category.
+    let mut counters: Vec<usize> = vec![0; tab_mapping.tabs.len()];
 
-                    is_matched = true;
-                    break;
-                }
-            }
+    for item in items.iter() {
+        let tab_position = tab_mapping.tabs.iter().position(|tab| {
+            tab.categories
+                .iter()
+                .any(|category| category.eq_ignore_ascii_case(&item.catalog.category))
+        });
+
+        if let Some(position) = tab_position {
+            counters[position] += 1;
         }
     }
 
-    // Initialize the counters
-    let counters = vec![
-        tab_tokens,
-        tab_equipment,
-        tab_craftresources,
-        tab_consumables,
-        tab_accessories,
-        tab_quest,
-        tab_ammunition,
-        tab_weapons,
-        tab_item,
-    ];
-
-    // Find the maximum value among the counters
-    let highest_counter = counters.iter().max().unwrap();
-
-    // Perform actions based on the highest counter
-    if highest_counter == &tab_tokens {
-        return InventoryItemRow::new("Tokens/Tickets".to_string(), items);
-    } else if highest_counter == &tab_equipment {
-        return InventoryItemRow::new("Equipment".to_string(), items);
-    } else if highest_counter == &tab_craftresources {
-        return InventoryItemRow::new("Outfits/Craftresources".to_string(), items);
-    } else if highest_counter == &tab_consumables {
-        return InventoryItemRow::new("Consumables".to_string(), items);
-    } else if highest_counter == &tab_accessories {
-        return InventoryItemRow::new("Accessories".to_string(), items);
-    } else if highest_counter == &tab_quest {
-        return InventoryItemRow::new("Quest Items".to_string(), items);
-    } else if highest_counter == &tab_ammunition {
-        return InventoryItemRow::new("Ammunition".to_string(), items);
-    } else if highest_counter == &tab_weapons {
-        return InventoryItemRow::new("Weapons".to_string(), items);
-    } else if highest_counter == &tab_item {
-        return InventoryItemRow::new("Items".to_string(), items);
+    // Find the tab with the highest counter.
+    let highest_position = counters
+        .iter()
+        .enumerate()
+        .filter(|(_, counter)| **counter > 0)
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))
+        .map(|(position, _)| position);
+
+    match highest_position {
+        Some(position) => {
+            InventoryItemRow::new(tab_mapping.tabs[position].name.clone(), marker, items)
+        }
+        None => InventoryItemRow::new(tab_mapping.default_tab.clone(), marker, items),
     }
-
-    InventoryItemRow::new("Items".to_string(), items)
 }
 
 /// Represents a method for finding all SGD chunks inside the inventory.
@@ -1051,12 +1419,12 @@ fn find_all_inventory_chunks(
     let mut chunks: Vec<InventoryChunk> = Vec::new();
 
     // Prepare offsets.
-    let level_offset: usize = 2;
-    let seed_offset: usize = 2;
-    let amount_offset: usize = 4;
-    let durability_offset: usize = 4;
-    let counter_stats_offset: usize = 4;
-    let space_offset: usize = 25;
+    let level_offset: usize = CHUNK_LEVEL_SIZE;
+    let seed_offset: usize = CHUNK_SEED_SIZE;
+    let amount_offset: usize = CHUNK_AMOUNT_SIZE;
+    let durability_offset: usize = CHUNK_DURABILITY_SIZE;
+    let counter_stats_offset: usize = CHUNK_COUNTER_STATS_SIZE;
+    let space_offset: usize = CHUNK_SPACE_SIZE;
     let data_offset: usize = level_offset
         + seed_offset
         + amount_offset
@@ -1171,7 +1539,7 @@ fn find_first_sgd_index(content: &[u8], start_index: usize) -> Result<usize> {
 ///
 /// ### Returns `String`
 /// The game version of the current save.
-fn get_game_version(content: &[u8]) -> String {
+pub fn get_game_version(content: &[u8]) -> String {
     // Convert the byte data to string to check regex patterns.
     let string_data: String = String::from_utf8_lossy(content).to_string();
     // The Regex pattern to match base skills.
@@ -1274,6 +1642,7 @@ fn is_savegame_between(content: &[u8], start_index: usize, end_index: usize) ->
 /// - `content`: The byte data of the inventory.
 /// - `start_index`: The start index of the search.
 /// - `amount`: The amount of chunks found for the current section.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
 /// - `logger`: The console logger that logs every event.
 /// - `is_debugging`: Indicates whether the file analyser is in debugging mode or not.
 ///
@@ -1283,6 +1652,7 @@ fn find_amount_of_matches(
     content: &[u8],
     start_index: usize,
     amount: usize,
+    item_rules: &ItemRuleEngine,
     logger: &mut ConsoleLogger,
     is_debugging: bool,
 ) -> (Vec<String>, Vec<usize>) {
@@ -1296,17 +1666,6 @@ fn find_amount_of_matches(
     // Convert the byte data to string to check regex patterns.
     let string_data = String::from_utf8_lossy(&content[start_index..]);
 
-    let nightrunner_items = vec![
-        "NightRunnerItemSGDs",
-        "ParachuteSGDs",
-        "ClimbPickaxeSGDs",
-        "RopeHookSGDs",
-        "BinocularsSGDs",
-        "LockpickItemSGDs",
-        "AntizinContainerSGDs",
-        "BicycleSGDs",
-    ];
-
     // The Regex pattern to match the sgds.
     let pattern: &str = r"(?:[a-zA-Z0-9_]{4,}(?:\x00*))*SGDs";
 
@@ -1318,8 +1677,8 @@ fn find_amount_of_matches(
         // Check if the amount of item matches is in range and if the length of the match is at least 4.
         if item_counter < amount && mat.as_str().len() > 4 {
             // Items normally always have an "_" inside their name.
-            // Except the following items: "NightRunnerItemSGDs", "ParachuteSGDs", "ClimbPickaxeSGDs", "RopeHookSGDs", "BinocularsSGDs"
-            if mat.as_str().contains("_") || nightrunner_items.contains(&mat.as_str()) {
+            // The exceptions (like "NightRunnerItemSGDs" or "ParachuteSGDs") are listed in the item rules.
+            if item_rules.matches(SGD_ENTRY_RULE, mat.as_str()) {
                 // set set the current item id.
                 let mut current_matching_value = mat.as_str().to_string();
                 let index = get_index_from_sequence(
@@ -1346,9 +1705,9 @@ fn find_amount_of_matches(
                 }
 
                 // Checks whether the match is an item or a mod.
-                if validate_item_or_mod(mat.as_str()) {
+                if validate_item_or_mod(mat.as_str(), item_rules) {
                     // Checks whether the match is bullet that acts as a mod.
-                    if validate_item_or_transmog(mat.as_str(), &last_match) {
+                    if validate_item_or_transmog(mat.as_str(), &last_match, item_rules) {
                         if is_debugging {
                             logger.log_message(&format!(
                                 "Found potential SGDs match for mod: [{}]",
@@ -1365,8 +1724,8 @@ fn find_amount_of_matches(
                         continue;
                     }
                     // Checks whether the match is an outfit that acts as a transmog.
-                    else if validate_outfit(mat.as_str())
-                        && validate_outfit(&last_match)
+                    else if validate_outfit(mat.as_str(), item_rules)
+                        && validate_outfit(&last_match, item_rules)
                         && index - (last_index + last_match.len()) <= 30
                     {
                         if is_debugging {
@@ -1582,15 +1941,31 @@ pub fn get_contents_from_file(file_path: &str) -> Result<Vec<u8>> {
     Ok(file_contents)
 }
 
-/// Represents a method for creating a backup file.
+/// Represents a method for creating the hash of the content of a save (as stored on disk).
 ///
 /// ### Parameter
-/// - `file_path`: The save file.
 /// - `file_content`: The content of the file.
-pub fn create_backup_from_file(file_path: &str, file_content: &[u8]) {
-    let backup_path = format!("{}.bak", file_path);
-    let mut file = fs::File::create(&backup_path).unwrap();
-    file.write_all(file_content).unwrap();
+///
+/// ### Returns `String`
+/// The SHA-256 hash as hex string.
+pub fn get_content_hash(file_content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(file_content))
+}
+
+/// Represents a method for removing the padding and the `SGDs` suffix of an SGD name.
+///
+/// ### Parameter
+/// - `name`: The name of the SGD.
+///
+/// ### Returns `String`
+/// The readable name.
+pub fn clean_sgd_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>()
+        .trim()
+        .trim_end_matches("SGDs")
+        .to_string()
 }
 
 /// Represents a method for converting to string and removing unnecessary characters.
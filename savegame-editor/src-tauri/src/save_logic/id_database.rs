@@ -0,0 +1,532 @@
+use crate::save_logic::file_analyser::get_content_hash;
+use crate::save_logic::id_fetcher::fetch_ids;
+use crate::save_logic::struct_data::{
+    IdConflict, IdData, IdDatabaseManifest, IdImport, IdMergeReport, IdSnapshot,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the directory of the ID database inside the app data directory.
+pub static ID_DATABASE_DIRECTORY: &str = "IDs_Database";
+
+// Defines the file and directory names inside the ID database.
+static MANIFEST_FILE: &str = "manifest.json";
+static SNAPSHOT_DIRECTORY: &str = "snapshots";
+static BUNDLED_SOURCE: &str = "bundled";
+static DISPLAY_NAME_FILE: &str = "display_names.csv";
+static DISPLAY_NAME_HEADER: &str = "name_id,ui_name";
+
+/// Represents a method for getting the directory of the current ID snapshot.
+/// If the database does not exist yet, it is created from the bundled IDs.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database (inside the app data directory).
+/// - `bundled_path`: The directory of the IDs that are shipped with the editor.
+///
+/// ### Returns `String`
+/// The directory that contains the ID files of the current snapshot.
+pub fn get_current_id_path(store_path: &String, bundled_path: &String) -> Result<String> {
+    let manifest: IdDatabaseManifest = load_manifest(store_path, bundled_path)?;
+
+    Ok(get_snapshot_path(store_path, manifest.current_version)
+        .display()
+        .to_string())
+}
+
+/// Represents a method for fetching all IDs of the current snapshot.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database (inside the app data directory).
+/// - `bundled_path`: The directory of the IDs that are shipped with the editor.
+///
+/// ### Returns `Vec<IdData>`
+/// A list of all id sections of the current snapshot.
+pub fn fetch_database_ids(store_path: &String, bundled_path: &String) -> Result<Vec<IdData>> {
+    fetch_ids(&get_current_id_path(store_path, bundled_path)?)
+}
+
+/// Represents a method for fetching the manifest with all snapshots of the ID database.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database (inside the app data directory).
+/// - `bundled_path`: The directory of the IDs that are shipped with the editor.
+///
+/// ### Returns `IdDatabaseManifest`
+/// The current version and all snapshots.
+pub fn fetch_id_snapshots(
+    store_path: &String,
+    bundled_path: &String,
+) -> Result<IdDatabaseManifest> {
+    load_manifest(store_path, bundled_path)
+}
+
+/// Represents a method for merging imported IDs into the current snapshot.
+/// The existing snapshot is never modified, the result is stored as a new snapshot.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database (inside the app data directory).
+/// - `bundled_path`: The directory of the IDs that are shipped with the editor.
+/// - `import`: The imported id sections and display names.
+///
+/// ### Returns `IdMergeReport`
+/// The report with the new version, the amount of added IDs and all conflicts.
+pub fn merge_ids(
+    store_path: &String,
+    bundled_path: &String,
+    import: IdImport,
+) -> Result<IdMergeReport> {
+    // Check if every category can be stored as file.
+    for imported_section in import.categories.iter() {
+        if !is_valid_category(imported_section.filename.trim()) {
+            return Err(format!(
+                "The category [{}] is invalid. Only letters, digits, '_' and '-' are allowed.",
+                imported_section.filename
+            )
+            .into());
+        }
+    }
+
+    let mut manifest: IdDatabaseManifest = load_manifest(store_path, bundled_path)?;
+
+    apply_import(store_path, &mut manifest, import)
+}
+
+/// Represents a method for merging imported IDs into the current snapshot of a manifest.
+/// If something changed, the result is stored as a new snapshot and the manifest is written.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database.
+/// - `manifest`: The manifest of the ID database.
+/// - `import`: The imported id sections and display names.
+///
+/// ### Returns `IdMergeReport`
+/// The report with the new version, the amount of added IDs and all conflicts.
+fn apply_import(
+    store_path: &String,
+    manifest: &mut IdDatabaseManifest,
+    import: IdImport,
+) -> Result<IdMergeReport> {
+    let current_path: PathBuf = get_snapshot_path(store_path, manifest.current_version);
+    let mut categories: Vec<IdData> =
+        dedupe_categories(fetch_ids(&current_path.display().to_string())?);
+    let mut display_names: BTreeMap<String, String> = read_display_names(&current_path)?;
+
+    // Map every existing ID to its category.
+    let mut known: HashMap<String, String> = HashMap::new();
+
+    for id_section in categories.iter() {
+        for id_name in id_section.ids.iter() {
+            known
+                .entry(id_name.clone())
+                .or_insert(id_section.filename.clone());
+        }
+    }
+
+    let mut added: usize = 0;
+    let mut duplicates: usize = 0;
+    let mut new_categories: Vec<String> = Vec::new();
+    let mut conflicts: Vec<IdConflict> = Vec::new();
+
+    for imported_section in import.categories {
+        let category: String = imported_section.filename.trim().to_string();
+
+        if category.is_empty() {
+            continue;
+        }
+
+        for id_name in imported_section.ids.iter() {
+            let id_name: String = id_name.trim().to_string();
+
+            if id_name.is_empty() {
+                continue;
+            }
+
+            // Check if the ID is already known.
+            if let Some(existing_category) = known.get(&id_name) {
+                if *existing_category == category {
+                    duplicates += 1;
+                } else {
+                    conflicts.push(IdConflict::new(
+                        id_name.clone(),
+                        existing_category.clone(),
+                        category.clone(),
+                    ));
+                }
+                continue;
+            }
+
+            match categories.iter_mut().find(|s| s.filename == category) {
+                Some(id_section) => id_section.ids.push(id_name.clone()),
+                None => {
+                    new_categories.push(category.clone());
+                    categories.push(IdData::new(category.clone(), vec![id_name.clone()]));
+                }
+            }
+
+            known.insert(id_name, category.clone());
+            added += 1;
+        }
+    }
+
+    // Existing display names are kept.
+    let mut added_display_names: usize = 0;
+
+    for (id_name, display_name) in import.display_names {
+        let id_name: String = id_name.trim().to_string();
+        let display_name: String = display_name.trim().to_string();
+
+        if id_name.is_empty() || display_name.is_empty() || display_names.contains_key(&id_name) {
+            continue;
+        }
+
+        display_names.insert(id_name, display_name);
+        added_display_names += 1;
+    }
+
+    // Only create a new snapshot if something changed.
+    if added > 0 || added_display_names > 0 {
+        let version: u32 = get_next_version(manifest);
+        write_snapshot(store_path, version, &categories, &display_names)?;
+
+        let source: String = if import.game_version.is_empty() {
+            import.source
+        } else {
+            format!("{} ({})", import.source, import.game_version)
+        };
+
+        manifest.snapshots.push(IdSnapshot::new(
+            version,
+            get_timestamp(),
+            source,
+            count_ids(&categories),
+        ));
+        manifest.current_version = version;
+        write_manifest(store_path, manifest)?;
+    }
+
+    Ok(IdMergeReport::new(
+        manifest.current_version,
+        added,
+        added_display_names,
+        duplicates,
+        new_categories,
+        conflicts,
+    ))
+}
+
+/// Represents a method for rolling the ID database back (or forward) to a snapshot.
+/// No snapshot is deleted, therefore every rollback can be undone.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database (inside the app data directory).
+/// - `bundled_path`: The directory of the IDs that are shipped with the editor.
+/// - `version`: The version of the snapshot.
+///
+/// ### Returns `IdDatabaseManifest`
+/// The updated manifest.
+pub fn rollback_ids(
+    store_path: &String,
+    bundled_path: &String,
+    version: u32,
+) -> Result<IdDatabaseManifest> {
+    let mut manifest: IdDatabaseManifest = load_manifest(store_path, bundled_path)?;
+
+    if !manifest.snapshots.iter().any(|s| s.version == version) {
+        return Err(format!("There is no ID snapshot with version [{}].", version).into());
+    }
+
+    if !get_snapshot_path(store_path, version).is_dir() {
+        return Err(format!("The files of the ID snapshot [{}] are missing.", version).into());
+    }
+
+    manifest.current_version = version;
+    write_manifest(store_path, &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Represents a method for loading the manifest of the ID database.
+/// If the database does not exist yet, the bundled IDs are stored as the first snapshot. If the bundled IDs changed
+/// since they were merged the last time (e.g. after an update of the editor), the new IDs are merged as a new snapshot.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database.
+/// - `bundled_path`: The directory of the IDs that are shipped with the editor.
+///
+/// ### Returns `IdDatabaseManifest`
+/// The manifest of the ID database.
+fn load_manifest(store_path: &String, bundled_path: &String) -> Result<IdDatabaseManifest> {
+    let manifest_path: PathBuf = Path::new(store_path).join(MANIFEST_FILE);
+
+    if manifest_path.is_file() {
+        let file_content = fs::read_to_string(&manifest_path)
+            .map_err(|e| format!("Error reading the ID database manifest: {}", e))?;
+
+        let manifest: IdDatabaseManifest = serde_json::from_str(&file_content)
+            .map_err(|e| format!("Error parsing the ID database manifest: {}", e))?;
+
+        // The database stays usable if the bundled IDs cannot be read.
+        let categories: Vec<IdData> = match fetch_ids(bundled_path) {
+            Ok(categories) => dedupe_categories(categories),
+            Err(_) => return Ok(manifest),
+        };
+        let bundled_hash: String = get_bundled_hash(&categories);
+
+        if categories.is_empty() || manifest.bundled_hash == bundled_hash {
+            return Ok(manifest);
+        }
+
+        let mut updated_manifest: IdDatabaseManifest = manifest.clone();
+        let import: IdImport = IdImport::new(
+            BUNDLED_SOURCE.to_string(),
+            String::new(),
+            categories,
+            HashMap::new(),
+        );
+        updated_manifest.bundled_hash = bundled_hash;
+
+        let result: Result<()> = apply_import(store_path, &mut updated_manifest, import)
+            .and_then(|_| write_manifest(store_path, &updated_manifest));
+
+        return match result {
+            Ok(()) => Ok(updated_manifest),
+            Err(err) => {
+                println!("[WARNING]: The bundled IDs could not be merged: {}", err);
+                Ok(manifest)
+            }
+        };
+    }
+
+    // Initialize the database with the bundled IDs.
+    let categories: Vec<IdData> = dedupe_categories(fetch_ids(bundled_path)?);
+
+    if categories.is_empty() {
+        return Err("Could not find any bundled IDs to initialize the ID database.".into());
+    }
+
+    write_snapshot(store_path, 1, &categories, &BTreeMap::new())?;
+
+    let manifest: IdDatabaseManifest = IdDatabaseManifest::new(
+        1,
+        vec![IdSnapshot::new(
+            1,
+            get_timestamp(),
+            BUNDLED_SOURCE.to_string(),
+            count_ids(&categories),
+        )],
+        get_bundled_hash(&categories),
+    );
+    write_manifest(store_path, &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Represents a method for getting the hash of the bundled IDs.
+///
+/// ### Parameter
+/// - `categories`: The deduplicated id sections of the bundled IDs.
+///
+/// ### Returns `String`
+/// The SHA-256 hash of all categories and IDs.
+fn get_bundled_hash(categories: &[IdData]) -> String {
+    let content: String = categories
+        .iter()
+        .map(|id_section| format!("[{}]\n{}", id_section.filename, id_section.ids.join("\n")))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    get_content_hash(content.as_bytes())
+}
+
+/// Represents a method for writing the manifest of the ID database.
+/// The manifest is written to a temporary file first, so it is never left half written.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database.
+/// - `manifest`: The manifest that should be written.
+fn write_manifest(store_path: &String, manifest: &IdDatabaseManifest) -> Result<()> {
+    let manifest_path: PathBuf = Path::new(store_path).join(MANIFEST_FILE);
+    let temp_path: PathBuf = Path::new(store_path).join(format!("{}.tmp", MANIFEST_FILE));
+
+    fs::create_dir_all(store_path)?;
+    fs::write(&temp_path, serde_json::to_string_pretty(manifest)?)?;
+    fs::rename(&temp_path, &manifest_path)?;
+
+    Ok(())
+}
+
+/// Represents a method for writing all id sections as a snapshot.
+/// The files are written to a temporary directory first, which is renamed once all files are written.
+/// The version must not be referenced by the manifest yet.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database.
+/// - `version`: The version of the snapshot.
+/// - `categories`: The id sections of the snapshot.
+/// - `display_names`: The display name for each ID.
+fn write_snapshot(
+    store_path: &String,
+    version: u32,
+    categories: &[IdData],
+    display_names: &BTreeMap<String, String>,
+) -> Result<()> {
+    let snapshot_path: PathBuf = get_snapshot_path(store_path, version);
+    let temp_path: PathBuf = Path::new(store_path)
+        .join(SNAPSHOT_DIRECTORY)
+        .join(format!(".{}.tmp", version));
+
+    // The version is not part of the manifest, so an existing directory was left by a write that did not finish.
+    if snapshot_path.exists() {
+        fs::remove_dir_all(&snapshot_path)?;
+    }
+
+    if temp_path.exists() {
+        fs::remove_dir_all(&temp_path)?;
+    }
+
+    fs::create_dir_all(&temp_path)?;
+
+    for id_section in categories.iter() {
+        fs::write(
+            temp_path.join(format!("{}.txt", id_section.filename)),
+            id_section.ids.join("\n"),
+        )?;
+    }
+
+    if !display_names.is_empty() {
+        let mut lines: Vec<String> = vec![DISPLAY_NAME_HEADER.to_string()];
+        lines.extend(
+            display_names
+                .iter()
+                .map(|(id_name, display_name)| format!("{},{}", id_name, display_name)),
+        );
+        fs::write(temp_path.join(DISPLAY_NAME_FILE), lines.join("\n"))?;
+    }
+
+    fs::rename(&temp_path, &snapshot_path)?;
+
+    Ok(())
+}
+
+/// Represents a method for reading the display names of a snapshot.
+///
+/// ### Parameter
+/// - `snapshot_path`: The directory of the snapshot.
+///
+/// ### Returns `BTreeMap<String, String>`
+/// The display name for each ID.
+fn read_display_names(snapshot_path: &Path) -> Result<BTreeMap<String, String>> {
+    let mut display_names: BTreeMap<String, String> = BTreeMap::new();
+    let file_path: PathBuf = snapshot_path.join(DISPLAY_NAME_FILE);
+
+    if !file_path.is_file() {
+        return Ok(display_names);
+    }
+
+    for line in fs::read_to_string(&file_path)?.lines().skip(1) {
+        if let Some((id_name, display_name)) = line.split_once(',') {
+            display_names.insert(id_name.to_string(), display_name.to_string());
+        }
+    }
+
+    Ok(display_names)
+}
+
+/// Represents a method for checking whether a category name can be used as file name.
+///
+/// ### Parameter
+/// - `category`: The name of the category.
+///
+/// ### Returns `bool`
+/// Indicates whether the category is valid.
+pub fn is_valid_category(category: &str) -> bool {
+    !category.is_empty()
+        && category
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Represents a method for removing empty and duplicated IDs within each category.
+///
+/// ### Parameter
+/// - `categories`: The id sections.
+///
+/// ### Returns `Vec<IdData>`
+/// The id sections without duplicates (sorted by category).
+fn dedupe_categories(categories: Vec<IdData>) -> Vec<IdData> {
+    let mut deduped: Vec<IdData> = categories
+        .into_iter()
+        .map(|id_section| {
+            let mut seen: HashSet<String> = HashSet::new();
+            let ids: Vec<String> = id_section
+                .ids
+                .iter()
+                .map(|id_name| id_name.trim().to_string())
+                .filter(|id_name| !id_name.is_empty() && seen.insert(id_name.clone()))
+                .collect();
+
+            IdData::new(id_section.filename, ids)
+        })
+        .collect();
+
+    deduped.sort_by(|a, b| a.filename.cmp(&b.filename));
+    deduped
+}
+
+/// Represents a method for getting the directory of a snapshot.
+///
+/// ### Parameter
+/// - `store_path`: The directory of the ID database.
+/// - `version`: The version of the snapshot.
+///
+/// ### Returns `PathBuf`
+/// The directory of the snapshot.
+fn get_snapshot_path(store_path: &String, version: u32) -> PathBuf {
+    Path::new(store_path)
+        .join(SNAPSHOT_DIRECTORY)
+        .join(version.to_string())
+}
+
+/// Represents a method for getting the next free snapshot version.
+///
+/// ### Parameter
+/// - `manifest`: The manifest of the ID database.
+///
+/// ### Returns `u32`
+/// The next version.
+fn get_next_version(manifest: &IdDatabaseManifest) -> u32 {
+    manifest
+        .snapshots
+        .iter()
+        .map(|s| s.version)
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+/// Represents a method for counting all IDs.
+///
+/// ### Parameter
+/// - `categories`: The id sections.
+///
+/// ### Returns `usize`
+/// The amount of IDs.
+fn count_ids(categories: &[IdData]) -> usize {
+    categories.iter().map(|s| s.ids.len()).sum()
+}
+
+/// Represents a method for getting the current unix timestamp.
+///
+/// ### Returns `u64`
+/// The seconds since the unix epoch.
+fn get_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
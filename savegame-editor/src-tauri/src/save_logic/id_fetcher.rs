@@ -1,5 +1,4 @@
 use crate::save_logic::struct_data::IdData;
-use std::io;
 use std::{error::Error, fs, path::Path};
 
 // Define global result definition for easier readability.
@@ -11,12 +10,16 @@ type Result<T> = std::result::Result<T, Box<dyn Error>>;
 /// A list of all fetched id sections.
 pub fn fetch_ids(id_path: &String) -> Result<Vec<IdData>> {
     let mut id_datas: Vec<IdData> = Vec::new();
-    let entries = fs::read_dir(id_path).unwrap();
+    let entries =
+        fs::read_dir(id_path).map_err(|e| format!("Error reading the ID directory: {}", e))?;
 
     for entry in entries {
         let entry = entry.map_err(|e| format!("Error reading directory entry: {}", e))?;
 
-        if entry.file_type().map_or(false, |t| t.is_file()) {
+        // Only text files contain IDs (snapshots also store the display names as CSV).
+        if entry.file_type().map_or(false, |t| t.is_file())
+            && entry.path().extension().and_then(|e| e.to_str()) == Some("txt")
+        {
             if let Ok(id_data) = read_id_file(entry.path().to_str().ok_or("Invalid file path")?) {
                 id_datas.push(id_data);
             }
@@ -26,26 +29,6 @@ pub fn fetch_ids(id_path: &String) -> Result<Vec<IdData>> {
     Ok(id_datas)
 }
 
-pub fn update_ids(new_file_path: &str, source_path: &str) -> io::Result<()> {
-    // Iterate over the contents of the source directory
-    for entry in fs::read_dir(source_path)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        // Remove each file or directory in the source directory
-        if path.is_file() {
-            fs::remove_file(&path)?;
-        } else if path.is_dir() {
-            fs::remove_dir_all(&path)?;
-        }
-    }
-
-    // Copy all contents from the new file path to the source directory
-    fs::copy(new_file_path, source_path)?;
-
-    Ok(())
-}
-
 /// Represents a method for reading a single id file and retrieving the IDs.
 ///
 /// ### Parameter
@@ -53,7 +36,7 @@ pub fn update_ids(new_file_path: &str, source_path: &str) -> io::Result<()> {
 ///
 /// ### Returns `Result<IdData>`
 /// The found IDs from the selected file.
-fn read_id_file(file_path: &str) -> Result<IdData> {
+pub fn read_id_file(file_path: &str) -> Result<IdData> {
     let path = Path::new(file_path);
     let filename = path
         .file_stem()
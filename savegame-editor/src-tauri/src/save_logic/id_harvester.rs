@@ -0,0 +1,133 @@
+use crate::save_logic::file_analyser::clean_sgd_name;
+use crate::save_logic::item_catalog::{find_catalog_item, UNKNOWN_CATEGORY};
+use crate::save_logic::struct_data::{
+    IdData, IdImport, InventoryItemRow, InventoryTabMapping, ItemCatalog, UnknownId,
+};
+use std::collections::HashMap;
+use std::error::Error;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the source of harvested IDs inside the ID database.
+static HARVEST_SOURCE: &str = "harvested from save";
+
+/// Represents a method for collecting every item and mod ID of a save that is not part of the ID database.
+///
+/// ### Parameter
+/// - `items`: The inventory rows of the save.
+/// - `catalog`: The item catalog with a record for every known ID.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
+///
+/// ### Returns `Vec<UnknownId>`
+/// The unknown IDs (each ID once) with a suggested category.
+pub fn harvest_unknown_ids(
+    items: &[InventoryItemRow],
+    catalog: &ItemCatalog,
+    tab_mapping: &InventoryTabMapping,
+) -> Vec<UnknownId> {
+    let mut unknown_ids: Vec<UnknownId> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for row in items.iter() {
+        // The first category of the tab is the fallback suggestion.
+        let tab_category: String = tab_mapping
+            .tabs
+            .iter()
+            .find(|tab| tab.name == row.name)
+            .and_then(|tab| tab.categories.first().cloned())
+            .unwrap_or(UNKNOWN_CATEGORY.to_string());
+
+        for item in row.inventory_items.iter() {
+            let mut names: Vec<(String, usize, bool)> =
+                vec![(item.name.clone(), item.index, false)];
+            names.extend(
+                item.mod_data
+                    .iter()
+                    .map(|mod_item| (clean_sgd_name(&mod_item.name), mod_item.index, true)),
+            );
+
+            for (name, index, is_mod) in names {
+                if !is_valid_id(&name) || is_known_id(catalog, &name) {
+                    continue;
+                }
+
+                if let Some(position) = positions.get(&name) {
+                    unknown_ids[*position].occurrences += 1;
+                    continue;
+                }
+
+                let suggested_category: String = match find_catalog_item(catalog, &name).category {
+                    category if category != UNKNOWN_CATEGORY => category,
+                    _ if is_mod => UNKNOWN_CATEGORY.to_string(),
+                    _ => tab_category.clone(),
+                };
+
+                positions.insert(name.clone(), unknown_ids.len());
+                unknown_ids.push(UnknownId::new(
+                    name,
+                    index,
+                    row.name.clone(),
+                    is_mod,
+                    suggested_category,
+                ));
+            }
+        }
+    }
+
+    unknown_ids
+}
+
+/// Represents a method for preparing harvested IDs to be merged into the ID database.
+///
+/// ### Parameter
+/// - `ids`: The harvested IDs.
+/// - `category`: The category the IDs should be added to.
+///
+/// ### Returns `IdImport`
+/// The import for the ID database.
+pub fn create_harvest_import(ids: Vec<String>, category: &str) -> Result<IdImport> {
+    let ids: Vec<String> = ids
+        .iter()
+        .map(|id_name| clean_sgd_name(id_name))
+        .filter(|id_name| is_valid_id(id_name))
+        .collect();
+
+    if ids.is_empty() {
+        return Err("There are no valid IDs to add.".into());
+    }
+
+    Ok(IdImport::new(
+        HARVEST_SOURCE.to_string(),
+        String::new(),
+        vec![IdData::new(category.trim().to_string(), ids)],
+        HashMap::new(),
+    ))
+}
+
+/// Represents a method for checking whether an ID is part of the ID database.
+/// Records that are only known from the patched items lists or the display names do not count.
+///
+/// ### Parameter
+/// - `catalog`: The item catalog.
+/// - `name`: The ID.
+///
+/// ### Returns `bool`
+/// Indicates whether the ID is known.
+fn is_known_id(catalog: &ItemCatalog, name: &str) -> bool {
+    catalog
+        .items
+        .get(&name.to_lowercase())
+        .is_some_and(|item| item.category != UNKNOWN_CATEGORY)
+}
+
+/// Represents a method for checking whether a name can be stored as ID.
+///
+/// ### Parameter
+/// - `name`: The name of the SGD.
+///
+/// ### Returns `bool`
+/// Indicates whether the name is a valid ID.
+fn is_valid_id(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_graphic())
+}
@@ -0,0 +1,312 @@
+use crate::save_logic::id_database::is_valid_category;
+use crate::save_logic::id_fetcher::{fetch_ids, read_id_file};
+use crate::save_logic::struct_data::{IdBundleManifest, IdData, IdImport};
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the file name of the manifest inside an ID bundle.
+static BUNDLE_MANIFEST_FILE: &str = "manifest.json";
+
+// Defines the columns of an ID CSV file (for example: "name_id,category,ui_name").
+static CSV_ID_COLUMN: &str = "name_id";
+static CSV_CATEGORY_COLUMN: &str = "category";
+static CSV_DISPLAY_NAME_COLUMN: &str = "ui_name";
+
+/// Represents a method for reading IDs that should be imported into the ID database.
+/// Supported are zip and tar (optionally gzip compressed) bundles with a manifest,
+/// CSV files with `name_id,category,ui_name` columns, single ID files and directories of ID files.
+///
+/// ### Parameter
+/// - `import_path`: The path of the bundle, file or directory.
+///
+/// ### Returns `IdImport`
+/// The validated id sections and display names.
+pub fn read_id_import(import_path: &str) -> Result<IdImport> {
+    let path = Path::new(import_path);
+    let lowercase_path: String = import_path.to_lowercase();
+
+    if path.is_dir() {
+        return Ok(IdImport::new(
+            import_path.to_string(),
+            String::new(),
+            fetch_ids(&import_path.to_string())?,
+            HashMap::new(),
+        ));
+    }
+
+    if !path.is_file() {
+        return Err(format!("The import path [{}] does not exist.", import_path).into());
+    }
+
+    if lowercase_path.ends_with(".zip") {
+        let entries: HashMap<String, Vec<u8>> = read_zip_entries(path)?;
+        return parse_id_bundle(import_path, &entries);
+    }
+
+    if lowercase_path.ends_with(".tar.gz") || lowercase_path.ends_with(".tgz") {
+        let entries: HashMap<String, Vec<u8>> =
+            read_tar_entries(GzDecoder::new(File::open(path)?))?;
+        return parse_id_bundle(import_path, &entries);
+    }
+
+    if lowercase_path.ends_with(".tar") {
+        let entries: HashMap<String, Vec<u8>> = read_tar_entries(File::open(path)?)?;
+        return parse_id_bundle(import_path, &entries);
+    }
+
+    if lowercase_path.ends_with(".csv") {
+        return parse_id_csv(import_path, &fs::read_to_string(path)?);
+    }
+
+    Ok(IdImport::new(
+        import_path.to_string(),
+        String::new(),
+        vec![read_id_file(import_path)?],
+        HashMap::new(),
+    ))
+}
+
+/// Represents a method for reading all files of a zip archive.
+///
+/// ### Parameter
+/// - `path`: The path of the zip archive.
+///
+/// ### Returns `HashMap<String, Vec<u8>>`
+/// The content of every file by its path inside the archive.
+fn read_zip_entries(path: &Path) -> Result<HashMap<String, Vec<u8>>> {
+    let mut archive = zip::ZipArchive::new(File::open(path)?)
+        .map_err(|e| format!("Error reading the zip bundle: {}", e))?;
+    let mut entries: HashMap<String, Vec<u8>> = HashMap::new();
+
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Error reading the zip bundle: {}", e))?;
+
+        if !file.is_file() {
+            continue;
+        }
+
+        let mut content: Vec<u8> = Vec::new();
+        file.read_to_end(&mut content)?;
+        entries.insert(file.name().replace('\\', "/"), content);
+    }
+
+    Ok(entries)
+}
+
+/// Represents a method for reading all files of a tar archive.
+///
+/// ### Parameter
+/// - `reader`: The reader of the (already decompressed) tar archive.
+///
+/// ### Returns `HashMap<String, Vec<u8>>`
+/// The content of every file by its path inside the archive.
+fn read_tar_entries<R: Read>(reader: R) -> Result<HashMap<String, Vec<u8>>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries: HashMap<String, Vec<u8>> = HashMap::new();
+
+    for entry in archive
+        .entries()
+        .map_err(|e| format!("Error reading the tar bundle: {}", e))?
+    {
+        let mut entry = entry.map_err(|e| format!("Error reading the tar bundle: {}", e))?;
+
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let name: String = entry.path()?.to_string_lossy().replace('\\', "/");
+        let mut content: Vec<u8> = Vec::new();
+        entry.read_to_end(&mut content)?;
+        entries.insert(name.trim_start_matches("./").to_string(), content);
+    }
+
+    Ok(entries)
+}
+
+/// Represents a method for validating an ID bundle and reading its categories.
+/// Every category of the manifest has to exist inside the bundle and match its checksum.
+///
+/// ### Parameter
+/// - `source`: The path of the bundle.
+/// - `entries`: The content of every file inside the bundle.
+///
+/// ### Returns `IdImport`
+/// The validated id sections.
+fn parse_id_bundle(source: &str, entries: &HashMap<String, Vec<u8>>) -> Result<IdImport> {
+    // The manifest can be stored inside a top level directory of the bundle.
+    let manifest_key: &String = entries
+        .keys()
+        .filter(|name| {
+            *name == BUNDLE_MANIFEST_FILE || name.ends_with(&format!("/{}", BUNDLE_MANIFEST_FILE))
+        })
+        .min_by_key(|name| name.len())
+        .ok_or("The ID bundle does not contain a manifest.json.")?;
+    let base_path: &str = manifest_key.trim_end_matches(BUNDLE_MANIFEST_FILE);
+
+    let manifest: IdBundleManifest = serde_json::from_slice(&entries[manifest_key])
+        .map_err(|e| format!("Error parsing the ID bundle manifest: {}", e))?;
+
+    if manifest.categories.is_empty() {
+        return Err("The ID bundle manifest does not define any categories.".into());
+    }
+
+    let mut categories: Vec<IdData> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for category in manifest.categories.iter() {
+        if !is_valid_category(&category.name) {
+            return Err(format!(
+                "The category [{}] of the ID bundle is invalid.",
+                category.name
+            )
+            .into());
+        }
+
+        if !seen.insert(category.name.clone()) {
+            return Err(format!("The category [{}] is listed twice.", category.name).into());
+        }
+
+        let content: &Vec<u8> = entries
+            .get(&format!("{}{}", base_path, category.file))
+            .ok_or(format!(
+                "The ID bundle is missing the file [{}].",
+                category.file
+            ))?;
+
+        let checksum: String = format!("{:x}", Sha256::digest(content));
+
+        if !checksum.eq_ignore_ascii_case(category.sha256.trim()) {
+            return Err(format!(
+                "The checksum of [{}] does not match the manifest (expected [{}], found [{}]).",
+                category.file, category.sha256, checksum
+            )
+            .into());
+        }
+
+        let text: String = String::from_utf8(content.clone())
+            .map_err(|_| format!("The file [{}] is not valid UTF-8.", category.file))?;
+        let ids: Vec<String> = text
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        if ids.is_empty() {
+            return Err(format!("The file [{}] does not contain any IDs.", category.file).into());
+        }
+
+        categories.push(IdData::new(category.name.clone(), ids));
+    }
+
+    Ok(IdImport::new(
+        source.to_string(),
+        manifest.game_version,
+        categories,
+        HashMap::new(),
+    ))
+}
+
+/// Represents a method for validating an ID CSV file and reading its categories and display names.
+///
+/// ### Parameter
+/// - `source`: The path of the CSV file.
+/// - `content`: The content of the CSV file.
+///
+/// ### Returns `IdImport`
+/// The validated id sections and display names.
+fn parse_id_csv(source: &str, content: &str) -> Result<IdImport> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let header: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Error reading the ID CSV file: {}", e))?
+        .iter()
+        .map(|c| c.to_lowercase())
+        .collect();
+
+    if header.iter().all(|c| c.is_empty()) {
+        return Err("The ID CSV file is empty.".into());
+    }
+
+    let id_column: usize = header
+        .iter()
+        .position(|c| c == CSV_ID_COLUMN)
+        .ok_or(format!(
+            "The ID CSV file has no [{}] column.",
+            CSV_ID_COLUMN
+        ))?;
+    let category_column: usize =
+        header
+            .iter()
+            .position(|c| c == CSV_CATEGORY_COLUMN)
+            .ok_or(format!(
+                "The ID CSV file has no [{}] column.",
+                CSV_CATEGORY_COLUMN
+            ))?;
+    let display_name_column: Option<usize> =
+        header.iter().position(|c| c == CSV_DISPLAY_NAME_COLUMN);
+
+    let mut categories: Vec<IdData> = Vec::new();
+    let mut display_names: HashMap<String, String> = HashMap::new();
+    let mut invalid_lines: Vec<u64> = Vec::new();
+
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Error reading the ID CSV file: {}", e))?;
+        let line: u64 = record.position().map_or(0, |position| position.line());
+
+        if record.iter().all(|cell| cell.is_empty()) {
+            continue;
+        }
+
+        let id_name: &str = record.get(id_column).unwrap_or_default();
+        let category: &str = record.get(category_column).unwrap_or_default();
+
+        if id_name.is_empty() || !is_valid_category(category) {
+            invalid_lines.push(line);
+            continue;
+        }
+
+        match categories.iter_mut().find(|s| s.filename == category) {
+            Some(id_section) => id_section.ids.push(id_name.to_string()),
+            None => categories.push(IdData::new(category.to_string(), vec![id_name.to_string()])),
+        }
+
+        if let Some(display_name) = display_name_column.and_then(|i| record.get(i)) {
+            if !display_name.is_empty() {
+                display_names.insert(id_name.to_string(), display_name.to_string());
+            }
+        }
+    }
+
+    if !invalid_lines.is_empty() {
+        return Err(format!(
+            "The ID CSV file contains invalid rows (missing ID or invalid category) at lines {:?}.",
+            invalid_lines
+        )
+        .into());
+    }
+
+    if categories.is_empty() {
+        return Err("The ID CSV file does not contain any IDs.".into());
+    }
+
+    Ok(IdImport::new(
+        source.to_string(),
+        String::new(),
+        categories,
+        display_names,
+    ))
+}
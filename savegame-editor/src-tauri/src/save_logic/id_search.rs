@@ -0,0 +1,152 @@
+use crate::save_logic::struct_data::{CatalogItem, IdSearchPage, IdSearchResult, ItemCatalog};
+
+// Defines the limits of a result page.
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+
+// Defines the scores of the different match types (higher is better).
+const EXACT_SCORE: u32 = 1000;
+const PREFIX_SCORE: u32 = 900;
+const CONTAINS_SCORE: u32 = 700;
+const TOKEN_SCORE: u32 = 500;
+const FUZZY_SCORE: u32 = 300;
+
+/// Represents a method for searching the item catalog by ID and display name.
+/// An empty query lists all items of the selected categories.
+///
+/// ### Parameter
+/// - `catalog`: The item catalog.
+/// - `query`: The search text.
+/// - `categories`: The categories the results are limited to (empty for all categories).
+/// - `page`: The page of the results (starting at 0).
+/// - `page_size`: The amount of results per page.
+///
+/// ### Returns `IdSearchPage`
+/// The ranked results of the requested page and the total amount of results.
+pub fn search_catalog(
+    catalog: &ItemCatalog,
+    query: &str,
+    categories: &[String],
+    page: usize,
+    page_size: usize,
+) -> IdSearchPage {
+    let query_text: String = query.trim().to_lowercase();
+    let page_size: usize = match page_size {
+        0 => DEFAULT_PAGE_SIZE,
+        size => size.min(MAX_PAGE_SIZE),
+    };
+
+    let mut results: Vec<IdSearchResult> = catalog
+        .items
+        .values()
+        .filter(|item| {
+            categories.is_empty()
+                || categories
+                    .iter()
+                    .any(|category| category.eq_ignore_ascii_case(&item.category))
+        })
+        .filter_map(|item| score_item(item, &query_text))
+        .collect();
+
+    // Best score first, shorter IDs first for equal scores.
+    results.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.item.id.len().cmp(&b.item.id.len()))
+            .then_with(|| a.item.id.cmp(&b.item.id))
+    });
+
+    let total: usize = results.len();
+    let page_results: Vec<IdSearchResult> = results
+        .into_iter()
+        .skip(page.saturating_mul(page_size))
+        .take(page_size)
+        .collect();
+
+    IdSearchPage::new(query.to_string(), total, page, page_size, page_results)
+}
+
+/// Represents a method for scoring an item against the query.
+///
+/// ### Parameter
+/// - `item`: The catalog record.
+/// - `query`: The lowercase search text.
+///
+/// ### Returns `Option<IdSearchResult>`
+/// The result with the best score of the ID and the display name, `None` if nothing matches.
+fn score_item(item: &CatalogItem, query: &str) -> Option<IdSearchResult> {
+    if query.is_empty() {
+        return Some(IdSearchResult::new(item.clone(), 0, String::new()));
+    }
+
+    let id_score: Option<u32> = score_text(&item.id.to_lowercase(), query);
+    let display_score: Option<u32> = if item.display_name == item.id {
+        None
+    } else {
+        score_text(&item.display_name.to_lowercase(), query)
+    };
+
+    match (id_score, display_score) {
+        (Some(id), Some(display)) if display > id => Some(IdSearchResult::new(
+            item.clone(),
+            display,
+            "display_name".to_string(),
+        )),
+        (Some(id), _) => Some(IdSearchResult::new(item.clone(), id, "id".to_string())),
+        (None, Some(display)) => Some(IdSearchResult::new(
+            item.clone(),
+            display,
+            "display_name".to_string(),
+        )),
+        (None, None) => None,
+    }
+}
+
+/// Represents a method for scoring a text against the query.
+/// Exact, prefix and substring matches rank above matches of all words (in any order),
+/// which rank above fuzzy matches (all characters in the same order).
+///
+/// ### Parameter
+/// - `text`: The lowercase text.
+/// - `query`: The lowercase search text.
+///
+/// ### Returns `Option<u32>`
+/// The score of the match, `None` if the text does not match.
+fn score_text(text: &str, query: &str) -> Option<u32> {
+    if text == query {
+        return Some(EXACT_SCORE);
+    }
+
+    if text.starts_with(query) {
+        return Some(PREFIX_SCORE - ((text.len() - query.len()).min(100) as u32));
+    }
+
+    if let Some(position) = text.find(query) {
+        return Some(CONTAINS_SCORE - (position.min(100) as u32));
+    }
+
+    let tokens: Vec<&str> = query
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    if tokens.len() > 1 && tokens.iter().all(|token| text.contains(token)) {
+        return Some(TOKEN_SCORE);
+    }
+
+    // Check if all characters appear in the same order and count the skipped characters.
+    let mut gaps: usize = 0;
+    let mut text_chars = text.chars();
+
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        loop {
+            match text_chars.next() {
+                Some(text_char) if text_char == query_char => break,
+                Some(_) => gaps += 1,
+                None => return None,
+            }
+        }
+    }
+
+    Some(FUZZY_SCORE - (gaps.min(200) as u32))
+}
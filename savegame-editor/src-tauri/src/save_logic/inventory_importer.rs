@@ -0,0 +1,288 @@
+use crate::save_logic::file_analyser::{edit_inventory_item_chunk, find_byte_changes};
+use crate::save_logic::struct_data::{
+    InventoryEditChange, InventoryEditIssue, InventoryEditPreview, InventoryItem, SaveFile,
+};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::str::FromStr;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the columns that identify an inventory item (as written by the export).
+static INDEX_COLUMN: &str = "index";
+static ID_COLUMN: &str = "id";
+
+// Defines the columns that can be edited.
+static LEVEL_COLUMN: &str = "level";
+static SEED_COLUMN: &str = "seed";
+static AMOUNT_COLUMN: &str = "amount";
+static DURABILITY_COLUMN: &str = "durability";
+
+/// Represents a method for previewing the edits of an inventory CSV file (created by the export).
+/// Rows are matched by their offset and ID, empty cells keep the current value.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+/// - `csv_path`: The path of the edited CSV file.
+///
+/// ### Returns `InventoryEditPreview`
+/// The changed fields, the resulting byte changes and every invalid row.
+pub fn preview_inventory_csv(save_file: &SaveFile, csv_path: &str) -> Result<InventoryEditPreview> {
+    let (preview, _) = create_inventory_csv_edits(save_file, csv_path)?;
+
+    Ok(preview)
+}
+
+/// Represents a method for applying the edits of an inventory CSV file.
+/// Nothing is applied if any row of the file is invalid.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+/// - `csv_path`: The path of the edited CSV file.
+///
+/// ### Returns `Vec<u8>`
+/// The new content of the save file.
+pub fn apply_inventory_csv(save_file: &SaveFile, csv_path: &str) -> Result<Vec<u8>> {
+    let (preview, content) = create_inventory_csv_edits(save_file, csv_path)?;
+
+    if let Some(issue) = preview.issues.first() {
+        return Err(format!(
+            "The CSV file contains {} invalid rows (line {}: {}).",
+            preview.issues.len(),
+            issue.line,
+            issue.message
+        )
+        .into());
+    }
+
+    Ok(content)
+}
+
+/// Represents a method for validating the rows of an inventory CSV file and applying them to a copy of the save content.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+/// - `csv_path`: The path of the edited CSV file.
+///
+/// ### Returns `(InventoryEditPreview, Vec<u8>)`
+/// The preview of the edits and the edited content.
+fn create_inventory_csv_edits(
+    save_file: &SaveFile,
+    csv_path: &str,
+) -> Result<(InventoryEditPreview, Vec<u8>)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(csv_path)
+        .map_err(|e| format!("Error reading the CSV file: {}", e))?;
+
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|header| header.to_lowercase())
+        .collect();
+    let find_column = |name: &str| headers.iter().position(|header| header == name);
+
+    let index_column: usize = find_column(INDEX_COLUMN)
+        .ok_or(format!("The CSV file has no [{}] column.", INDEX_COLUMN))?;
+    let id_column: usize =
+        find_column(ID_COLUMN).ok_or(format!("The CSV file has no [{}] column.", ID_COLUMN))?;
+    let level_column: Option<usize> = find_column(LEVEL_COLUMN);
+    let seed_column: Option<usize> = find_column(SEED_COLUMN);
+    let amount_column: Option<usize> = find_column(AMOUNT_COLUMN);
+    let durability_column: Option<usize> = find_column(DURABILITY_COLUMN);
+
+    if level_column.is_none()
+        && seed_column.is_none()
+        && amount_column.is_none()
+        && durability_column.is_none()
+    {
+        return Err("The CSV file does not contain any editable column.".into());
+    }
+
+    // Maps the offset of every inventory item to the item.
+    let items: HashMap<usize, &InventoryItem> = save_file
+        .items
+        .iter()
+        .flat_map(|row| row.inventory_items.iter())
+        .map(|item| (item.index, item))
+        .collect();
+
+    let mut preview: InventoryEditPreview = InventoryEditPreview::new(csv_path.to_string());
+    let mut content: Vec<u8> = save_file.file_content.clone();
+    let mut seen: HashSet<usize> = HashSet::new();
+
+    for (row, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| format!("Error reading the CSV file: {}", e))?;
+        // The line inside the file (after the header line).
+        let line: u64 = row as u64 + 2;
+
+        if record.iter().all(|cell| cell.is_empty()) {
+            continue;
+        }
+
+        let id_name: &str = record.get(id_column).unwrap_or_default();
+        let index: &str = record.get(index_column).unwrap_or_default();
+        let item: &InventoryItem = match index
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| items.get(&index))
+        {
+            Some(item) => item,
+            None => {
+                preview.issues.push(InventoryEditIssue::new(
+                    line,
+                    format!(
+                        "There is no inventory item [{}] at offset [{}].",
+                        id_name, index
+                    ),
+                ));
+                continue;
+            }
+        };
+
+        if item.name != id_name {
+            preview.issues.push(InventoryEditIssue::new(
+                line,
+                format!(
+                    "The ID [{}] does not match the item [{}] at offset [{}].",
+                    id_name, item.name, item.index
+                ),
+            ));
+            continue;
+        }
+
+        if !seen.insert(item.index) {
+            preview.issues.push(InventoryEditIssue::new(
+                line,
+                format!("The item at offset [{}] is listed twice.", item.index),
+            ));
+            continue;
+        }
+
+        preview.matched_rows += 1;
+
+        let chunk = &item.chunk_data;
+        let current_durability: f32 =
+            f32::from_le_bytes(chunk.durability.clone().try_into().unwrap_or_default());
+
+        let level = read_field(&record, level_column, LEVEL_COLUMN, chunk.level_value);
+        let seed = read_field(&record, seed_column, SEED_COLUMN, chunk.seed_value);
+        let amount = read_field(&record, amount_column, AMOUNT_COLUMN, chunk.amount_value);
+        let durability = read_field(
+            &record,
+            durability_column,
+            DURABILITY_COLUMN,
+            current_durability,
+        )
+        .and_then(|value| match value.is_finite() {
+            true => Ok(value),
+            false => Err(format!("The durability [{}] is not a valid number.", value)),
+        });
+
+        let (new_level, new_seed, new_amount, mut new_durability) =
+            match (level, seed, amount, durability) {
+                (Ok(level), Ok(seed), Ok(amount), Ok(durability)) => {
+                    (level, seed, amount, durability)
+                }
+                (level, seed, amount, durability) => {
+                    let messages: Vec<String> =
+                        [level.err(), seed.err(), amount.err(), durability.err()]
+                            .into_iter()
+                            .flatten()
+                            .collect();
+                    preview
+                        .issues
+                        .push(InventoryEditIssue::new(line, messages.join(" ")));
+                    continue;
+                }
+            };
+
+        // The export rounds the durability, so the rounded value counts as unchanged.
+        if format!("{:.1}", new_durability) == chunk.durability_value {
+            new_durability = current_durability;
+        }
+
+        let changes: Vec<(&str, String, String)> = vec![
+            (
+                LEVEL_COLUMN,
+                chunk.level_value.to_string(),
+                new_level.to_string(),
+            ),
+            (
+                SEED_COLUMN,
+                chunk.seed_value.to_string(),
+                new_seed.to_string(),
+            ),
+            (
+                AMOUNT_COLUMN,
+                chunk.amount_value.to_string(),
+                new_amount.to_string(),
+            ),
+            (
+                DURABILITY_COLUMN,
+                chunk.durability_value.clone(),
+                format!("{:.1}", new_durability),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, old_value, new_value)| old_value != new_value)
+        .collect();
+
+        if changes.is_empty() {
+            continue;
+        }
+
+        for (field, old_value, new_value) in changes {
+            preview.changes.push(InventoryEditChange::new(
+                line,
+                item.index,
+                item.name.clone(),
+                field.to_string(),
+                old_value,
+                new_value,
+            ));
+        }
+
+        content = edit_inventory_item_chunk(
+            item.index,
+            item.name.clone(),
+            chunk.index,
+            item.size,
+            new_level,
+            new_seed,
+            new_amount,
+            new_durability,
+            content,
+        );
+    }
+
+    preview.byte_changes = find_byte_changes(&save_file.file_content, &content);
+
+    Ok((preview, content))
+}
+
+/// Represents a method for reading and validating a single field of a CSV row.
+///
+/// ### Parameter
+/// - `record`: The CSV row.
+/// - `column`: The column of the field (`None` if the file does not contain it).
+/// - `name`: The name of the field.
+/// - `current`: The current value of the field.
+///
+/// ### Returns `T`
+/// The new value, the current value if the cell is empty or missing.
+fn read_field<T: FromStr>(
+    record: &csv::StringRecord,
+    column: Option<usize>,
+    name: &str,
+    current: T,
+) -> std::result::Result<T, String> {
+    match column.and_then(|c| record.get(c)) {
+        None | Some("") => Ok(current),
+        Some(value) => value
+            .parse::<T>()
+            .map_err(|_| format!("The {} [{}] is not a number or out of range.", name, value)),
+    }
+}
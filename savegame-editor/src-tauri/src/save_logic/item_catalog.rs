@@ -0,0 +1,242 @@
+use crate::save_logic::file_analyser::clean_sgd_name;
+use crate::save_logic::id_fetcher::fetch_ids;
+use crate::save_logic::patched_items_fetcher::fetch_patched_ids;
+use crate::save_logic::struct_data::{CatalogItem, IdData, ItemCatalog, PatchedItems};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the category of items that are not part of the ID files.
+pub static UNKNOWN_CATEGORY: &str = "Unknown";
+
+// Defines the header of the display name files (for example "mchawk.csv").
+static DISPLAY_NAME_HEADER: &str = "name_id";
+
+/// Represents a method for fetching the item catalog from the ID files and the patched item files.
+///
+/// ### Parameter
+/// - `id_path`: The directory where the ID files (and imported display names) are stored.
+/// - `patched_path`: The directory where the patched item files (display names, not dropable, not shareable) are stored.
+///
+/// ### Returns `ItemCatalog`
+/// The catalog with a record for every known ID.
+pub fn fetch_item_catalog(id_path: &String, patched_path: &String) -> Result<ItemCatalog> {
+    let ids: Vec<IdData> = fetch_ids(id_path)?;
+
+    // The patched items are optional, without them every item is dropable and shareable.
+    let patched_items: PatchedItems = fetch_patched_ids(patched_path).unwrap_or_else(|err| {
+        println!("[WARNING]: The patched items could not be read: {}", err);
+        PatchedItems::new(Vec::new(), Vec::new())
+    });
+    let mut display_names: HashMap<String, String> =
+        fetch_display_names(patched_path).unwrap_or_default();
+
+    // Display names that were imported into the ID database.
+    for (id_name, display_name) in fetch_display_names(id_path)? {
+        display_names.entry(id_name).or_insert(display_name);
+    }
+
+    Ok(build_item_catalog(&ids, &patched_items, &display_names))
+}
+
+/// Represents a method for merging all item sources into a single catalog.
+/// Every ID that appears in any source gets a record. The keys of the catalog are lowercase,
+/// since the sources do not agree on the casing of the IDs.
+///
+/// ### Parameter
+/// - `ids`: The list of all IDs (the file name is the category).
+/// - `patched_items`: The IDs that are not dropable or not shareable.
+/// - `display_names`: The display name for each lowercase ID.
+///
+/// ### Returns `ItemCatalog`
+/// The catalog with a record for every known ID.
+pub fn build_item_catalog(
+    ids: &[IdData],
+    patched_items: &PatchedItems,
+    display_names: &HashMap<String, String>,
+) -> ItemCatalog {
+    let not_dropable: HashSet<String> = to_lowercase_set(&patched_items.not_dropable);
+    let not_shareable: HashSet<String> = to_lowercase_set(&patched_items.not_shareable);
+
+    // Collect every ID with its category. The first category wins if an ID is listed twice.
+    let mut categories: Vec<(String, String)> = Vec::new();
+    let mut known: HashSet<String> = HashSet::new();
+
+    let mut sorted_ids: Vec<&IdData> = ids.iter().collect();
+    sorted_ids.sort_by(|a, b| a.filename.cmp(&b.filename));
+
+    for id_section in sorted_ids {
+        for id_name in id_section.ids.iter() {
+            let id_name: &str = id_name.trim();
+
+            if !id_name.is_empty() && known.insert(id_name.to_lowercase()) {
+                categories.push((id_name.to_string(), id_section.filename.clone()));
+            }
+        }
+    }
+
+    // IDs that are only listed in the patched item files are added as unknown category.
+    let patched_ids = patched_items
+        .not_dropable
+        .iter()
+        .chain(patched_items.not_shareable.iter());
+
+    for id_name in patched_ids {
+        let id_name: &str = id_name.trim();
+
+        if !id_name.is_empty() && known.insert(id_name.to_lowercase()) {
+            categories.push((id_name.to_string(), UNKNOWN_CATEGORY.to_string()));
+        }
+    }
+
+    let mut items: HashMap<String, CatalogItem> = HashMap::new();
+
+    for (id_name, category) in categories {
+        let key: String = id_name.to_lowercase();
+        let display_name: String = display_names
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| id_name.clone());
+
+        items.insert(
+            key.clone(),
+            CatalogItem::new(
+                id_name.clone(),
+                display_name,
+                category,
+                !not_dropable.contains(&key),
+                !not_shareable.contains(&key),
+                is_dlc_item(&id_name),
+            ),
+        );
+    }
+
+    // Display names of IDs that are not part of any other file.
+    for (key, display_name) in display_names.iter() {
+        if !items.contains_key(key) {
+            items.insert(
+                key.clone(),
+                CatalogItem::new(
+                    key.clone(),
+                    display_name.clone(),
+                    UNKNOWN_CATEGORY.to_string(),
+                    true,
+                    true,
+                    is_dlc_item(key),
+                ),
+            );
+        }
+    }
+
+    ItemCatalog::new(items)
+}
+
+/// Represents a method for finding the catalog record of an item.
+/// Only exact matches count (ignoring the casing, control characters and the SGD suffix), since a partial match
+/// would give the item the flags of an unrelated ID.
+///
+/// ### Parameter
+/// - `catalog`: The item catalog.
+/// - `item_name`: The name of the item.
+///
+/// ### Returns `CatalogItem`
+/// The record of the item or an `Unknown` record if the item is not part of the catalog.
+pub fn find_catalog_item(catalog: &ItemCatalog, item_name: &str) -> CatalogItem {
+    let key: String = item_name.trim().to_lowercase();
+
+    if let Some(item) = catalog.items.get(&key) {
+        return item.clone();
+    }
+
+    match catalog.items.get(&clean_sgd_name(item_name).to_lowercase()) {
+        Some(item) => item.clone(),
+        None => CatalogItem::new(
+            item_name.to_string(),
+            item_name.to_string(),
+            UNKNOWN_CATEGORY.to_string(),
+            true,
+            true,
+            is_dlc_item(item_name),
+        ),
+    }
+}
+
+/// Represents a method for reading the display names of all CSV files (`name_id,ui_name`) in a directory.
+///
+/// ### Parameter
+/// - `directory_path`: The directory where the display name files are stored.
+///
+/// ### Returns `HashMap<String, String>`
+/// The display name for each lowercase ID.
+fn fetch_display_names(directory_path: &String) -> Result<HashMap<String, String>> {
+    let mut display_names: HashMap<String, String> = HashMap::new();
+    let entries = fs::read_dir(directory_path)
+        .map_err(|e| format!("Error reading the display names directory: {}", e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Error reading directory entry: {}", e))?;
+        let path = entry.path();
+
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("csv") {
+            continue;
+        }
+
+        read_display_name_file(&path, &mut display_names)?;
+    }
+
+    Ok(display_names)
+}
+
+/// Represents a method for reading a single display name file.
+///
+/// ### Parameter
+/// - `file_path`: The path of the CSV file.
+/// - `display_names`: The collected display names (existing entries are kept).
+fn read_display_name_file(
+    file_path: &Path,
+    display_names: &mut HashMap<String, String>,
+) -> Result<()> {
+    let file_content = fs::read_to_string(file_path)?;
+
+    for line in file_content.lines() {
+        let mut columns = line.splitn(2, ',');
+        let id_name: &str = columns.next().unwrap_or_default().trim();
+        let display_name: &str = columns.next().unwrap_or_default().trim().trim_matches('"');
+
+        if id_name.is_empty() || display_name.is_empty() || id_name == DISPLAY_NAME_HEADER {
+            continue;
+        }
+
+        display_names
+            .entry(id_name.to_lowercase())
+            .or_insert(display_name.to_string());
+    }
+
+    Ok(())
+}
+
+/// Represents a method for checking whether an ID belongs to a DLC.
+/// The game marks DLC items with "DLC" inside the ID (for example "DLC_Opera_Mine_Explosive").
+///
+/// ### Parameter
+/// - `id_name`: The ID of the item.
+///
+/// ### Returns `bool`
+/// Indicates whether the item belongs to a DLC.
+fn is_dlc_item(id_name: &str) -> bool {
+    id_name.to_lowercase().contains("dlc")
+}
+
+/// Represents a method for converting a list of IDs to a lowercase set.
+///
+/// ### Parameter
+/// - `ids`: The list of IDs.
+///
+/// ### Returns `HashSet<String>`
+/// The lowercase IDs.
+fn to_lowercase_set(ids: &[String]) -> HashSet<String> {
+    ids.iter().map(|id| id.trim().to_lowercase()).collect()
+}
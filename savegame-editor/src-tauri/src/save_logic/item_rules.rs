@@ -0,0 +1,193 @@
+//! Evaluates the versioned classification rules for SGD matches.
+//!
+//! The file analyser decides whether a match is an item, a mod or a transmog. Instead of hard-coding
+//! substrings, the decisions are based on named rules from `Mappings/item_rules.json`.
+//! Each rule contains explicit IDs and case-insensitive regex patterns (with optional exclusions).
+
+use crate::save_logic::file_analyser::clean_sgd_name;
+use crate::save_logic::struct_data::{ItemRule, ItemRuleSet};
+use regex::{Regex, RegexBuilder};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the file name of the classification rules.
+static ITEM_RULES_FILE: &str = "item_rules.json";
+
+// Defines the highest rule file version this editor understands.
+pub const SUPPORTED_RULE_VERSION: u32 = 1;
+
+// Defines the names of the rules the file analyser relies on.
+pub static SGD_ENTRY_RULE: &str = "sgd_entry";
+pub static MOD_RULE: &str = "mod";
+pub static AMMO_MOD_RULE: &str = "ammo_mod";
+pub static RANGED_WEAPON_RULE: &str = "ranged_weapon";
+pub static OUTFIT_RULE: &str = "outfit";
+
+/// Represents a single rule with its compiled patterns.
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    ids: HashSet<String>,
+    patterns: Vec<(String, Regex, Vec<Regex>)>,
+}
+
+/// Represents the engine that evaluates the classification rules.
+#[derive(Debug, Clone)]
+pub struct ItemRuleEngine {
+    pub version: u32,
+    rules: HashMap<String, CompiledRule>,
+}
+
+impl ItemRuleEngine {
+    /// Represents a method for compiling a rule set into an engine.
+    ///
+    /// ### Parameter
+    /// - `rule_set`: The rule set read from the rule file.
+    ///
+    /// ### Returns `Result<ItemRuleEngine>`
+    /// The engine or an error if the rule set is invalid.
+    pub fn new(rule_set: ItemRuleSet) -> Result<Self> {
+        if rule_set.version > SUPPORTED_RULE_VERSION {
+            return Err(format!(
+                "The item rules have version [{}], but only version [{}] is supported.",
+                rule_set.version, SUPPORTED_RULE_VERSION
+            )
+            .into());
+        }
+
+        let mut rules: HashMap<String, CompiledRule> = HashMap::new();
+
+        for rule in rule_set.rules {
+            let name: String = rule.name.clone();
+            rules.insert(name, compile_rule(rule)?);
+        }
+
+        // Check if all rules the file analyser relies on are defined.
+        for required in [
+            SGD_ENTRY_RULE,
+            MOD_RULE,
+            AMMO_MOD_RULE,
+            RANGED_WEAPON_RULE,
+            OUTFIT_RULE,
+        ] {
+            if !rules.contains_key(required) {
+                return Err(
+                    format!("The item rules do not define the rule [{}].", required).into(),
+                );
+            }
+        }
+
+        Ok(ItemRuleEngine {
+            version: rule_set.version,
+            rules,
+        })
+    }
+
+    /// Represents a method for evaluating a rule against a match.
+    ///
+    /// ### Parameter
+    /// - `rule_name`: The name of the rule.
+    /// - `value`: The SGD match (with or without the `SGDs` suffix).
+    ///
+    /// ### Returns `Option<String>`
+    /// A description of the ID or pattern that matched, `None` if the rule does not apply.
+    pub fn evaluate(&self, rule_name: &str, value: &str) -> Option<String> {
+        let rule: &CompiledRule = self.rules.get(rule_name)?;
+        let normalized: String = clean_sgd_name(value);
+
+        if rule.ids.contains(&normalized) {
+            return Some(format!("{} (id: {})", rule_name, normalized));
+        }
+
+        for (source, pattern, excludes) in rule.patterns.iter() {
+            if pattern.is_match(&normalized)
+                && !excludes.iter().any(|exclude| exclude.is_match(&normalized))
+            {
+                return Some(format!("{} (pattern: {})", rule_name, source));
+            }
+        }
+
+        None
+    }
+
+    /// Represents a method for checking whether a rule applies to a match.
+    ///
+    /// ### Parameter
+    /// - `rule_name`: The name of the rule.
+    /// - `value`: The SGD match (with or without the `SGDs` suffix).
+    ///
+    /// ### Returns `bool`
+    /// Indicates whether the rule applies.
+    pub fn matches(&self, rule_name: &str, value: &str) -> bool {
+        self.evaluate(rule_name, value).is_some()
+    }
+}
+
+/// Represents a method for fetching and compiling the classification rules.
+///
+/// ### Parameter
+/// - `rules_path`: The directory where the rule file is stored.
+///
+/// ### Returns `Result<ItemRuleEngine>`
+/// The engine with all compiled rules.
+pub fn fetch_item_rules(rules_path: &String) -> Result<ItemRuleEngine> {
+    let file_path = Path::new(rules_path).join(ITEM_RULES_FILE);
+    let file_content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Error reading the item rules: {}", e))?;
+
+    let rule_set: ItemRuleSet = serde_json::from_str(&file_content)
+        .map_err(|e| format!("Error parsing the item rules: {}", e))?;
+
+    ItemRuleEngine::new(rule_set)
+}
+
+/// Represents a method for compiling the patterns of a single rule.
+///
+/// ### Parameter
+/// - `rule`: The rule read from the rule file.
+///
+/// ### Returns `Result<CompiledRule>`
+/// The rule with compiled patterns.
+fn compile_rule(rule: ItemRule) -> Result<CompiledRule> {
+    let mut patterns: Vec<(String, Regex, Vec<Regex>)> = Vec::new();
+
+    for rule_pattern in rule.patterns {
+        let pattern: Regex = compile_pattern(&rule.name, &rule_pattern.pattern)?;
+        let mut excludes: Vec<Regex> = Vec::new();
+
+        for exclude in rule_pattern.exclude.iter() {
+            excludes.push(compile_pattern(&rule.name, exclude)?);
+        }
+
+        patterns.push((rule_pattern.pattern, pattern, excludes));
+    }
+
+    Ok(CompiledRule {
+        ids: rule.ids.iter().map(|id| clean_sgd_name(id)).collect(),
+        patterns,
+    })
+}
+
+/// Represents a method for compiling a case-insensitive pattern.
+///
+/// ### Parameter
+/// - `rule_name`: The name of the rule (for the error message).
+/// - `pattern`: The regex pattern.
+///
+/// ### Returns `Result<Regex>`
+/// The compiled pattern.
+fn compile_pattern(rule_name: &str, pattern: &str) -> Result<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| {
+            format!(
+                "Invalid pattern [{}] in rule [{}]: {}",
+                pattern, rule_name, e
+            )
+            .into()
+        })
+}
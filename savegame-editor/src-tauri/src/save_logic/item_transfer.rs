@@ -0,0 +1,595 @@
+//! Transfers inventory items (ID, chunk values and attached mods) from one save to another.
+//!
+//! An inventory item is a group of records that refer to each other by their handles (see `save_records`):
+//! - the instance record, which contains the chunk values and is listed by the handle array of its container,
+//! - the records owned by the instance: the ID, the mod slots, and for weapons the slot records and their mods.
+//!
+//! The instances of a container are stored in a row, followed by the records they own in the reversed order (the
+//! records of the last instance come first). A tab can consist of several containers, so the item is inserted into
+//! the container of a target item with the same layout: behind its last instance, right in front of the records of
+//! that instance, and its instance is appended to the handle array. Every record gets a new handle and the references
+//! between the records are rewritten. The target save is analysed again afterwards and the transfer is rejected if the
+//! item cannot be found in the target tab.
+//!
+//! The item is only transferred if its records can be copied completely: the records owned by the instance have to
+//! be stored next to each other and must not be used by any other record. The source save is not changed.
+
+use crate::logger::ConsoleLogger;
+use crate::save_logic::file_analyser::{clean_sgd_name, load_save_file};
+use crate::save_logic::item_rules::ItemRuleEngine;
+use crate::save_logic::save_records::{
+    find_handle_array, find_records, insert_records, read_u64, RECORD_CONTENT_OFFSET,
+    RECORD_HANDLE_OFFSET, RECORD_TYPE_OFFSET,
+};
+use crate::save_logic::struct_data::{
+    InventoryItem, InventoryItemRow, InventoryTabMapping, ItemCatalog, ItemTransferReport,
+    NewRecord, SaveFile,
+};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the size of a handle inside the handle array.
+const HANDLE_SIZE: usize = 8;
+
+/// Represents the records of a save and the positions every handle is used at.
+struct SaveRecords {
+    records: Vec<(usize, usize)>,
+    handles: HashMap<u64, usize>,
+    references: HashMap<u64, Vec<usize>>,
+}
+
+/// Represents the records of an inventory item: the instance, the records it owns (in the order they are stored) and the
+/// references between them (record, offset inside the record, referenced record; the instance is record 0).
+struct ItemRecords {
+    instance: (usize, usize),
+    owned: Vec<(usize, usize)>,
+    references: Vec<(usize, usize, usize)>,
+}
+
+/// Represents a method for inserting an inventory item of the source save into a tab of the target save.
+///
+/// ### Parameter
+/// - `source_save`: The parsed save that contains the item.
+/// - `target_save`: The parsed save that receives the item.
+/// - `source_index`: The offset of the transferred item inside the source save.
+/// - `target_tab`: The inventory tab of the target save.
+///
+/// ### Returns `(ItemTransferReport, Vec<u8>)`
+/// The transferred data and the new content of the target save.
+pub fn transfer_inventory_item(
+    source_save: &SaveFile,
+    target_save: &SaveFile,
+    source_index: usize,
+    target_tab: &str,
+) -> Result<(ItemTransferReport, Vec<u8>)> {
+    let source_item: &InventoryItem = source_save
+        .items
+        .iter()
+        .flat_map(|row| row.inventory_items.iter())
+        .find(|item| item.index == source_index)
+        .ok_or(format!(
+            "There is no inventory item at offset [{}] in the source save.",
+            source_index
+        ))?;
+
+    let target_row: &InventoryItemRow = target_save
+        .items
+        .iter()
+        .find(|row| row.name.eq_ignore_ascii_case(target_tab))
+        .ok_or(format!(
+            "The target save has no inventory tab [{}].",
+            target_tab
+        ))?;
+
+    // Collects the records of the item inside the source save.
+    let source_content: &[u8] = &source_save.file_content;
+    let source_records: SaveRecords = index_records(source_content)?;
+    let item_records: ItemRecords =
+        find_item_records(source_content, &source_records, source_item)?;
+
+    // Finds an item of the target tab with the same layout, its container receives the new item.
+    let item_layout = |content: &[u8], instance: (usize, usize)| {
+        (
+            instance.1 - instance.0,
+            read_u64(content, instance.0 + RECORD_TYPE_OFFSET),
+        )
+    };
+    let source_layout: (usize, u64) = item_layout(source_content, item_records.instance);
+
+    let target_content: &[u8] = &target_save.file_content;
+    let target_records: SaveRecords = index_records(target_content)?;
+    let target_instance: (usize, usize) = target_row
+        .inventory_items
+        .iter()
+        .filter_map(|item| find_item_records(target_content, &target_records, item).ok())
+        .map(|records| records.instance)
+        .find(|instance| item_layout(target_content, *instance) == source_layout)
+        .ok_or(format!(
+            "The tab [{}] of the target save does not contain any item with the layout of the item [{}].",
+            target_row.name, source_item.name
+        ))?;
+
+    let last_item: ItemRecords = find_last_item(
+        target_content,
+        &target_records,
+        target_instance,
+        &target_row.name,
+    )?;
+
+    // Copies the records, the instance is the only record that is listed by the container.
+    let records: Vec<NewRecord> = std::iter::once(item_records.instance)
+        .chain(item_records.owned.iter().copied())
+        .enumerate()
+        .map(|(position, (start, end))| {
+            let references: Vec<(usize, usize)> = item_records
+                .references
+                .iter()
+                .filter(|(record, _, _)| *record == position)
+                .map(|(_, offset, referenced_record)| (*offset, *referenced_record))
+                .collect();
+
+            NewRecord::new(
+                source_content[start..end].to_vec(),
+                position == 0,
+                references,
+            )
+        })
+        .collect();
+
+    let insert_index: usize = last_item.instance.1;
+    let (content, _) = insert_records(
+        target_content.to_vec(),
+        target_instance.0,
+        insert_index,
+        records,
+        "inventory items",
+    )?;
+
+    // The handle array is stored in front of the records, therefore the new records are moved by the new handle.
+    let block_start: usize = item_records.owned[0].0;
+    let instance_size: usize = item_records.instance.1 - item_records.instance.0;
+    let target_index: usize =
+        insert_index + HANDLE_SIZE + instance_size + (source_item.index - block_start);
+
+    let report: ItemTransferReport = ItemTransferReport::new(
+        source_save.path.clone(),
+        target_save.path.clone(),
+        source_item.name.clone(),
+        source_item.index,
+        target_row.name.clone(),
+        target_index,
+        source_item
+            .mod_data
+            .iter()
+            .map(|mod_item| clean_sgd_name(&mod_item.name))
+            .collect(),
+        1 + item_records.owned.len(),
+        content.len() - target_content.len(),
+    );
+
+    Ok((report, content))
+}
+
+/// Represents a method for transferring an inventory item between two saves and analysing the target save again,
+/// so every item of the target save has its current offsets.
+///
+/// ### Parameter
+/// - `source_save`: The parsed save that contains the item.
+/// - `target_save`: The parsed save that receives the item.
+/// - `source_index`: The offset of the transferred item inside the source save.
+/// - `target_tab`: The inventory tab of the target save.
+/// - `catalog`: The item catalog with a record for every known ID.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
+/// - `logger`: The console logger that logs every event.
+///
+/// ### Returns `(ItemTransferReport, SaveFile)`
+/// The transferred data and the re-indexed target save.
+pub fn transfer_item_between_saves(
+    source_save: &SaveFile,
+    target_save: &SaveFile,
+    source_index: usize,
+    target_tab: &str,
+    catalog: ItemCatalog,
+    tab_mapping: InventoryTabMapping,
+    item_rules: ItemRuleEngine,
+    logger: &mut ConsoleLogger,
+) -> Result<(ItemTransferReport, SaveFile)> {
+    let (report, content) =
+        transfer_inventory_item(source_save, target_save, source_index, target_tab)?;
+
+    let mut new_target_save: SaveFile = load_save_file(
+        &target_save.path,
+        content,
+        catalog,
+        tab_mapping,
+        item_rules,
+        logger,
+        false,
+        target_save.is_compressed,
+    )
+    .map_err(|e| {
+        format!(
+            "The target save could not be analysed after the transfer: {}",
+            e
+        )
+    })?;
+
+    // The target save on disk is not changed yet, so the hash of the loaded file stays valid.
+    new_target_save.file_hash = target_save.file_hash.clone();
+    new_target_save.gzip_profile = target_save.gzip_profile.clone();
+
+    // The transferred item has to be found in the target tab with all of its mods, otherwise the transfer is rejected.
+    let count_items = |save_file: &SaveFile| {
+        save_file
+            .items
+            .iter()
+            .filter(|row| row.name == report.target_tab)
+            .map(|row| row.inventory_items.len())
+            .sum::<usize>()
+    };
+    let is_transferred: bool = new_target_save
+        .items
+        .iter()
+        .filter(|row| row.name == report.target_tab)
+        .flat_map(|row| row.inventory_items.iter())
+        .any(|item| {
+            item.index == report.target_index
+                && item.name == report.id
+                && item
+                    .mod_data
+                    .iter()
+                    .map(|mod_item| clean_sgd_name(&mod_item.name))
+                    .eq(report.mods.iter().cloned())
+        });
+
+    if !is_transferred || count_items(&new_target_save) != count_items(target_save) + 1 {
+        return Err(format!(
+            "The item [{}] could not be found in the tab [{}] after the transfer.",
+            report.id, report.target_tab
+        )
+        .into());
+    }
+
+    Ok((report, new_target_save))
+}
+
+/// Represents a method for collecting the records of a save and the positions their handles are used at.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+///
+/// ### Returns `SaveRecords`
+/// The records, the record of every handle and the positions of every handle outside of its own record header.
+fn index_records(content: &[u8]) -> Result<SaveRecords> {
+    let records: Vec<(usize, usize)> = find_records(content);
+    let handles: HashMap<u64, usize> = records
+        .iter()
+        .enumerate()
+        .map(|(position, (start, _))| (read_u64(content, start + RECORD_HANDLE_OFFSET), position))
+        .collect();
+
+    if handles.len() != records.len() {
+        return Err("The handles of the save are not unique.".into());
+    }
+
+    let mut references: HashMap<u64, Vec<usize>> = HashMap::new();
+
+    for index in 0..content.len().saturating_sub(HANDLE_SIZE - 1) {
+        let handle: u64 = read_u64(content, index);
+
+        if let Some(position) = handles.get(&handle) {
+            if records[*position].0 + RECORD_HANDLE_OFFSET != index {
+                references.entry(handle).or_default().push(index);
+            }
+        }
+    }
+
+    Ok(SaveRecords {
+        records,
+        handles,
+        references,
+    })
+}
+
+/// Represents a method for finding the instance of an inventory item and collecting the records it owns.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+/// - `save_records`: The records of the save.
+/// - `item`: The parsed item.
+///
+/// ### Returns `ItemRecords`
+/// The instance of the item, the records it owns and the references between them.
+fn find_item_records(
+    content: &[u8],
+    save_records: &SaveRecords,
+    item: &InventoryItem,
+) -> Result<ItemRecords> {
+    let invalid = |reason: &str| format!("The item [{}] {}.", item.name, reason);
+    let records: &[(usize, usize)] = &save_records.records;
+
+    // The ID of the item is stored inside its own record.
+    let id_record: usize = records
+        .partition_point(|(start, _)| *start <= item.index)
+        .checked_sub(1)
+        .filter(|position| {
+            item.index + item.name.len() <= records[*position].1
+                && content.get(item.index..item.index + item.name.len())
+                    == Some(item.name.as_bytes())
+        })
+        .ok_or_else(|| invalid("has no ID record"))?;
+
+    // The instance is the only record that refers to the ID.
+    let record_of = |index: usize| {
+        records
+            .partition_point(|(start, _)| *start <= index)
+            .checked_sub(1)
+            .filter(|position| index + HANDLE_SIZE <= records[*position].1)
+    };
+    let id_handle: u64 = read_u64(content, records[id_record].0 + RECORD_HANDLE_OFFSET);
+    let instance: usize = match save_records.references.get(&id_handle).map(Vec::as_slice) {
+        Some([reference]) => record_of(*reference).ok_or_else(|| invalid("has no instance"))?,
+        _ => return Err(invalid("is not referenced by a single instance").into()),
+    };
+
+    collect_item_records(content, save_records, instance, &item.name)
+}
+
+/// Represents a method for collecting the records that are owned by an instance and validating that they can be
+/// copied.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+/// - `save_records`: The records of the save.
+/// - `instance`: The position of the instance inside the records.
+/// - `name`: The name of the item for the error messages.
+///
+/// ### Returns `ItemRecords`
+/// The instance of the item, the records it owns and the references between them.
+fn collect_item_records(
+    content: &[u8],
+    save_records: &SaveRecords,
+    instance: usize,
+    name: &str,
+) -> Result<ItemRecords> {
+    let invalid = |reason: &str| format!("The item [{}] {}.", name, reason);
+    let records: &[(usize, usize)] = &save_records.records;
+
+    // Collects every record that is reachable from the instance.
+    let mut item_positions: Vec<usize> = vec![instance];
+    let mut references: Vec<(usize, usize, usize)> = Vec::new();
+    let mut next: usize = 0;
+
+    while next < item_positions.len() {
+        let (start, end) = records[item_positions[next]];
+
+        for index in start + RECORD_CONTENT_OFFSET..(end + 1).saturating_sub(HANDLE_SIZE) {
+            let referenced: usize = match save_records.handles.get(&read_u64(content, index)) {
+                Some(referenced) if *referenced != item_positions[next] => *referenced,
+                _ => continue,
+            };
+
+            let referenced_position: usize = match item_positions
+                .iter()
+                .position(|position| *position == referenced)
+            {
+                Some(position) => position,
+                None => {
+                    item_positions.push(referenced);
+                    item_positions.len() - 1
+                }
+            };
+            references.push((next, index - start, referenced_position));
+        }
+
+        next += 1;
+    }
+
+    // The handles of the records are only used by the records of the item and the container of the instance.
+    let item_references: HashSet<usize> = references
+        .iter()
+        .map(|(record, offset, _)| records[item_positions[*record]].0 + offset)
+        .collect();
+
+    for (position, record) in item_positions.iter().enumerate() {
+        let handle: u64 = read_u64(content, records[*record].0 + RECORD_HANDLE_OFFSET);
+        let outer_references: usize = save_records.references.get(&handle).map_or(0, |indices| {
+            indices
+                .iter()
+                .filter(|index| !item_references.contains(index))
+                .count()
+        });
+
+        if outer_references != usize::from(position == 0) {
+            return Err(invalid("shares its records with other records of the save").into());
+        }
+    }
+
+    let instance_record: (usize, usize) = records[instance];
+    let handle_positions: HashSet<u64> = save_records.handles.keys().copied().collect();
+    find_handle_array(
+        content,
+        &handle_positions,
+        read_u64(content, instance_record.0 + RECORD_HANDLE_OFFSET),
+        instance_record.0,
+    )
+    .map_err(|_| invalid("is not listed by a container"))?;
+
+    // The owned records have to be stored next to each other (the references are renumbered in that order).
+    let mut owned_positions: Vec<usize> = (1..item_positions.len()).collect();
+    owned_positions.sort_by_key(|position| records[item_positions[*position]].0);
+
+    let owned: Vec<(usize, usize)> = owned_positions
+        .iter()
+        .map(|position| records[item_positions[*position]])
+        .collect();
+
+    if owned.is_empty() || owned.windows(2).any(|pair| pair[0].1 != pair[1].0) {
+        return Err(invalid("owns records that are not stored next to each other").into());
+    }
+
+    let mut order: Vec<usize> = vec![0; item_positions.len()];
+    for (new_position, position) in owned_positions.iter().enumerate() {
+        order[*position] = new_position + 1;
+    }
+
+    Ok(ItemRecords {
+        instance: instance_record,
+        owned,
+        references: references
+            .into_iter()
+            .map(|(record, offset, referenced)| (order[record], offset, order[referenced]))
+            .collect(),
+    })
+}
+
+/// Represents a method for finding the item whose instance is listed last by the container of an instance. A new
+/// item is inserted behind this instance and in front of the records it owns.
+///
+/// ### Parameter
+/// - `content`: The content of the target save.
+/// - `save_records`: The records of the target save.
+/// - `instance`: An instance that is listed by the container.
+/// - `tab`: The name of the target tab.
+///
+/// ### Returns `ItemRecords`
+/// The records of the last item of the container.
+fn find_last_item(
+    content: &[u8],
+    save_records: &SaveRecords,
+    instance: (usize, usize),
+    tab: &str,
+) -> Result<ItemRecords> {
+    let handles: HashSet<u64> = save_records.handles.keys().copied().collect();
+    let (_, array_end) = find_handle_array(
+        content,
+        &handles,
+        read_u64(content, instance.0 + RECORD_HANDLE_OFFSET),
+        instance.0,
+    )?;
+
+    let unknown_order = || format!("The items of the tab [{}] have an unknown order.", tab);
+    let last_instance: usize = save_records.handles[&read_u64(content, array_end - HANDLE_SIZE)];
+    let last_item: ItemRecords = collect_item_records(content, save_records, last_instance, tab)
+        .map_err(|_| unknown_order())?;
+
+    if last_item.owned[0].0 != last_item.instance.1 {
+        return Err(unknown_order().into());
+    }
+
+    Ok(last_item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save_logic::save_records::{find_section, read_u32, FILE_SIZE_OFFSET};
+    use crate::save_logic::test_support::{load_outpost_save, load_resources};
+
+    /// Finds the offset of the first item with the given ID.
+    fn find_item_index(save_file: &SaveFile, id: &str) -> usize {
+        save_file
+            .items
+            .iter()
+            .flat_map(|row| row.inventory_items.iter())
+            .find(|item| item.name == id)
+            .unwrap()
+            .index
+    }
+
+    /// Counts the items of an inventory tab.
+    fn count_tab_items(save_file: &SaveFile, tab: &str) -> usize {
+        save_file
+            .items
+            .iter()
+            .find(|row| row.name == tab)
+            .unwrap()
+            .inventory_items
+            .len()
+    }
+
+    /// Transfers an item between two copies of the endgame save and validates the sizes of the target save.
+    fn transfer_endgame_item(id: &str, tab: &str) -> (ItemTransferReport, SaveFile, SaveFile) {
+        let source: SaveFile = load_outpost_save("Endgame/endgame.sav");
+        let target: SaveFile = source.clone();
+
+        let (catalog, tab_mapping, item_rules) = load_resources();
+        let (report, transferred) = transfer_item_between_saves(
+            &source,
+            &target,
+            find_item_index(&source, id),
+            tab,
+            catalog,
+            tab_mapping,
+            item_rules,
+            &mut ConsoleLogger::new(),
+        )
+        .unwrap();
+
+        let content: &[u8] = &transferred.file_content;
+        assert_eq!(
+            content.len(),
+            target.file_content.len() + report.added_bytes
+        );
+        assert_eq!(
+            read_u32(content, FILE_SIZE_OFFSET) as usize + FILE_SIZE_OFFSET + 4,
+            content.len()
+        );
+        assert!(find_section(content, report.target_index).is_ok());
+        assert_eq!(
+            count_tab_items(&transferred, tab),
+            count_tab_items(&target, tab) + 1
+        );
+
+        (report, target, transferred)
+    }
+
+    #[test]
+    fn inserts_a_token_into_the_tickets_tab() {
+        let (report, target, transferred) =
+            transfer_endgame_item("Token_HuntersToken", "Tokens/Tickets");
+
+        assert_eq!(report.id, "Token_HuntersToken");
+        assert_eq!(report.added_records, 6);
+
+        // The other tabs keep their items.
+        for row in target
+            .items
+            .iter()
+            .filter(|row| row.name != "Tokens/Tickets")
+        {
+            assert_eq!(
+                count_tab_items(&transferred, &row.name),
+                row.inventory_items.len()
+            );
+        }
+    }
+
+    #[test]
+    fn inserts_a_weapon_with_its_slot_records() {
+        let (report, _, transferred) =
+            transfer_endgame_item("wpn_harran_spectacle_4", "Consumables");
+
+        // The ID, the slot records and both sets of mod records are copied with the instance.
+        assert_eq!(report.added_records, 18);
+        assert!(transferred
+            .items
+            .iter()
+            .flat_map(|row| row.inventory_items.iter())
+            .any(|item| item.index == report.target_index && item.name == report.id));
+    }
+
+    #[test]
+    fn rejects_items_without_the_layout_of_the_target_tab() {
+        let source: SaveFile = load_outpost_save("Endgame/endgame.sav");
+        let weapon_index: usize = find_item_index(&source, "wpn_harran_spectacle_4");
+
+        assert!(transfer_inventory_item(&source, &source, weapon_index, "Tokens/Tickets").is_err());
+        assert!(transfer_inventory_item(&source, &source, weapon_index, "Unknown").is_err());
+        assert!(transfer_inventory_item(&source, &source, 0, "Tokens/Tickets").is_err());
+    }
+}
@@ -0,0 +1,587 @@
+//! Scans a Dying Light 2 save for SGD entries and infers the inventory layout heuristically.
+//!
+//! When a new game patch moves the inventory chunks around, the file analyser usually stops
+//! finding any SGDs. This module does not rely on the hard-coded offsets. Instead it:
+//! - Collects every named SGD entry (`<name>SGDs` with a length prefix) and every chunk marker (a bare `SGDs`).
+//! - Groups chunk markers into runs with a constant stride and keeps the runs that are followed by known item IDs.
+//! - Looks for the size header behind the markers that explains the stride between two chunks.
+//! - Tests every possible field distance in front of the markers and keeps the one with the most plausible values.
+//!
+//! The result is a candidate layout profile and a confidence report that maintainers can review.
+
+use crate::logger::{ConsoleLogger, LoggerFunctions};
+use crate::save_logic::file_analyser::{
+    get_game_version, CHUNK_AMOUNT_SIZE, CHUNK_COUNTER_STATS_SIZE, CHUNK_DURABILITY_SIZE,
+    CHUNK_LEVEL_SIZE, CHUNK_SEED_SIZE, CHUNK_SPACE_SIZE,
+};
+use crate::save_logic::struct_data::{IdData, LayoutFieldCandidate, LayoutProfile, LayoutReport};
+use regex::bytes::Regex;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the largest gap between two chunk markers that still counts as the same run.
+const MAX_CHUNK_STRIDE: usize = 256;
+
+// Defines the range of distances in front of a chunk marker that are tested for fields.
+const MIN_FIELD_DISTANCE: usize = 4;
+const MAX_FIELD_DISTANCE: usize = 64;
+
+// Defines the plausible value ranges for the inferred fields.
+const MAX_PLAUSIBLE_LEVEL: u16 = 500;
+const MAX_PLAUSIBLE_AMOUNT: u32 = 10_000_000;
+const MAX_PLAUSIBLE_DURABILITY: f32 = 10_000_000.0;
+
+// Defines the minimum share of a stride before it is listed as a candidate.
+const MIN_STRIDE_SHARE: f32 = 0.1;
+
+// Defines the largest distance behind a chunk marker that is tested for the size header.
+const MAX_SIZE_POSITION: usize = 16;
+
+// Defines the size header the file analyser currently expects: "SGDs", a version (u32) and the payload size (u32).
+const CURRENT_SIZE_POSITION: usize = 8;
+const CURRENT_HEADER_SIZE: usize = 12;
+
+/// Represents a named SGD entry like `wpn_2hs_g_fpp_llSGDs`.
+struct NamedSgd {
+    name: String,
+    index: usize,
+}
+
+/// Represents a method for building the layout profile the file analyser currently uses.
+///
+/// ### Parameter
+/// - `game_version`: The game version of the current save.
+///
+/// ### Returns `LayoutProfile`
+/// The layout profile of the hard-coded chunk offsets.
+pub fn current_layout_profile(game_version: String) -> LayoutProfile {
+    let data_offset: usize = CHUNK_LEVEL_SIZE
+        + CHUNK_SEED_SIZE
+        + CHUNK_AMOUNT_SIZE
+        + CHUNK_DURABILITY_SIZE
+        + CHUNK_COUNTER_STATS_SIZE
+        + CHUNK_SPACE_SIZE;
+
+    LayoutProfile::new(
+        game_version,
+        data_offset,
+        0,
+        CHUNK_LEVEL_SIZE,
+        CHUNK_LEVEL_SIZE + CHUNK_SEED_SIZE,
+        CHUNK_LEVEL_SIZE + CHUNK_SEED_SIZE + CHUNK_AMOUNT_SIZE,
+        CHUNK_LEVEL_SIZE + CHUNK_SEED_SIZE + CHUNK_AMOUNT_SIZE + CHUNK_DURABILITY_SIZE,
+        Vec::new(),
+        CURRENT_SIZE_POSITION,
+        CURRENT_HEADER_SIZE,
+    )
+}
+
+/// Represents a method for scanning a save and inferring a candidate inventory layout.
+///
+/// ### Parameter
+/// - `file_path`: The filepath of the current selected save.
+/// - `file_content`: The decompressed content of the current file.
+/// - `ids`: The list of all IDs.
+/// - `logger`: The console logger that logs every event.
+/// - `is_debugging`: Indicates whether the file analyser is in debugging mode or not.
+///
+/// ### Returns `Result<LayoutReport>`
+/// The candidate layout profile and the confidence report.
+pub fn discover_layout(
+    file_path: &str,
+    file_content: &[u8],
+    ids: &[IdData],
+    logger: &mut ConsoleLogger,
+    is_debugging: bool,
+) -> Result<LayoutReport> {
+    let game_version: String = get_game_version(file_content);
+    let current: LayoutProfile = current_layout_profile(game_version.clone());
+    let mut notes: Vec<String> = Vec::new();
+
+    // Collect all SGD entries of the save.
+    let named_sgds: Vec<NamedSgd> = find_named_sgds(file_content);
+    let markers: Vec<usize> = find_chunk_markers(file_content);
+
+    if named_sgds.is_empty() && markers.is_empty() {
+        return Err("The save does not contain a single SGD entry.".into());
+    }
+
+    let known_ids: HashSet<&str> = ids
+        .iter()
+        .flat_map(|id_data| id_data.ids.iter().map(|id| id.trim()))
+        .collect();
+    let known_item_count: usize = named_sgds
+        .iter()
+        .filter(|sgd| known_ids.contains(sgd.name.as_str()))
+        .count();
+
+    if is_debugging {
+        logger.log_message(&format!(
+            "Found [{}] named SGDs ([{}] known items) and [{}] chunk markers.",
+            named_sgds.len(),
+            known_item_count,
+            markers.len()
+        ));
+    }
+
+    // Group the markers into runs and keep the ones that are followed by known items.
+    let runs: Vec<Vec<usize>> = group_marker_runs(&markers);
+    let known_runs: Vec<&Vec<usize>> = runs
+        .iter()
+        .filter(|run| is_known_run(run, &named_sgds, &known_ids))
+        .collect();
+
+    if runs.is_empty() {
+        notes.push("No run of chunk markers with a constant stride was found.".to_string());
+    } else if known_runs.is_empty() {
+        notes.push("No run of chunk markers is followed by known item IDs; offsets were inferred from all runs.".to_string());
+    }
+
+    let sample_runs: Vec<&Vec<usize>> = if known_runs.is_empty() {
+        runs.iter().collect()
+    } else {
+        known_runs.clone()
+    };
+    let samples: Vec<usize> = sample_runs
+        .iter()
+        .flat_map(|run| run.iter().copied())
+        .collect();
+
+    if is_debugging {
+        logger.log_message(&format!(
+            "Grouped the chunk markers into [{}] runs, [{}] of them are followed by known items.",
+            runs.len(),
+            known_runs.len()
+        ));
+    }
+
+    // Infer the chunk strides and the size header that explains them.
+    let (chunk_strides, stride_confidence) = infer_strides(&sample_runs);
+    let (size_position, header_size, header_confidence) =
+        infer_size_header(file_content, &sample_runs);
+
+    if is_debugging {
+        logger.log_message(&format!(
+            "The chunk size is expected [{}] bytes behind the marker with a header of [{}] bytes ([{:.2}] of all gaps).",
+            size_position, header_size, header_confidence
+        ));
+    }
+
+    if header_confidence < 0.5 {
+        notes.push(
+            "The gaps between chunks could not be explained by a size header behind the markers."
+                .to_string(),
+        );
+    }
+
+    // Infer the field distances in front of the chunk markers.
+    let mut taken: Vec<(usize, usize)> = Vec::new();
+    let amount = infer_field(
+        "amount",
+        file_content,
+        &samples,
+        CHUNK_AMOUNT_SIZE,
+        current.data_offset - current.amount_position,
+        &taken,
+        is_plausible_amount,
+    );
+    taken.push((amount.distance, CHUNK_AMOUNT_SIZE));
+    let durability = infer_field(
+        "durability",
+        file_content,
+        &samples,
+        CHUNK_DURABILITY_SIZE,
+        current.data_offset - current.durability_position,
+        &taken,
+        is_plausible_durability,
+    );
+    taken.push((durability.distance, CHUNK_DURABILITY_SIZE));
+    let level = infer_field(
+        "level",
+        file_content,
+        &samples,
+        CHUNK_LEVEL_SIZE,
+        current.data_offset - current.level_position,
+        &taken,
+        is_plausible_level,
+    );
+
+    for field in [&amount, &durability, &level] {
+        if is_debugging {
+            logger.log_message(&format!(
+                "Candidate for [{}] is [{}] bytes in front of the chunk marker ([{}/{}] plausible values).",
+                field.field, field.distance, field.hits, field.samples
+            ));
+        }
+
+        if field.samples > 0 && field.confidence < 0.5 {
+            notes.push(format!(
+                "The [{}] field could only be validated for [{}/{}] chunks.",
+                field.field, field.hits, field.samples
+            ));
+        }
+    }
+
+    // The chunk starts with the field that is the furthest away from the marker.
+    let data_offset: usize = level.distance.max(amount.distance).max(durability.distance);
+    let candidate: LayoutProfile = LayoutProfile::new(
+        game_version,
+        data_offset,
+        data_offset - level.distance,
+        data_offset - level.distance + CHUNK_LEVEL_SIZE,
+        data_offset - amount.distance,
+        data_offset - durability.distance,
+        data_offset - durability.distance + CHUNK_DURABILITY_SIZE,
+        chunk_strides,
+        size_position,
+        header_size,
+    );
+
+    let matches_current: bool = candidate.data_offset == current.data_offset
+        && candidate.level_position == current.level_position
+        && candidate.amount_position == current.amount_position
+        && candidate.durability_position == current.durability_position
+        && candidate.size_position == current.size_position
+        && candidate.header_size == current.header_size;
+
+    if !matches_current {
+        notes.push(format!(
+            "The candidate layout differs from the current one: data offset [{}] instead of [{}], amount at [{}] instead of [{}], durability at [{}] instead of [{}].",
+            candidate.data_offset,
+            current.data_offset,
+            candidate.amount_position,
+            current.amount_position,
+            candidate.durability_position,
+            current.durability_position
+        ));
+    }
+
+    // Combine all confidences into a single value.
+    let field_confidence: f32 =
+        (amount.confidence + durability.confidence + level.confidence) / 3.0;
+    let mut confidence: f32 = field_confidence * (stride_confidence + header_confidence) / 2.0;
+
+    if known_runs.is_empty() {
+        confidence /= 2.0;
+    }
+
+    if is_debugging {
+        logger.log_message(&format!(
+            "The candidate layout has a confidence of [{:.2}].",
+            confidence
+        ));
+    }
+
+    Ok(LayoutReport {
+        path: file_path.to_string(),
+        named_sgd_count: named_sgds.len(),
+        known_item_count,
+        chunk_marker_count: markers.len(),
+        chunk_run_count: runs.len(),
+        known_chunk_run_count: known_runs.len(),
+        stride_confidence,
+        header_confidence,
+        fields: vec![level, amount, durability],
+        confidence,
+        candidate,
+        current,
+        matches_current,
+        notes,
+    })
+}
+
+/// Represents a method for finding all named SGD entries.
+/// The entries are validated by the u16 length prefix in front of the name.
+///
+/// ### Parameter
+/// - `content`: The byte data of the current save.
+///
+/// ### Returns `Vec<NamedSgd>`
+/// All named SGD entries, ordered by their index.
+fn find_named_sgds(content: &[u8]) -> Vec<NamedSgd> {
+    let regex: Regex = Regex::new(r"([A-Za-z0-9_]+)SGDs").expect("Failed to create regex");
+    let mut named_sgds: Vec<NamedSgd> = Vec::new();
+
+    for capture in regex.captures_iter(content) {
+        let name_match = capture.get(1).unwrap();
+        let index: usize = name_match.start();
+
+        if index < 2 {
+            continue;
+        }
+
+        let size: usize = u16::from_le_bytes([content[index - 2], content[index - 1]]) as usize;
+        let name: String = String::from_utf8_lossy(name_match.as_bytes()).to_string();
+
+        // Empty mod slots are stored as "None".
+        if size == name.len() && name != "None" {
+            named_sgds.push(NamedSgd { name, index });
+        }
+    }
+
+    named_sgds
+}
+
+/// Represents a method for finding all chunk markers (a bare `SGDs` without a name in front).
+///
+/// ### Parameter
+/// - `content`: The byte data of the current save.
+///
+/// ### Returns `Vec<usize>`
+/// The indices of all chunk markers.
+fn find_chunk_markers(content: &[u8]) -> Vec<usize> {
+    let regex: Regex = Regex::new(r"(?-u:[^A-Za-z0-9_])SGDs").expect("Failed to create regex");
+
+    regex.find_iter(content).map(|m| m.start() + 1).collect()
+}
+
+/// Represents a method for grouping chunk markers into runs with a constant stride.
+///
+/// ### Parameter
+/// - `markers`: The indices of all chunk markers.
+///
+/// ### Returns `Vec<Vec<usize>>`
+/// All runs that contain at least two markers.
+fn group_marker_runs(markers: &[usize]) -> Vec<Vec<usize>> {
+    let mut runs: Vec<Vec<usize>> = Vec::new();
+    let mut current_run: Vec<usize> = Vec::new();
+    let mut current_stride: usize = 0;
+
+    for marker in markers {
+        if let Some(last) = current_run.last() {
+            let stride: usize = marker - last;
+            let fits_run: bool =
+                stride <= MAX_CHUNK_STRIDE && (current_run.len() == 1 || stride == current_stride);
+
+            if fits_run {
+                current_stride = stride;
+                current_run.push(*marker);
+                continue;
+            }
+
+            if current_run.len() > 1 {
+                runs.push(current_run.clone());
+            }
+
+            current_run.clear();
+        }
+
+        current_run.push(*marker);
+    }
+
+    if current_run.len() > 1 {
+        runs.push(current_run);
+    }
+
+    runs
+}
+
+/// Represents a method for checking whether a run of chunk markers belongs to known items.
+/// The items of a tab follow right after its chunks, so the first named entries after the run are checked.
+///
+/// ### Parameter
+/// - `run`: The indices of the chunk markers of the run.
+/// - `named_sgds`: All named SGD entries.
+/// - `known_ids`: All IDs of the ID database.
+///
+/// ### Returns `bool`
+/// Indicates whether at least half of the following entries are known items.
+fn is_known_run(run: &[usize], named_sgds: &[NamedSgd], known_ids: &HashSet<&str>) -> bool {
+    let last_marker: usize = *run.last().unwrap();
+    let following: Vec<&NamedSgd> = named_sgds
+        .iter()
+        .filter(|sgd| sgd.index > last_marker)
+        .take(run.len())
+        .collect();
+
+    if following.is_empty() {
+        return false;
+    }
+
+    let known: usize = following
+        .iter()
+        .filter(|sgd| known_ids.contains(sgd.name.as_str()))
+        .count();
+
+    known * 2 >= following.len()
+}
+
+/// Represents a method for inferring the most common strides between chunks.
+///
+/// ### Parameter
+/// - `runs`: The runs of chunk markers.
+///
+/// ### Returns `(Vec<usize>, f32)`
+/// The candidate strides ordered by frequency and the share of all gaps they cover.
+fn infer_strides(runs: &[&Vec<usize>]) -> (Vec<usize>, f32) {
+    let mut counter: HashMap<usize, usize> = HashMap::new();
+    let mut total: usize = 0;
+
+    for run in runs {
+        // All gaps inside a run are equal, so the run is counted with its amount of gaps.
+        let stride: usize = run[1] - run[0];
+        *counter.entry(stride).or_insert(0) += run.len() - 1;
+        total += run.len() - 1;
+    }
+
+    if total == 0 {
+        return (Vec::new(), 0.0);
+    }
+
+    let mut strides: Vec<(usize, usize)> = counter.into_iter().collect();
+    strides.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let candidates: Vec<(usize, usize)> = strides
+        .into_iter()
+        .filter(|(_, count)| *count as f32 / total as f32 >= MIN_STRIDE_SHARE)
+        .collect();
+    let covered: usize = candidates.iter().map(|(_, count)| count).sum();
+
+    (
+        candidates.into_iter().map(|(stride, _)| stride).collect(),
+        covered as f32 / total as f32,
+    )
+}
+
+/// Represents a method for inferring the size header behind the chunk markers.
+/// Each gap between two markers of a run should equal the stored payload size plus the header size.
+///
+/// ### Parameter
+/// - `content`: The byte data of the current save.
+/// - `runs`: The runs of chunk markers.
+///
+/// ### Returns `(usize, usize, f32)`
+/// The position of the size behind the marker, the header size and the share of all gaps it explains.
+fn infer_size_header(content: &[u8], runs: &[&Vec<usize>]) -> (usize, usize, f32) {
+    let mut counter: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut total: usize = 0;
+
+    for run in runs {
+        for pair in run.windows(2) {
+            let gap: usize = pair[1] - pair[0];
+            total += 1;
+
+            for size_position in (4..=MAX_SIZE_POSITION).step_by(4) {
+                let start: usize = pair[0] + size_position;
+                // Runs near the end of the content have no room for the size header.
+                let size: usize = match content.get(start..start + 4) {
+                    Some(bytes) => {
+                        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize
+                    }
+                    None => continue,
+                };
+
+                // The header has to contain at least the marker and the size itself.
+                if size < gap && gap - size >= size_position + 4 {
+                    *counter.entry((size_position, gap - size)).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    match counter.into_iter().max_by(|a, b| {
+        a.1.cmp(&b.1)
+            .then(b.0 .0.cmp(&a.0 .0))
+            .then(b.0 .1.cmp(&a.0 .1))
+    }) {
+        Some(((size_position, header_size), count)) if total > 0 => {
+            (size_position, header_size, count as f32 / total as f32)
+        }
+        _ => (CURRENT_SIZE_POSITION, CURRENT_HEADER_SIZE, 0.0),
+    }
+}
+
+/// Represents a method for inferring the distance of a field in front of the chunk markers.
+///
+/// ### Parameter
+/// - `field`: The name of the field.
+/// - `content`: The byte data of the current save.
+/// - `samples`: The indices of the sampled chunk markers.
+/// - `size`: The byte size of the field.
+/// - `preferred_distance`: The distance of the current layout, used when candidates are tied.
+/// - `taken`: The distances and sizes of the fields that were already inferred.
+/// - `is_plausible`: Checks whether the bytes of a field contain a plausible value.
+///
+/// ### Returns `LayoutFieldCandidate`
+/// The distance with the most plausible values.
+fn infer_field(
+    field: &str,
+    content: &[u8],
+    samples: &[usize],
+    size: usize,
+    preferred_distance: usize,
+    taken: &[(usize, usize)],
+    is_plausible: fn(&[u8]) -> bool,
+) -> LayoutFieldCandidate {
+    let mut best: LayoutFieldCandidate =
+        LayoutFieldCandidate::new(field.to_string(), preferred_distance, 0, samples.len());
+
+    for distance in MIN_FIELD_DISTANCE.max(size)..=MAX_FIELD_DISTANCE {
+        // Skip distances that overlap with a field that was already inferred.
+        let overlaps: bool = taken.iter().any(|(other_distance, other_size)| {
+            distance > other_distance - other_size && distance - size < *other_distance
+        });
+
+        if overlaps {
+            continue;
+        }
+
+        let hits: usize = samples
+            .iter()
+            .filter(|marker| **marker >= distance)
+            .filter(|marker| is_plausible(&content[*marker - distance..*marker - distance + size]))
+            .count();
+
+        let is_better: bool =
+            hits > best.hits || (hits == best.hits && hits > 0 && distance == preferred_distance);
+
+        if is_better {
+            best = LayoutFieldCandidate::new(field.to_string(), distance, hits, samples.len());
+        }
+    }
+
+    best
+}
+
+/// Represents a method for checking whether the bytes contain a plausible item level.
+///
+/// ### Parameter
+/// - `bytes`: The bytes of the field.
+///
+/// ### Returns `bool`
+/// Indicates whether the value is a plausible level.
+fn is_plausible_level(bytes: &[u8]) -> bool {
+    let value: u16 = u16::from_le_bytes([bytes[0], bytes[1]]);
+
+    value > 0 && value <= MAX_PLAUSIBLE_LEVEL
+}
+
+/// Represents a method for checking whether the bytes contain a plausible item amount.
+///
+/// ### Parameter
+/// - `bytes`: The bytes of the field.
+///
+/// ### Returns `bool`
+/// Indicates whether the value is a plausible amount.
+fn is_plausible_amount(bytes: &[u8]) -> bool {
+    let value: u32 = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    value > 0 && value <= MAX_PLAUSIBLE_AMOUNT
+}
+
+/// Represents a method for checking whether the bytes contain a plausible durability.
+/// Small integers are stored as denormal floats, which is why only normal floats are accepted.
+///
+/// ### Parameter
+/// - `bytes`: The bytes of the field.
+///
+/// ### Returns `bool`
+/// Indicates whether the value is a plausible durability.
+fn is_plausible_durability(bytes: &[u8]) -> bool {
+    let value: f32 = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    value.is_normal() && (-1.0..=MAX_PLAUSIBLE_DURABILITY).contains(&value)
+}
@@ -1,6 +1,34 @@
+pub mod backup_manager;
+pub mod bulk_editor;
 pub mod bypass_crc;
+pub mod coop_checker;
+pub mod edit_plan;
 pub mod file_analyser;
+pub mod id_database;
 pub mod id_fetcher;
+pub mod id_harvester;
+pub mod id_importer;
+pub mod id_search;
+pub mod inventory_importer;
+pub mod item_catalog;
+pub mod item_rules;
+pub mod item_transfer;
+pub mod layout_discovery;
+pub mod pak_extractor;
 pub mod patched_items_fetcher;
+pub mod progression_merger;
+pub mod save_compression;
+pub mod save_converter;
+pub mod save_diff;
+pub mod save_discovery;
+pub mod save_exporter;
+pub mod save_importer;
 pub mod save_outpost;
+pub mod save_records;
+pub mod save_watcher;
+pub mod save_writer;
+pub mod script_runner;
 pub mod struct_data;
+pub mod tab_mapping_fetcher;
+#[cfg(test)]
+pub mod test_support;
@@ -0,0 +1,279 @@
+use crate::save_logic::id_database::is_valid_category;
+use crate::save_logic::struct_data::{IdData, IdExtractionReport, IdImport};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the category of item definitions without (valid) category.
+static FALLBACK_CATEGORY: &str = "Uncategorized";
+
+// Defines how deep the install directory is searched for archives.
+const MAX_SEARCH_DEPTH: usize = 6;
+
+/// Represents a method for extracting all item IDs from the archives of a game installation.
+///
+/// ### Parameter
+/// - `install_path`: The install directory of the game.
+///
+/// ### Returns `(IdImport, IdExtractionReport)`
+/// The import for the ID database and a report of the extraction.
+pub fn extract_game_ids(install_path: &str) -> Result<(IdImport, IdExtractionReport)> {
+    let mut report: IdExtractionReport = IdExtractionReport::new(install_path.to_string());
+    let archives: Vec<PathBuf> = find_pak_archives(Path::new(install_path))?;
+
+    if archives.is_empty() {
+        return Err(format!(
+            "Could not find any .pak archives inside [{}].",
+            install_path
+        )
+        .into());
+    }
+
+    let mut categories: Vec<IdData> = Vec::new();
+    let mut known: HashSet<String> = HashSet::new();
+
+    for archive_path in archives {
+        let archive_name: String = archive_path.display().to_string();
+
+        let definitions = match File::open(&archive_path)
+            .map_err(|e| e.into())
+            .and_then(extract_item_definitions)
+        {
+            Ok(definitions) => definitions,
+            Err(err) => {
+                report
+                    .skipped_archives
+                    .push(format!("{} ({})", archive_name, err));
+                continue;
+            }
+        };
+
+        report.archives.push(archive_name);
+        report.script_count += definitions.0;
+
+        for (id_name, category) in definitions.1 {
+            // The first definition of an ID wins.
+            if !known.insert(id_name.clone()) {
+                continue;
+            }
+
+            *report.category_counts.entry(category.clone()).or_insert(0) += 1;
+            report.item_count += 1;
+
+            match categories.iter_mut().find(|s| s.filename == category) {
+                Some(id_section) => id_section.ids.push(id_name),
+                None => categories.push(IdData::new(category, vec![id_name])),
+            }
+        }
+    }
+
+    if report.item_count == 0 {
+        return Err("The archives do not contain any item definitions.".into());
+    }
+
+    let import: IdImport = IdImport::new(
+        format!("game archives ({})", install_path),
+        String::new(),
+        categories,
+        HashMap::new(),
+    );
+
+    Ok((import, report))
+}
+
+/// Represents a method for reading all item definitions of a single `.pak` archive (zip container).
+///
+/// ### Parameter
+/// - `reader`: The reader of the archive.
+///
+/// ### Returns `(usize, Vec<(String, String)>)`
+/// The amount of read script files and every item definition (ID and category).
+pub fn extract_item_definitions<R: Read + Seek>(
+    reader: R,
+) -> Result<(usize, Vec<(String, String)>)> {
+    let mut archive =
+        zip::ZipArchive::new(reader).map_err(|e| format!("Not a valid archive: {}", e))?;
+    let mut definitions: Vec<(String, String)> = Vec::new();
+    let mut script_count: usize = 0;
+
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| format!("Error reading the archive: {}", e))?;
+
+        if !file.is_file() || !file.name().to_lowercase().ends_with(".scr") {
+            continue;
+        }
+
+        let mut content: Vec<u8> = Vec::new();
+        file.read_to_end(&mut content)?;
+        script_count += 1;
+
+        definitions.extend(parse_item_definitions(&String::from_utf8_lossy(&content)));
+    }
+
+    Ok((script_count, definitions))
+}
+
+/// Represents a method for parsing the item definitions of a script.
+/// Items are defined as `Item("<id>", "<category>")`. The category can be missing or
+/// written without quotes, invalid categories are stored as `Uncategorized`.
+///
+/// ### Parameter
+/// - `script`: The content of the `.scr` file.
+///
+/// ### Returns `Vec<(String, String)>`
+/// The ID and the category of every item definition.
+pub fn parse_item_definitions(script: &str) -> Vec<(String, String)> {
+    let regex: Regex =
+        Regex::new(r#"(?m)^\s*Item\s*\(\s*"([^"]+)"\s*(?:,\s*"?&?([^",&)]*)&?"?\s*)?\)"#)
+            .expect("Failed to create regex");
+
+    regex
+        .captures_iter(script)
+        .filter_map(|captures| {
+            let id_name: String = captures.get(1)?.as_str().trim().to_string();
+            let category: String = captures
+                .get(2)
+                .map(|c| c.as_str().trim().to_string())
+                .filter(|c| is_valid_category(c))
+                .unwrap_or(FALLBACK_CATEGORY.to_string());
+
+            if id_name.is_empty() || !id_name.chars().all(|c| c.is_ascii_graphic()) {
+                return None;
+            }
+
+            Some((id_name, category))
+        })
+        .collect()
+}
+
+/// Represents a method for finding all `.pak` archives inside the install directory.
+///
+/// ### Parameter
+/// - `install_path`: The install directory of the game.
+///
+/// ### Returns `Vec<PathBuf>`
+/// The paths of all archives (sorted).
+fn find_pak_archives(install_path: &Path) -> Result<Vec<PathBuf>> {
+    if !install_path.is_dir() {
+        return Err(format!(
+            "The install directory [{}] does not exist.",
+            install_path.display()
+        )
+        .into());
+    }
+
+    let mut archives: Vec<PathBuf> = Vec::new();
+    let mut directories: Vec<(PathBuf, usize)> = vec![(install_path.to_path_buf(), 0)];
+
+    while let Some((directory, depth)) = directories.pop() {
+        let entries = match fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let path: PathBuf = entry.path();
+
+            if path.is_dir() && depth < MAX_SEARCH_DEPTH {
+                directories.push((path, depth + 1));
+            } else if path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("pak"))
+            {
+                archives.push(path);
+            }
+        }
+    }
+
+    archives.sort();
+    Ok(archives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+
+    /// Builds a synthetic `.pak` archive with the given files.
+    fn create_pak(files: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+
+        writer.add_directory("scripts/", options).unwrap();
+
+        for (name, content) in files {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+
+        let mut archive = writer.finish().unwrap();
+        archive.set_position(0);
+        archive
+    }
+
+    #[test]
+    fn parses_item_definitions() {
+        let script = r#"
+Item("Token_Gold", "Token")
+  Item ( "Medkit_Small" , Medkit )
+Item("Craft_Part_1", &CraftPart&)
+Item("No_Category")
+Item("Bad_Category", "Not a category")
+// Item("Commented", "Melee")
+Item("Has Space", "Melee")
+Item("", "Melee")
+"#;
+
+        assert_eq!(
+            parse_item_definitions(script),
+            vec![
+                ("Token_Gold".to_string(), "Token".to_string()),
+                ("Medkit_Small".to_string(), "Medkit".to_string()),
+                ("Craft_Part_1".to_string(), "CraftPart".to_string()),
+                ("No_Category".to_string(), FALLBACK_CATEGORY.to_string()),
+                ("Bad_Category".to_string(), FALLBACK_CATEGORY.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_definitions_of_scripts_only() {
+        let archive = create_pak(&[
+            (
+                "scripts/inventory/items.scr",
+                "Item(\"Token_Gold\", \"Token\")\n",
+            ),
+            (
+                "scripts/inventory/WEAPONS.SCR",
+                "Item(\"Knife\", \"Melee\")\n",
+            ),
+            ("scripts/readme.txt", "Item(\"Ignored\", \"Melee\")\n"),
+        ]);
+
+        let (script_count, definitions) = extract_item_definitions(archive).unwrap();
+
+        assert_eq!(script_count, 2);
+        assert_eq!(
+            definitions,
+            vec![
+                ("Token_Gold".to_string(), "Token".to_string()),
+                ("Knife".to_string(), "Melee".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_archives() {
+        assert!(extract_item_definitions(Cursor::new(b"not a zip".to_vec())).is_err());
+    }
+}
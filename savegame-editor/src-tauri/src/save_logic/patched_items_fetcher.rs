@@ -9,7 +9,8 @@ type Result<T> = std::result::Result<T, Box<dyn Error>>;
 /// ### Returns `PatchedItems`
 /// A collection of all patched items.
 pub fn fetch_patched_ids(id_path: &String) -> Result<PatchedItems> {
-    let entries = fs::read_dir(id_path).unwrap();
+    let entries = fs::read_dir(id_path)
+        .map_err(|e| format!("Error reading the patched items directory: {}", e))?;
 
     // Check if there is a file called "not_dropable.txt" and "not_shareable.txt"
     let mut not_dropable: Vec<String> = Vec::new();
@@ -0,0 +1,637 @@
+//! Copies the progression (skill points and unlockables) of one save into another.
+//!
+//! Skill points are changed in place. Skills and unlockables that are missing in the target save are inserted, which
+//! changes the size of the save (see `save_records`). The records of both kinds look like:
+//! - unlockables: `SGDs [u32 version] [u32 size] [u64 handle] [u64 type] [u32 ID value] [u16 name size] [name]`
+//! - skills: `SGDs [u32 version] [u32 size] [u64 handle] [u64 type] [u16 name size] [name] [u16 points]`
+//!
+//! A missing record is copied from the source save behind the last record of its kind in the target save, and its
+//! handle is listed next to the handles of the other records of its kind.
+
+use crate::logger::ConsoleLogger;
+use crate::save_logic::file_analyser::{clean_sgd_name, edit_skill, load_save_file};
+use crate::save_logic::item_rules::ItemRuleEngine;
+use crate::save_logic::save_records::{
+    insert_records, read_u16, read_u32, shift_index, Insertions, RECORD_HEADER_SIZE, RECORD_MARKER,
+    RECORD_SIZE_OFFSET,
+};
+use crate::save_logic::struct_data::{
+    InventoryTabMapping, ItemCatalog, NewRecord, ProgressionMergePreview, SaveFile, SkillItem,
+    SkillMergeChange, UnlockableItem,
+};
+use std::collections::HashSet;
+use std::error::Error;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the bytes in front of the name of an unlockable record (handle, type, ID value and name size).
+const UNLOCKABLE_PREFIX_SIZE: usize = 22;
+
+// Defines the bytes in front of the name of a skill record (handle, type and name size) and the size of the points.
+const SKILL_PREFIX_SIZE: usize = 18;
+const SKILL_POINTS_SIZE: usize = 2;
+
+// Defines the suffix of the parsed names (the SGDs marker of the next record).
+static SGD_SUFFIX: &str = "SGDs";
+
+// Defines the skill trees.
+static BASE_SKILL_TREE: &str = "base";
+static LEGEND_SKILL_TREE: &str = "legend";
+
+/// Represents a method for previewing the merge of the progression of the source save into the target save.
+///
+/// ### Parameter
+/// - `source_save`: The parsed save whose progression is copied.
+/// - `target_save`: The parsed save that receives the progression.
+///
+/// ### Returns `ProgressionMergePreview`
+/// The changed skills, the added skills and the added unlockables.
+pub fn preview_progression_merge(
+    source_save: &SaveFile,
+    target_save: &SaveFile,
+) -> Result<ProgressionMergePreview> {
+    let (preview, _) = merge_progression(source_save, target_save)?;
+
+    Ok(preview)
+}
+
+/// Represents a method for merging the progression of the source save into the target save.
+/// The skill points of the source save are copied (missing skills are added) and the unlockables of both saves are
+/// combined.
+///
+/// ### Parameter
+/// - `source_save`: The parsed save whose progression is copied.
+/// - `target_save`: The parsed save that receives the progression.
+///
+/// ### Returns `(ProgressionMergePreview, Vec<u8>)`
+/// The changes of the merge and the new content of the target save.
+pub fn merge_progression(
+    source_save: &SaveFile,
+    target_save: &SaveFile,
+) -> Result<(ProgressionMergePreview, Vec<u8>)> {
+    let mut preview: ProgressionMergePreview =
+        ProgressionMergePreview::new(source_save.path.clone(), target_save.path.clone());
+    let mut content: Vec<u8> = target_save.file_content.clone();
+    let mut added_skills: HashSet<String> = HashSet::new();
+    let mut missing_skills: Vec<&SkillItem> = Vec::new();
+
+    // Copies the skill points of both trees (changed in place, so the offsets stay valid).
+    for (tree, source_skills, target_skills) in [
+        (
+            BASE_SKILL_TREE,
+            get_valid_skills(&source_save.file_content, &source_save.skills.base_skills),
+            get_valid_skills(&target_save.file_content, &target_save.skills.base_skills),
+        ),
+        (
+            LEGEND_SKILL_TREE,
+            get_valid_skills(&source_save.file_content, &source_save.skills.legend_skills),
+            get_valid_skills(&target_save.file_content, &target_save.skills.legend_skills),
+        ),
+    ] {
+        for source_skill in source_skills.into_iter() {
+            let name: String = clean_sgd_name(&source_skill.name);
+            let target_skill: Option<&SkillItem> = target_skills
+                .iter()
+                .find(|skill| clean_sgd_name(&skill.name) == name)
+                .copied();
+
+            match target_skill {
+                Some(target_skill) if target_skill.points_value != source_skill.points_value => {
+                    content = edit_skill(
+                        target_skill.size,
+                        target_skill.index,
+                        source_skill.points_value,
+                        content,
+                    );
+                    preview.skill_changes.push(SkillMergeChange::new(
+                        tree.to_string(),
+                        name,
+                        target_skill.points_value,
+                        source_skill.points_value,
+                    ));
+                }
+                Some(_) => {}
+                None if added_skills.insert(name.clone()) => {
+                    missing_skills.push(source_skill);
+                    preview.added_skills.push(name);
+                }
+                None => {}
+            }
+        }
+    }
+
+    // Collects the unlockables that are only part of the source save.
+    let target_names: HashSet<&str> = target_save
+        .unlockable_items
+        .iter()
+        .map(|unlockable| unlockable.name.as_str())
+        .collect();
+    let mut added: HashSet<&str> = HashSet::new();
+    let missing_unlockables: Vec<&UnlockableItem> = source_save
+        .unlockable_items
+        .iter()
+        .filter(|unlockable| {
+            !target_names.contains(unlockable.name.as_str()) && added.insert(&unlockable.name)
+        })
+        .collect();
+
+    // Tracks the inserted bytes, so the offsets of the target save can be shifted behind them.
+    let mut insertions: Insertions = Vec::new();
+
+    if !missing_skills.is_empty() {
+        let mut target_skills: Vec<&SkillItem> =
+            get_valid_skills(&target_save.file_content, &target_save.skills.base_skills);
+        target_skills.extend(get_valid_skills(
+            &target_save.file_content,
+            &target_save.skills.legend_skills,
+        ));
+
+        (content, insertions) = insert_skills(
+            &source_save.file_content,
+            &missing_skills,
+            &target_save.file_content,
+            &target_skills,
+            content,
+        )?;
+    }
+
+    if !missing_unlockables.is_empty() {
+        (content, _) = insert_unlockables(
+            &source_save.file_content,
+            &missing_unlockables,
+            &target_save.file_content,
+            &target_save.unlockable_items,
+            &insertions,
+            content,
+        )?;
+        preview.added_unlockables = missing_unlockables
+            .iter()
+            .map(|unlockable| clean_sgd_name(&unlockable.name))
+            .collect();
+    }
+
+    preview.added_bytes = content.len() - target_save.file_content.len();
+
+    Ok((preview, content))
+}
+
+/// Represents a method for merging the progression of the source save into the target save and analysing the
+/// target save again, so every entry of the target save has its current offsets.
+///
+/// ### Parameter
+/// - `source_save`: The parsed save whose progression is copied.
+/// - `target_save`: The parsed save that receives the progression.
+/// - `catalog`: The item catalog with a record for every known ID.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
+/// - `logger`: The console logger that logs every event.
+///
+/// ### Returns `(ProgressionMergePreview, SaveFile)`
+/// The changes of the merge and the re-indexed target save.
+pub fn merge_progression_between_saves(
+    source_save: &SaveFile,
+    target_save: &SaveFile,
+    catalog: ItemCatalog,
+    tab_mapping: InventoryTabMapping,
+    item_rules: ItemRuleEngine,
+    logger: &mut ConsoleLogger,
+) -> Result<(ProgressionMergePreview, SaveFile)> {
+    let (preview, content) = merge_progression(source_save, target_save)?;
+
+    let mut new_target_save: SaveFile = load_save_file(
+        &target_save.path,
+        content,
+        catalog,
+        tab_mapping,
+        item_rules,
+        logger,
+        false,
+        target_save.is_compressed,
+    )
+    .map_err(|e| {
+        format!(
+            "The target save could not be analysed after the merge: {}",
+            e
+        )
+    })?;
+    new_target_save.file_hash = target_save.file_hash.clone();
+    new_target_save.gzip_profile = target_save.gzip_profile.clone();
+
+    // Every added skill has to be found again, otherwise the merge is rejected.
+    let skills: HashSet<String> = new_target_save
+        .skills
+        .base_skills
+        .iter()
+        .chain(new_target_save.skills.legend_skills.iter())
+        .map(|skill| clean_sgd_name(&skill.name))
+        .collect();
+
+    if let Some(name) = preview
+        .added_skills
+        .iter()
+        .find(|name| !skills.contains(*name))
+    {
+        return Err(format!("The skill [{}] could not be found after the merge.", name).into());
+    }
+
+    // Every added unlockable has to be found again, otherwise the merge is rejected.
+    let unlockables: HashSet<String> = new_target_save
+        .unlockable_items
+        .iter()
+        .map(|unlockable| clean_sgd_name(&unlockable.name))
+        .collect();
+
+    if let Some(name) = preview
+        .added_unlockables
+        .iter()
+        .find(|name| !unlockables.contains(*name))
+    {
+        return Err(format!(
+            "The unlockable [{}] could not be found after the merge.",
+            name
+        )
+        .into());
+    }
+
+    Ok((preview, new_target_save))
+}
+
+/// Represents a method for inserting the records of skills behind the last skill of the target save.
+///
+/// ### Parameter
+/// - `source_content`: The content of the source save.
+/// - `skills`: The skills of the source save that are inserted.
+/// - `target_content`: The original content of the target save (the offsets of the skills point into it).
+/// - `target_skills`: The skills of the target save (of both trees).
+/// - `content`: The content of the target save.
+///
+/// ### Returns `(Vec<u8>, Insertions)`
+/// The new content of the target save and the inserted bytes (offset inside the original content and length).
+fn insert_skills(
+    source_content: &[u8],
+    skills: &[&SkillItem],
+    target_content: &[u8],
+    target_skills: &[&SkillItem],
+    content: Vec<u8>,
+) -> Result<(Vec<u8>, Insertions)> {
+    let first_skill: &SkillItem = target_skills
+        .iter()
+        .min_by_key(|skill| skill.index)
+        .ok_or("The target save does not contain any skill.")?;
+    let last_skill: &SkillItem = target_skills
+        .iter()
+        .max_by_key(|skill| skill.index)
+        .unwrap();
+
+    let (first_record, _) = find_skill_record(target_content, first_skill)?;
+    let (_, insert_index) = find_skill_record(target_content, last_skill)?;
+    let records: Vec<NewRecord> = skills
+        .iter()
+        .map(|skill| {
+            find_skill_record(source_content, skill).map(|(start, end)| {
+                NewRecord::new(source_content[start..end].to_vec(), true, Vec::new())
+            })
+        })
+        .collect::<Result<Vec<NewRecord>>>()?;
+
+    insert_records(content, first_record, insert_index, records, "skills")
+}
+
+/// Represents a method for inserting the records of unlockables behind the last unlockable of the target save.
+///
+/// ### Parameter
+/// - `source_content`: The content of the source save.
+/// - `unlockables`: The unlockables of the source save that are inserted.
+/// - `target_content`: The original content of the target save (the offsets of the unlockables point into it).
+/// - `target_unlockables`: The unlockables of the target save.
+/// - `insertions`: The bytes that were inserted into the target save before.
+/// - `content`: The content of the target save.
+///
+/// ### Returns `(Vec<u8>, Insertions)`
+/// The new content of the target save and the inserted bytes (offset inside the given content and length).
+fn insert_unlockables(
+    source_content: &[u8],
+    unlockables: &[&UnlockableItem],
+    target_content: &[u8],
+    target_unlockables: &[UnlockableItem],
+    insertions: &[(usize, usize)],
+    content: Vec<u8>,
+) -> Result<(Vec<u8>, Insertions)> {
+    let first_unlockable: &UnlockableItem = target_unlockables
+        .first()
+        .ok_or("The target save does not contain any unlockable.")?;
+    let last_unlockable: &UnlockableItem = target_unlockables.last().unwrap();
+
+    let (first_record, _) = find_unlockable_record(target_content, first_unlockable)?;
+    let (_, insert_index) = find_unlockable_record(target_content, last_unlockable)?;
+    let records: Vec<NewRecord> = unlockables
+        .iter()
+        .map(|unlockable| {
+            read_unlockable_record(source_content, unlockable)
+                .map(|record| NewRecord::new(record, true, Vec::new()))
+        })
+        .collect::<Result<Vec<NewRecord>>>()?;
+
+    insert_records(
+        content,
+        shift_index(first_record, insertions),
+        shift_index(insert_index, insertions),
+        records,
+        "unlockables",
+    )
+}
+
+/// Represents a method for finding and validating the record of an unlockable.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+/// - `unlockable`: The unlockable (its name ends with the SGDs marker of the next record).
+///
+/// ### Returns `(usize, usize)`
+/// The start (SGDs marker) and the end of the record.
+fn find_unlockable_record(content: &[u8], unlockable: &UnlockableItem) -> Result<(usize, usize)> {
+    let invalid = || {
+        format!(
+            "The unlockable [{}] has an unknown layout.",
+            unlockable.name
+        )
+    };
+    let id_name: &str = unlockable
+        .name
+        .strip_suffix(SGD_SUFFIX)
+        .ok_or_else(invalid)?;
+    let start: usize = unlockable
+        .index
+        .checked_sub(UNLOCKABLE_PREFIX_SIZE + RECORD_HEADER_SIZE)
+        .ok_or_else(invalid)?;
+    let end: usize = unlockable.index + id_name.len();
+
+    let is_valid: bool = content.get(start..start + RECORD_MARKER.len())
+        == Some(&RECORD_MARKER[..])
+        && read_u32(content, start + RECORD_SIZE_OFFSET) as usize
+            == UNLOCKABLE_PREFIX_SIZE + id_name.len()
+        && read_u16(content, unlockable.index - 2) as usize == id_name.len()
+        && content.get(unlockable.index..end) == Some(id_name.as_bytes());
+
+    match is_valid {
+        true => Ok((start, end)),
+        false => Err(invalid().into()),
+    }
+}
+
+/// Represents a method for reading the complete record of an unlockable.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+/// - `unlockable`: The unlockable.
+///
+/// ### Returns `Vec<u8>`
+/// The bytes of the record (from the SGDs marker to the end of the name).
+fn read_unlockable_record(content: &[u8], unlockable: &UnlockableItem) -> Result<Vec<u8>> {
+    let (start, end) = find_unlockable_record(content, unlockable)?;
+
+    Ok(content[start..end].to_vec())
+}
+
+/// Represents a method for filtering the skills that have a valid skill record.
+/// The skill pattern also matches inside the names of other records, these entries are never merged.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+/// - `skills`: The parsed skills.
+///
+/// ### Returns `Vec<&SkillItem>`
+/// The skills with a valid record.
+fn get_valid_skills<'a>(content: &[u8], skills: &'a [SkillItem]) -> Vec<&'a SkillItem> {
+    skills
+        .iter()
+        .filter(|skill| find_skill_record(content, skill).is_ok())
+        .collect()
+}
+
+/// Represents a method for finding and validating the record of a skill.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+/// - `skill`: The skill.
+///
+/// ### Returns `(usize, usize)`
+/// The start (SGDs marker) and the end of the record (behind the points).
+fn find_skill_record(content: &[u8], skill: &SkillItem) -> Result<(usize, usize)> {
+    let invalid = || format!("The skill [{}] has an unknown layout.", skill.name);
+    let start: usize = skill
+        .index
+        .checked_sub(SKILL_PREFIX_SIZE + RECORD_HEADER_SIZE)
+        .ok_or_else(invalid)?;
+    let end: usize = skill.index + skill.name.len() + SKILL_POINTS_SIZE;
+
+    let is_valid: bool = end <= content.len()
+        && content.get(start..start + RECORD_MARKER.len()) == Some(&RECORD_MARKER[..])
+        && read_u32(content, start + RECORD_SIZE_OFFSET) as usize
+            == SKILL_PREFIX_SIZE + skill.name.len() + SKILL_POINTS_SIZE
+        && read_u16(content, skill.index - 2) as usize == skill.name.len()
+        && content.get(skill.index..skill.index + skill.name.len()) == Some(skill.name.as_bytes());
+
+    match is_valid {
+        true => Ok((start, end)),
+        false => Err(invalid().into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save_logic::save_records::{
+        find_handle_array, find_records, find_section, read_u64, FILE_SIZE_OFFSET,
+        RECORD_HANDLE_OFFSET, SECTION_MARKER, SECTION_SIZES,
+    };
+    use crate::save_logic::test_support::{load_outpost_save, load_resources};
+
+    /// Builds a record with the given handle and the content behind its type.
+    fn create_record(handle: u64, body: &[u8]) -> Vec<u8> {
+        let mut record: Vec<u8> = RECORD_MARKER.to_vec();
+        record.extend(((16 + body.len()) as u32).to_le_bytes());
+        record.extend(handle.to_le_bytes());
+        record.extend(1u64.to_le_bytes());
+        record.extend(body);
+        record
+    }
+
+    /// Builds the record of an unlockable and the parsed unlockable (relative to the start of the record).
+    fn create_unlockable(handle: u64, name: &str) -> (Vec<u8>, UnlockableItem) {
+        let mut body: Vec<u8> = 7u32.to_le_bytes().to_vec();
+        body.extend((name.len() as u16).to_le_bytes());
+        body.extend(name.as_bytes());
+
+        let unlockable: UnlockableItem = UnlockableItem::new(
+            format!("{}{}", name, SGD_SUFFIX),
+            RECORD_HEADER_SIZE + UNLOCKABLE_PREFIX_SIZE,
+            name.len(),
+            Vec::new(),
+        );
+
+        (create_record(handle, &body), unlockable)
+    }
+
+    /// Builds a section with valid sizes around the given records.
+    fn create_section(records: &[u8]) -> Vec<u8> {
+        let mut section: Vec<u8> = SECTION_MARKER.to_vec();
+        section.resize(41, 0);
+        section.extend(records);
+
+        for (offset, counted_from) in SECTION_SIZES.iter() {
+            let size: u32 = (section.len() - counted_from) as u32;
+            section[*offset..offset + 4].copy_from_slice(&size.to_le_bytes());
+        }
+
+        section
+    }
+
+    /// Asserts that the sections around the handle array and the last record of a kind have valid sizes.
+    fn assert_valid_sections(content: &[u8], first_record: usize, last_record: usize) {
+        let handles: HashSet<u64> = find_records(content)
+            .iter()
+            .map(|(start, _)| read_u64(content, start + RECORD_HANDLE_OFFSET))
+            .collect();
+        let (_, array_end) = find_handle_array(
+            content,
+            &handles,
+            read_u64(content, first_record + RECORD_HANDLE_OFFSET),
+            first_record,
+        )
+        .unwrap();
+
+        assert!(find_section(content, array_end).is_ok());
+        assert!(find_section(content, last_record).is_ok());
+    }
+
+    /// Counts the sections whose sizes match their content.
+    fn count_valid_sections(content: &[u8]) -> usize {
+        content
+            .windows(SECTION_MARKER.len())
+            .enumerate()
+            .filter(|(position, window)| {
+                *window == SECTION_MARKER
+                    && find_section(content, position + 1).is_ok_and(|start| start == *position)
+            })
+            .count()
+    }
+
+    #[test]
+    fn merges_outpost_saves_with_valid_sizes() {
+        let source: SaveFile = load_outpost_save("Endgame/endgame.sav");
+        let target: SaveFile = load_outpost_save("Ghost/ghost.sav");
+
+        let (catalog, tab_mapping, item_rules) = load_resources();
+        let (preview, merged) = merge_progression_between_saves(
+            &source,
+            &target,
+            catalog,
+            tab_mapping,
+            item_rules,
+            &mut ConsoleLogger::new(),
+        )
+        .unwrap();
+
+        assert!(!preview.added_skills.is_empty());
+        assert!(!preview.added_unlockables.is_empty());
+        assert_eq!(
+            merged.file_content.len(),
+            target.file_content.len() + preview.added_bytes
+        );
+
+        // The file header and the sections around the handles and the records have the new sizes.
+        let content: &[u8] = &merged.file_content;
+        assert_eq!(
+            read_u32(content, FILE_SIZE_OFFSET) as usize + FILE_SIZE_OFFSET + 4,
+            content.len()
+        );
+
+        let mut skills: Vec<&SkillItem> = get_valid_skills(content, &merged.skills.base_skills);
+        skills.extend(get_valid_skills(content, &merged.skills.legend_skills));
+        skills.sort_by_key(|skill| skill.index);
+
+        assert_valid_sections(
+            content,
+            find_skill_record(content, skills.first().unwrap())
+                .unwrap()
+                .0,
+            find_skill_record(content, skills.last().unwrap())
+                .unwrap()
+                .1,
+        );
+        assert_valid_sections(
+            content,
+            find_unlockable_record(content, merged.unlockable_items.first().unwrap())
+                .unwrap()
+                .0,
+            find_unlockable_record(content, merged.unlockable_items.last().unwrap())
+                .unwrap()
+                .1,
+        );
+        assert_eq!(
+            count_valid_sections(content),
+            count_valid_sections(&target.file_content)
+        );
+
+        // A second merge has nothing left to add.
+        let (preview, _) = merge_progression(&source, &merged).unwrap();
+        assert!(preview.added_skills.is_empty());
+        assert!(preview.added_unlockables.is_empty());
+    }
+
+    #[test]
+    fn inserts_unlockables_into_a_later_section() {
+        // The handle array is part of the first section, the unlockables are part of the second one.
+        let mut array: Vec<u8> = 1u32.to_le_bytes().to_vec();
+        array.extend(0x200u64.to_le_bytes());
+        let (record, mut unlockable) = create_unlockable(0x200, "Token_A");
+
+        let first_section: Vec<u8> = create_section(&create_record(0x100, &array));
+        let mut records: Vec<u8> = record;
+        records.extend(create_record(0x300, &[]));
+        let second_section: Vec<u8> = create_section(&records);
+
+        let mut content: Vec<u8> = vec![0; FILE_SIZE_OFFSET + 4];
+        content.extend(&first_section);
+        content.extend(&second_section);
+        let file_size: u32 = (content.len() - FILE_SIZE_OFFSET - 4) as u32;
+        content[FILE_SIZE_OFFSET..FILE_SIZE_OFFSET + 4].copy_from_slice(&file_size.to_le_bytes());
+        unlockable.index += FILE_SIZE_OFFSET + 4 + first_section.len() + 41;
+
+        let (source_content, source_unlockable) = create_unlockable(0x200, "Token_B");
+        let (merged, _) = insert_unlockables(
+            &source_content,
+            &[&source_unlockable],
+            &content,
+            &[unlockable],
+            &[],
+            content.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(merged.len(), content.len() + 8 + source_content.len());
+        assert_eq!(
+            read_u32(&merged, FILE_SIZE_OFFSET) as usize + FILE_SIZE_OFFSET + 4,
+            merged.len()
+        );
+        assert_eq!(count_valid_sections(&merged), 2);
+
+        // The handle array lists both unlockables and the new record follows the old one.
+        let count_index: usize = FILE_SIZE_OFFSET + 4 + 41 + RECORD_HEADER_SIZE + 16;
+        assert_eq!(read_u32(&merged, count_index), 2);
+        assert_eq!(read_u64(&merged, count_index + 4), 0x200);
+        assert_eq!(read_u64(&merged, count_index + 12), 0x320);
+
+        let records: Vec<(usize, usize)> = find_records(&merged);
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].1, records[1].0 - 41);
+        assert_eq!(records[2].0, records[1].1);
+        assert_eq!(
+            read_u64(&merged, records[2].0 + RECORD_HANDLE_OFFSET),
+            0x320
+        );
+        assert!(merged[records[2].0..records[2].1].ends_with(b"Token_B"));
+    }
+}
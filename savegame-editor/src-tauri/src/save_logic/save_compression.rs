@@ -0,0 +1,283 @@
+//! Compresses PC saves the same way they were compressed when they were loaded.
+//!
+//! A PC save is a single gzip member: `[header] [deflate stream] [u32 CRC32] [u32 size]`. When a save is loaded,
+//! the complete header (flags, modification time, extra flags, operating system and the optional extra field,
+//! file name, comment and header CRC) is kept as it is, and the compression level that reproduces the original
+//! deflate stream is searched. When the save is compressed again, the kept header and the found level are used,
+//! so an unchanged save results in the same bytes. Every compressed save is decompressed again and compared to
+//! the content before it is returned.
+
+use crate::save_logic::file_analyser::{
+    decompress_save_content, export_save_for_pc, get_content_hash,
+};
+use crate::save_logic::struct_data::GzipProfile;
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use std::error::Error;
+use std::io::Write;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the magic bytes and the compression method (deflate) of a gzip file.
+static GZIP_MAGIC: [u8; 3] = [0x1f, 0x8b, 0x08];
+
+// Defines the flags of the gzip header.
+const FLAG_HEADER_CRC: u8 = 0x02;
+const FLAG_EXTRA: u8 = 0x04;
+const FLAG_NAME: u8 = 0x08;
+const FLAG_COMMENT: u8 = 0x10;
+
+// Defines the size of the fixed header and the trailer (CRC32 and size) of a gzip file.
+const HEADER_SIZE: usize = 10;
+const TRAILER_SIZE: usize = 8;
+
+// Defines the order the compression levels are tested in (the most common levels first).
+const LEVEL_ORDER: [u32; 10] = [6, 9, 1, 5, 7, 8, 4, 3, 2, 0];
+
+// Defines the amount of bytes that is compressed before the output is compared to the original stream.
+const COMPARE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Represents a method for reading the gzip header and the compression parameters of a PC save.
+///
+/// ### Parameter
+/// - `compressed`: The compressed content of the save (as stored on disk).
+/// - `content`: The decompressed content of the save.
+///
+/// ### Returns `GzipProfile`
+/// The header and the compression level of the save.
+pub fn read_gzip_profile(compressed: &[u8], content: &[u8]) -> Result<GzipProfile> {
+    if compressed.len() < HEADER_SIZE + TRAILER_SIZE || compressed[..3] != GZIP_MAGIC {
+        return Err("The save is not a gzip compressed PC save.".into());
+    }
+
+    let flags: u8 = compressed[3];
+    let mtime: u32 = u32::from_le_bytes(compressed[4..8].try_into()?);
+    let extra_flags: u8 = compressed[8];
+    let operating_system: u8 = compressed[9];
+    let mut index: usize = HEADER_SIZE;
+    let mut filename: String = String::new();
+    let mut comment: String = String::new();
+
+    if flags & FLAG_EXTRA != 0 {
+        let extra_size: usize = u16::from_le_bytes(
+            compressed
+                .get(index..index + 2)
+                .ok_or("The gzip header is incomplete.")?
+                .try_into()?,
+        ) as usize;
+        index += 2 + extra_size;
+    }
+
+    if flags & FLAG_NAME != 0 {
+        let (text, end) = read_header_text(compressed, index)?;
+        filename = text;
+        index = end;
+    }
+
+    if flags & FLAG_COMMENT != 0 {
+        let (text, end) = read_header_text(compressed, index)?;
+        comment = text;
+        index = end;
+    }
+
+    if flags & FLAG_HEADER_CRC != 0 {
+        index += 2;
+    }
+
+    if index + TRAILER_SIZE > compressed.len() {
+        return Err("The gzip header is incomplete.".into());
+    }
+
+    // The stream can only be reproduced if the save consists of a single gzip member.
+    let stream: &[u8] = &compressed[index..compressed.len() - TRAILER_SIZE];
+    let is_single_member: bool =
+        compressed[compressed.len() - TRAILER_SIZE..] == create_trailer(content)[..];
+    let found_level: Option<u32> = match is_single_member {
+        true => LEVEL_ORDER
+            .iter()
+            .copied()
+            .find(|level| is_matching_level(content, stream, *level)),
+        false => None,
+    };
+
+    // The extra flags hint at the level (2 = best compression, 4 = fastest compression).
+    let level: u32 = found_level.unwrap_or(match extra_flags {
+        2 => Compression::best().level(),
+        4 => Compression::fast().level(),
+        _ => Compression::default().level(),
+    });
+
+    Ok(GzipProfile::new(
+        compressed[..index].to_vec(),
+        flags,
+        mtime,
+        extra_flags,
+        operating_system,
+        filename,
+        comment,
+        level,
+        found_level.is_some(),
+        get_content_hash(content),
+    ))
+}
+
+/// Represents a method for compressing the content of a PC save and verifying the result.
+///
+/// ### Parameter
+/// - `content`: The (edited) content of the save.
+/// - `profile`: The gzip profile of the loaded save (`None` creates a new header with the default compression).
+///
+/// ### Returns `Vec<u8>`
+/// The compressed content.
+pub fn compress_save_content(content: &[u8], profile: Option<&GzipProfile>) -> Result<Vec<u8>> {
+    let compressed: Vec<u8> = match profile {
+        Some(profile) => {
+            let mut encoder =
+                DeflateEncoder::new(profile.header.clone(), Compression::new(profile.level));
+            encoder.write_all(content)?;
+            let mut compressed: Vec<u8> = encoder.finish()?;
+            compressed.extend(create_trailer(content));
+            compressed
+        }
+        None => export_save_for_pc(&content.to_vec()),
+    };
+
+    let decompressed: Vec<u8> = decompress_save_content(&compressed)
+        .map_err(|e| format!("The compressed save could not be verified: {}", e))?;
+
+    if decompressed != content {
+        return Err("The compressed save does not match the edited content.".into());
+    }
+
+    Ok(compressed)
+}
+
+/// Represents a method for checking whether a compression level reproduces the original deflate stream.
+/// The content is compressed in chunks, so a wrong level is usually rejected after the first chunk.
+///
+/// ### Parameter
+/// - `content`: The decompressed content of the save.
+/// - `stream`: The original deflate stream.
+/// - `level`: The tested compression level.
+///
+/// ### Returns `bool`
+/// Indicates whether the level creates exactly the original stream.
+fn is_matching_level(content: &[u8], stream: &[u8], level: u32) -> bool {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::new(level));
+
+    for chunk in content.chunks(COMPARE_CHUNK_SIZE) {
+        if encoder.write_all(chunk).is_err() {
+            return false;
+        }
+
+        let output: &Vec<u8> = encoder.get_ref();
+        if output.len() > stream.len() || output[..] != stream[..output.len()] {
+            return false;
+        }
+    }
+
+    encoder.finish().is_ok_and(|output| output == stream)
+}
+
+/// Represents a method for creating the gzip trailer of a content.
+///
+/// ### Parameter
+/// - `content`: The decompressed content.
+///
+/// ### Returns `Vec<u8>`
+/// The CRC32 and the size of the content.
+fn create_trailer(content: &[u8]) -> Vec<u8> {
+    let mut crc: Crc = Crc::new();
+    crc.update(content);
+
+    let mut trailer: Vec<u8> = crc.sum().to_le_bytes().to_vec();
+    trailer.extend((content.len() as u32).to_le_bytes());
+    trailer
+}
+
+/// Represents a method for reading a zero terminated text (ISO 8859-1) of the gzip header.
+///
+/// ### Parameter
+/// - `compressed`: The compressed content.
+/// - `index`: The start of the text.
+///
+/// ### Returns `(String, usize)`
+/// The text and the index behind the terminating zero.
+fn read_header_text(compressed: &[u8], index: usize) -> Result<(String, usize)> {
+    let length: usize = compressed
+        .get(index..)
+        .and_then(|bytes| bytes.iter().position(|byte| *byte == 0))
+        .ok_or("The gzip header is incomplete.")?;
+
+    let text: String = compressed[index..index + length]
+        .iter()
+        .map(|byte| *byte as char)
+        .collect();
+
+    Ok((text, index + length + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::GzBuilder;
+    use std::fs;
+
+    /// Loads the decompressed content of a save of the Hawks Outpost.
+    fn load_outpost_content(name: &str) -> Vec<u8> {
+        fs::read(format!(
+            "{}/Hawks_Outpost/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        ))
+        .unwrap()
+    }
+
+    /// Compresses a content into a gzip file with a file name, a comment and a modification time.
+    fn create_gzip_fixture(content: &[u8], level: u32) -> Vec<u8> {
+        let mut encoder = GzBuilder::new()
+            .filename("save_main_0.sav")
+            .comment("Hawks Outpost")
+            .mtime(1_700_000_000)
+            .operating_system(0)
+            .write(Vec::new(), Compression::new(level));
+        encoder.write_all(content).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn reproduces_gzip_fixtures_of_every_common_level() {
+        let content: Vec<u8> = load_outpost_content("Endgame/endgame.sav");
+
+        for level in [1, 6, 9] {
+            let compressed: Vec<u8> = create_gzip_fixture(&content, level);
+            let profile: GzipProfile = read_gzip_profile(&compressed, &content).unwrap();
+
+            assert!(profile.is_reproducible, "level {}", level);
+            assert_eq!(profile.level, level);
+            assert_eq!(profile.filename, "save_main_0.sav");
+            assert_eq!(profile.comment, "Hawks Outpost");
+            assert_eq!(profile.mtime, 1_700_000_000);
+            assert!(
+                compress_save_content(&content, Some(&profile)).unwrap() == compressed,
+                "level {}",
+                level
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_the_header_of_an_edited_save() {
+        let content: Vec<u8> = load_outpost_content("Ghost/ghost.sav");
+        let compressed: Vec<u8> = create_gzip_fixture(&content, 6);
+        let profile: GzipProfile = read_gzip_profile(&compressed, &content).unwrap();
+
+        let mut edited: Vec<u8> = content.clone();
+        edited[0x40] ^= 0xff;
+        let edited_compressed: Vec<u8> = compress_save_content(&edited, Some(&profile)).unwrap();
+
+        assert!(edited_compressed[..profile.header.len()] == compressed[..profile.header.len()]);
+        assert!(decompress_save_content(&edited_compressed).unwrap() == edited);
+    }
+}
@@ -0,0 +1,159 @@
+//! Converts saves between the PC format (gzip compressed) and the console format (raw content).
+//!
+//! Both formats contain the same save content, the PC format only wraps it in a gzip member. A conversion never
+//! parses the save, it only (de)compresses the content after checking that it is a Dying Light 2 save. The written
+//! file is read back and its content is compared to the content of the source before the conversion is reported.
+//! The fingerprint of a save lists its format, the gzip header (PC saves), the header of the save content and the
+//! game version.
+
+use crate::save_logic::file_analyser::{
+    decompress_save_content, get_content_hash, get_contents_from_file, get_game_version,
+    is_compressed_content,
+};
+use crate::save_logic::save_compression::{compress_save_content, read_gzip_profile};
+use crate::save_logic::save_writer::{is_same_file, write_file_atomically};
+use crate::save_logic::struct_data::{GzipProfile, SaveConversionReport, SaveFingerprint};
+use std::error::Error;
+use std::path::Path;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the names of the save formats.
+pub static PC_FORMAT: &str = "pc";
+pub static CONSOLE_FORMAT: &str = "console";
+
+// Defines the first bytes of the content of every save.
+static SAVE_MAGIC: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+
+// Defines the amount of bytes of the save header that is shown in the fingerprint.
+const SAVE_HEADER_SIZE: usize = 16;
+
+// Defines the game version of content that does not contain a version.
+static UNKNOWN_VERSION: &str = "Unknown";
+
+/// Represents a method for creating the fingerprint of a save on disk.
+///
+/// ### Parameter
+/// - `file_path`: The path of the save.
+///
+/// ### Returns `SaveFingerprint`
+/// The format, the headers and the game version of the save.
+pub fn fetch_save_fingerprint(file_path: &str) -> Result<SaveFingerprint> {
+    let file_content: Vec<u8> = get_contents_from_file(file_path)
+        .map_err(|e| format!("Error reading the save [{}]: {}", file_path, e))?;
+
+    create_save_fingerprint(file_path, &file_content)
+}
+
+/// Represents a method for converting a save into the PC or the console format.
+///
+/// ### Parameter
+/// - `file_path`: The path of the converted save.
+/// - `output_path`: The path of the written save.
+/// - `format`: The target format (`pc` or `console`).
+///
+/// ### Returns `SaveConversionReport`
+/// The fingerprints of the source and the written save.
+pub fn convert_save_file(
+    file_path: &str,
+    output_path: &str,
+    format: &str,
+) -> Result<SaveConversionReport> {
+    let is_compressed: bool = match format {
+        f if f == PC_FORMAT => true,
+        f if f == CONSOLE_FORMAT => false,
+        _ => {
+            return Err(format!(
+                "The format [{}] is unknown, expected [{}] or [{}].",
+                format, PC_FORMAT, CONSOLE_FORMAT
+            )
+            .into())
+        }
+    };
+
+    if is_same_file(file_path, output_path) {
+        return Err("The converted save cannot overwrite the original save.".into());
+    }
+
+    let file_content: Vec<u8> = get_contents_from_file(file_path)
+        .map_err(|e| format!("Error reading the save [{}]: {}", file_path, e))?;
+    let source: SaveFingerprint = create_save_fingerprint(file_path, &file_content)?;
+
+    if source.is_compressed == is_compressed {
+        return Err(format!("The save [{}] is already a {} save.", file_path, format).into());
+    }
+
+    let output_content: Vec<u8> = match is_compressed {
+        true => compress_save_content(&file_content, None)?,
+        false => decompress_save_content(&file_content)?,
+    };
+
+    write_file_atomically(Path::new(output_path), &output_content)?;
+
+    // The written save is read again, so the report describes the file on disk.
+    let target: SaveFingerprint = fetch_save_fingerprint(output_path)?;
+
+    if target.content_hash != source.content_hash {
+        return Err(format!(
+            "The content of the converted save [{}] differs from the content of the original save.",
+            output_path
+        )
+        .into());
+    }
+
+    Ok(SaveConversionReport::new(
+        source,
+        target,
+        output_path.to_string(),
+    ))
+}
+
+/// Represents a method for creating the fingerprint of the content of a save.
+///
+/// ### Parameter
+/// - `file_path`: The path of the save.
+/// - `file_content`: The content of the save (as stored on disk).
+///
+/// ### Returns `SaveFingerprint`
+/// The format, the headers and the game version of the save.
+fn create_save_fingerprint(file_path: &str, file_content: &[u8]) -> Result<SaveFingerprint> {
+    let is_compressed: bool = is_compressed_content(file_content);
+    let content: Vec<u8> = match is_compressed {
+        true => decompress_save_content(file_content)?,
+        false => file_content.to_vec(),
+    };
+
+    let game_version: String = get_game_version(&content);
+
+    if !content.starts_with(&SAVE_MAGIC) || game_version == UNKNOWN_VERSION {
+        return Err(format!("The file [{}] is not a Dying Light 2 save.", file_path).into());
+    }
+
+    let gzip_profile: Option<GzipProfile> = match is_compressed {
+        true => Some(read_gzip_profile(file_content, &content)?),
+        false => None,
+    };
+
+    let save_header: String = content
+        .iter()
+        .take(SAVE_HEADER_SIZE)
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    Ok(SaveFingerprint::new(
+        file_path.to_string(),
+        match is_compressed {
+            true => PC_FORMAT.to_string(),
+            false => CONSOLE_FORMAT.to_string(),
+        },
+        is_compressed,
+        file_content.len(),
+        content.len(),
+        get_content_hash(&content),
+        save_header,
+        game_version,
+        gzip_profile,
+    ))
+}
@@ -0,0 +1,228 @@
+//! Compares the save of the editor session to the save on disk.
+//!
+//! When the game rewrites the open save, the offsets of the entries change, therefore the entries are compared by
+//! their content and not by their index:
+//! - inventory items are grouped by tab and ID, items that only exist on one side are listed
+//! - skills are compared by tree and name
+//! - unlockables are compared by name
+//!
+//! The report also tells whether the session contains edits that were not written yet, those are lost on a reload.
+
+use crate::save_logic::file_analyser::get_content_hash;
+use crate::save_logic::save_exporter::create_save_export;
+use crate::save_logic::struct_data::{
+    ExportedItem, SaveDiffEntry, SaveDiffReport, SaveExport, SaveFile,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+// Defines the sections of the differences.
+static VERSION_SECTION: &str = "game_version";
+static INVENTORY_SECTION: &str = "inventory";
+static SKILLS_SECTION: &str = "skills";
+static UNLOCKABLES_SECTION: &str = "unlockables";
+
+// Defines the fields of the differences.
+static ITEM_FIELD: &str = "item";
+static POINTS_FIELD: &str = "points";
+static UNLOCKED_FIELD: &str = "unlocked";
+
+/// Represents a method for comparing the save of the session to the save on disk.
+///
+/// ### Parameter
+/// - `session_save`: The save of the editor session (with `file_hash` and `gzip_profile` of the loaded file).
+/// - `disk_save`: The save that is currently on disk.
+///
+/// ### Returns `SaveDiffReport`
+/// The differences and whether the session contains edits that were not written yet.
+pub fn diff_saves(session_save: &SaveFile, disk_save: &SaveFile) -> SaveDiffReport {
+    let session_export: SaveExport = create_save_export(session_save);
+    let disk_export: SaveExport = create_save_export(disk_save);
+    let mut entries: Vec<SaveDiffEntry> = Vec::new();
+
+    if session_export.game_version != disk_export.game_version {
+        entries.push(SaveDiffEntry::new(
+            VERSION_SECTION.to_string(),
+            String::new(),
+            VERSION_SECTION.to_string(),
+            session_export.game_version.clone(),
+            disk_export.game_version.clone(),
+        ));
+    }
+
+    diff_inventory(&session_export, &disk_export, &mut entries);
+    diff_skills(&session_export, &disk_export, &mut entries);
+    diff_unlockables(&session_export, &disk_export, &mut entries);
+
+    SaveDiffReport::new(
+        disk_save.path.clone(),
+        has_pending_edits(session_save),
+        disk_save.file_hash.clone(),
+        entries,
+    )
+}
+
+/// Represents a method for checking whether the session contains edits that were not written yet.
+///
+/// ### Parameter
+/// - `save_file`: The save of the editor session.
+///
+/// ### Returns `bool`
+/// Indicates whether the content differs from the loaded file (`true` if this cannot be checked).
+pub fn has_pending_edits(save_file: &SaveFile) -> bool {
+    let content_hash: String = get_content_hash(&save_file.file_content);
+
+    match (save_file.is_compressed, save_file.gzip_profile.as_ref()) {
+        (true, Some(profile)) => profile.content_hash != content_hash,
+        (true, None) => true,
+        (false, _) => save_file.file_hash != content_hash,
+    }
+}
+
+/// Represents a method for comparing the inventory items (grouped by tab and ID).
+///
+/// ### Parameter
+/// - `session_export`: The export of the session save.
+/// - `disk_export`: The export of the save on disk.
+/// - `entries`: The found differences.
+fn diff_inventory(
+    session_export: &SaveExport,
+    disk_export: &SaveExport,
+    entries: &mut Vec<SaveDiffEntry>,
+) {
+    let session_items: BTreeMap<(String, String), Vec<String>> =
+        group_items(&session_export.inventory);
+    let disk_items: BTreeMap<(String, String), Vec<String>> = group_items(&disk_export.inventory);
+    let keys: BTreeSet<&(String, String)> = session_items.keys().chain(disk_items.keys()).collect();
+
+    for key in keys {
+        let mut session_only: Vec<String> = session_items.get(key).cloned().unwrap_or_default();
+        let mut disk_only: Vec<String> = Vec::new();
+
+        // Items with the same values on both sides are no difference.
+        for description in disk_items.get(key).cloned().unwrap_or_default() {
+            match session_only.iter().position(|d| *d == description) {
+                Some(position) => {
+                    session_only.remove(position);
+                }
+                None => disk_only.push(description),
+            }
+        }
+
+        for index in 0..session_only.len().max(disk_only.len()) {
+            entries.push(SaveDiffEntry::new(
+                INVENTORY_SECTION.to_string(),
+                format!("{} ({})", key.1, key.0),
+                ITEM_FIELD.to_string(),
+                session_only.get(index).cloned().unwrap_or_default(),
+                disk_only.get(index).cloned().unwrap_or_default(),
+            ));
+        }
+    }
+}
+
+/// Represents a method for comparing the points of the skills.
+///
+/// ### Parameter
+/// - `session_export`: The export of the session save.
+/// - `disk_export`: The export of the save on disk.
+/// - `entries`: The found differences.
+fn diff_skills(
+    session_export: &SaveExport,
+    disk_export: &SaveExport,
+    entries: &mut Vec<SaveDiffEntry>,
+) {
+    let session_skills: BTreeMap<(String, String), u16> = session_export
+        .skills
+        .iter()
+        .map(|skill| ((skill.tree.clone(), skill.name.clone()), skill.points))
+        .collect();
+    let disk_skills: BTreeMap<(String, String), u16> = disk_export
+        .skills
+        .iter()
+        .map(|skill| ((skill.tree.clone(), skill.name.clone()), skill.points))
+        .collect();
+    let keys: BTreeSet<&(String, String)> =
+        session_skills.keys().chain(disk_skills.keys()).collect();
+
+    for key in keys {
+        let session_points: Option<&u16> = session_skills.get(key);
+        let disk_points: Option<&u16> = disk_skills.get(key);
+
+        if session_points != disk_points {
+            entries.push(SaveDiffEntry::new(
+                SKILLS_SECTION.to_string(),
+                format!("{} ({})", key.1, key.0),
+                POINTS_FIELD.to_string(),
+                session_points.map(|p| p.to_string()).unwrap_or_default(),
+                disk_points.map(|p| p.to_string()).unwrap_or_default(),
+            ));
+        }
+    }
+}
+
+/// Represents a method for comparing the unlockables.
+///
+/// ### Parameter
+/// - `session_export`: The export of the session save.
+/// - `disk_export`: The export of the save on disk.
+/// - `entries`: The found differences.
+fn diff_unlockables(
+    session_export: &SaveExport,
+    disk_export: &SaveExport,
+    entries: &mut Vec<SaveDiffEntry>,
+) {
+    let session_unlockables: BTreeSet<&String> =
+        session_export.unlockables.iter().map(|u| &u.name).collect();
+    let disk_unlockables: BTreeSet<&String> =
+        disk_export.unlockables.iter().map(|u| &u.name).collect();
+
+    for name in session_unlockables.symmetric_difference(&disk_unlockables) {
+        entries.push(SaveDiffEntry::new(
+            UNLOCKABLES_SECTION.to_string(),
+            name.to_string(),
+            UNLOCKED_FIELD.to_string(),
+            session_unlockables.contains(name).to_string(),
+            disk_unlockables.contains(name).to_string(),
+        ));
+    }
+}
+
+/// Represents a method for grouping the inventory items by tab and ID.
+///
+/// ### Parameter
+/// - `items`: The exported inventory items.
+///
+/// ### Returns `BTreeMap<(String, String), Vec<String>>`
+/// The description of every item for each tab and ID.
+fn group_items(items: &[ExportedItem]) -> BTreeMap<(String, String), Vec<String>> {
+    let mut grouped: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+
+    for item in items.iter() {
+        grouped
+            .entry((item.tab.clone(), item.id.clone()))
+            .or_default()
+            .push(describe_item(item));
+    }
+
+    grouped
+}
+
+/// Represents a method for describing the values of an inventory item.
+///
+/// ### Parameter
+/// - `item`: The exported inventory item.
+///
+/// ### Returns `String`
+/// The level, seed, amount, durability and mods of the item.
+fn describe_item(item: &ExportedItem) -> String {
+    let mut description: String = format!(
+        "level {}, seed {}, amount {}, durability {}",
+        item.level, item.seed, item.amount, item.durability
+    );
+
+    if !item.mods.is_empty() {
+        description.push_str(&format!(", mods [{}]", item.mods.join(", ")));
+    }
+
+    description
+}
@@ -0,0 +1,346 @@
+//! Finds the save slots of Dying Light 2 on Linux.
+//!
+//! The following directories are searched:
+//! - Steam Cloud saves: `<steam>/userdata/<user id>/534380/remote/out/save`
+//! - Proton prefixes: `<library>/steamapps/compatdata/534380/pfx/drive_c/users/steamuser/**/out/save`
+//! - Configured roots: every `.sav` file inside the directory (and its sub directories)
+//!
+//! The Steam installations are searched in the default locations (native, `$XDG_DATA_HOME`, `~/.steam` links and
+//! Flatpak), the libraries of each installation are read from its `libraryfolders.vdf`. The saves are not parsed,
+//! the metadata only contains the format, the size, the modification time and the game version of each slot.
+
+use crate::save_logic::file_analyser::{
+    decompress_save_content, get_contents_from_file, get_game_version, is_compressed_content,
+};
+use crate::save_logic::struct_data::SaveMetadata;
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use std::{env, fs};
+
+// Defines the Steam app ID of Dying Light 2.
+static APP_ID: &str = "534380";
+
+// Defines the Steam installations relative to the home directory.
+static STEAM_ROOTS: [&str; 4] = [
+    ".local/share/Steam",
+    ".steam/steam",
+    ".steam/root",
+    ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+];
+
+// Defines the files that list the libraries of a Steam installation.
+static LIBRARY_FILES: [&str; 2] = ["steamapps/libraryfolders.vdf", "config/libraryfolders.vdf"];
+
+// Defines the directory of the saves (`out/save`) inside the game directories.
+static SAVE_PARENT_DIRECTORY: &str = "out";
+static SAVE_DIRECTORY: &str = "save";
+static PREFIX_USER_DIRECTORY: &str = "pfx/drive_c/users/steamuser";
+
+// Defines the extension of the save files.
+static SAVE_EXTENSION: &str = "sav";
+
+// Defines the sources of the save slots.
+pub static STEAM_SOURCE: &str = "steam";
+pub static PROTON_SOURCE: &str = "proton";
+pub static CUSTOM_SOURCE: &str = "custom";
+
+// Defines the game version of files that cannot be read.
+static UNKNOWN_VERSION: &str = "Unknown";
+
+// Defines the maximum depth that is searched inside Proton prefixes and configured roots.
+const MAX_SEARCH_DEPTH: usize = 8;
+
+/// Represents a method for discovering the save slots of all Steam installations and configured roots.
+///
+/// ### Parameter
+/// - `save_roots`: The additional directories that are searched for saves.
+///
+/// ### Returns `Vec<SaveMetadata>`
+/// The save slots (the most recently changed first).
+pub fn discover_save_slots(save_roots: &[String]) -> Vec<SaveMetadata> {
+    let mut steam_roots: Vec<PathBuf> = match get_home_path() {
+        Some(home_path) => STEAM_ROOTS
+            .iter()
+            .map(|root| home_path.join(root))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    if let Some(data_path) = env::var_os("XDG_DATA_HOME").filter(|path| !path.is_empty()) {
+        steam_roots.push(PathBuf::from(data_path).join("Steam"));
+    }
+
+    find_save_slots(&steam_roots, save_roots)
+}
+
+/// Represents a method for finding the save slots of the given Steam installations and configured roots.
+///
+/// ### Parameter
+/// - `steam_roots`: The directories of the Steam installations.
+/// - `save_roots`: The additional directories that are searched for saves.
+///
+/// ### Returns `Vec<SaveMetadata>`
+/// The save slots (the most recently changed first).
+pub fn find_save_slots(steam_roots: &[PathBuf], save_roots: &[String]) -> Vec<SaveMetadata> {
+    let mut slots: Vec<SaveMetadata> = Vec::new();
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+
+    // The links of `~/.steam` point to the same installation, therefore every installation is searched once.
+    let mut steam_paths: Vec<PathBuf> = Vec::new();
+
+    for steam_root in steam_roots
+        .iter()
+        .filter_map(|root| fs::canonicalize(root).ok())
+    {
+        if steam_root.is_dir() && !steam_paths.contains(&steam_root) {
+            steam_paths.push(steam_root);
+        }
+    }
+
+    for steam_root in steam_paths.iter() {
+        for user_path in read_directories(&steam_root.join("userdata")) {
+            let user_id: String = get_file_name(&user_path);
+            let save_path: PathBuf = user_path
+                .join(APP_ID)
+                .join("remote")
+                .join(SAVE_PARENT_DIRECTORY)
+                .join(SAVE_DIRECTORY);
+
+            for file_path in read_save_files(&save_path) {
+                add_save_slot(&mut slots, &mut seen, &file_path, STEAM_SOURCE, &user_id);
+            }
+        }
+
+        for library_path in read_library_paths(steam_root) {
+            let prefix_path: PathBuf = library_path
+                .join("steamapps/compatdata")
+                .join(APP_ID)
+                .join(PREFIX_USER_DIRECTORY);
+            let mut file_paths: Vec<PathBuf> = Vec::new();
+            collect_save_files(&prefix_path, MAX_SEARCH_DEPTH, true, &mut file_paths);
+
+            for file_path in file_paths {
+                add_save_slot(&mut slots, &mut seen, &file_path, PROTON_SOURCE, "");
+            }
+        }
+    }
+
+    // Configured roots that do not exist (anymore) are skipped.
+    for save_root in save_roots.iter() {
+        let root_path: &Path = Path::new(save_root);
+
+        if !root_path.is_dir() {
+            continue;
+        }
+
+        let mut file_paths: Vec<PathBuf> = Vec::new();
+        collect_save_files(root_path, MAX_SEARCH_DEPTH, false, &mut file_paths);
+
+        for file_path in file_paths {
+            add_save_slot(&mut slots, &mut seen, &file_path, CUSTOM_SOURCE, "");
+        }
+    }
+
+    slots.sort_by(|a, b| b.modified_at.cmp(&a.modified_at).then(a.path.cmp(&b.path)));
+    slots
+}
+
+/// Represents a method for reading the metadata of a save slot and adding it to the list.
+/// Slots that were already found through another directory are skipped.
+///
+/// ### Parameter
+/// - `slots`: The found save slots.
+/// - `seen`: The absolute paths of the found save slots.
+/// - `file_path`: The path of the save.
+/// - `source`: The directory type the save was found in.
+/// - `steam_user_id`: The Steam user the save belongs to (empty if unknown).
+fn add_save_slot(
+    slots: &mut Vec<SaveMetadata>,
+    seen: &mut HashSet<PathBuf>,
+    file_path: &Path,
+    source: &str,
+    steam_user_id: &str,
+) {
+    let file_path: PathBuf = fs::canonicalize(file_path).unwrap_or(file_path.to_path_buf());
+
+    if !seen.insert(file_path.clone()) {
+        return;
+    }
+
+    let path: String = file_path.display().to_string();
+    let modified_at: u64 = fs::metadata(&file_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    // A slot that cannot be read is still listed, so it can be restored or replaced.
+    let file_content: Vec<u8> = get_contents_from_file(&path).unwrap_or_default();
+    let is_compressed: bool = is_compressed_content(&file_content);
+    let game_version: String = match is_compressed {
+        true => decompress_save_content(&file_content)
+            .map(|content| get_game_version(&content))
+            .unwrap_or(UNKNOWN_VERSION.to_string()),
+        false => get_game_version(&file_content),
+    };
+
+    slots.push(SaveMetadata::new(
+        path,
+        file_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        source.to_string(),
+        steam_user_id.to_string(),
+        is_compressed,
+        file_content.len(),
+        modified_at,
+        game_version,
+    ));
+}
+
+/// Represents a method for reading the library directories of a Steam installation.
+///
+/// ### Parameter
+/// - `steam_root`: The directory of the Steam installation.
+///
+/// ### Returns `Vec<PathBuf>`
+/// The library directories (including the installation itself).
+fn read_library_paths(steam_root: &Path) -> Vec<PathBuf> {
+    let mut library_paths: Vec<PathBuf> = vec![steam_root.to_path_buf()];
+    // Defines the regex for the path entries, e.g. `"path"		"/mnt/games/SteamLibrary"`.
+    let re: Regex = Regex::new(r#""path"\s+"((?:[^"\\]|\\.)*)""#).unwrap();
+
+    for library_file in LIBRARY_FILES.iter() {
+        let file_content: String = match fs::read_to_string(steam_root.join(library_file)) {
+            Ok(file_content) => file_content,
+            Err(_) => continue,
+        };
+
+        for captures in re.captures_iter(&file_content) {
+            let library_path: PathBuf = PathBuf::from(captures[1].replace("\\\\", "\\"));
+            let library_path: PathBuf = fs::canonicalize(&library_path).unwrap_or(library_path);
+
+            if library_path.is_dir() && !library_paths.contains(&library_path) {
+                library_paths.push(library_path);
+            }
+        }
+    }
+
+    library_paths
+}
+
+/// Represents a method for collecting the save files of a directory and its sub directories.
+///
+/// ### Parameter
+/// - `directory`: The searched directory.
+/// - `depth`: The amount of sub directory levels that are searched.
+/// - `is_game_directory`: Indicates whether only saves inside `out/save` directories are collected.
+/// - `file_paths`: The collected save files.
+fn collect_save_files(
+    directory: &Path,
+    depth: usize,
+    is_game_directory: bool,
+    file_paths: &mut Vec<PathBuf>,
+) {
+    if !is_game_directory || is_save_directory(directory) {
+        file_paths.extend(read_save_files(directory));
+    }
+
+    if depth == 0 {
+        return;
+    }
+
+    for sub_directory in read_directories(directory) {
+        collect_save_files(&sub_directory, depth - 1, is_game_directory, file_paths);
+    }
+}
+
+/// Represents a method for checking whether a directory is the save directory of the game (`out/save`).
+///
+/// ### Parameter
+/// - `directory`: The checked directory.
+///
+/// ### Returns `bool`
+/// Indicates whether the directory is a save directory.
+fn is_save_directory(directory: &Path) -> bool {
+    let parent: Option<String> = directory.parent().map(get_file_name);
+
+    get_file_name(directory).eq_ignore_ascii_case(SAVE_DIRECTORY)
+        && parent.is_some_and(|parent| parent.eq_ignore_ascii_case(SAVE_PARENT_DIRECTORY))
+}
+
+/// Represents a method for reading the save files of a directory (without sub directories).
+///
+/// ### Parameter
+/// - `directory`: The directory.
+///
+/// ### Returns `Vec<PathBuf>`
+/// The save files.
+fn read_save_files(directory: &Path) -> Vec<PathBuf> {
+    let mut file_paths: Vec<PathBuf> = fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.is_file()
+                        && path
+                            .extension()
+                            .is_some_and(|e| e.eq_ignore_ascii_case(SAVE_EXTENSION))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    file_paths.sort();
+    file_paths
+}
+
+/// Represents a method for reading the sub directories of a directory (symbolic links are not followed).
+///
+/// ### Parameter
+/// - `directory`: The directory.
+///
+/// ### Returns `Vec<PathBuf>`
+/// The sub directories.
+fn read_directories(directory: &Path) -> Vec<PathBuf> {
+    let mut directories: Vec<PathBuf> = fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    directories.sort();
+    directories
+}
+
+/// Represents a method for getting the name of a file or directory.
+///
+/// ### Parameter
+/// - `path`: The path.
+///
+/// ### Returns `String`
+/// The last component of the path.
+fn get_file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Represents a method for getting the home directory of the user.
+///
+/// ### Returns `Option<PathBuf>`
+/// The home directory (`None` if `HOME` is not set).
+fn get_home_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
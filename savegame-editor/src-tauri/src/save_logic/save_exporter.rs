@@ -0,0 +1,231 @@
+use crate::save_logic::file_analyser::clean_sgd_name;
+use crate::save_logic::struct_data::{
+    ExportReport, ExportedItem, ExportedSkill, ExportedUnlockable, SaveExport, SaveFile,
+};
+use std::path::{Path, PathBuf};
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the supported export formats.
+static FORMAT_CSV: &str = "csv";
+static FORMAT_JSON: &str = "json";
+static FORMAT_ALL: &str = "all";
+
+// Defines the separator of multiple mods inside a single CSV cell.
+static MOD_SEPARATOR: &str = "|";
+
+/// Represents a method for creating the structured export of a save.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+///
+/// ### Returns `SaveExport`
+/// Every inventory item, skill and unlockable of the save.
+pub fn create_save_export(save_file: &SaveFile) -> SaveExport {
+    let inventory: Vec<ExportedItem> = save_file
+        .items
+        .iter()
+        .flat_map(|row| {
+            row.inventory_items.iter().map(|item| {
+                ExportedItem::new(
+                    row.name.clone(),
+                    row.container.clone(),
+                    item,
+                    item.mod_data
+                        .iter()
+                        .map(|mod_item| clean_sgd_name(&mod_item.name))
+                        .collect(),
+                )
+            })
+        })
+        .collect();
+
+    let skills: Vec<ExportedSkill> = save_file
+        .skills
+        .base_skills
+        .iter()
+        .map(|skill| ("base", skill))
+        .chain(
+            save_file
+                .skills
+                .legend_skills
+                .iter()
+                .map(|skill| ("legend", skill)),
+        )
+        .map(|(tree, skill)| {
+            ExportedSkill::new(
+                tree.to_string(),
+                clean_sgd_name(&skill.name),
+                skill.index,
+                skill.points_value,
+            )
+        })
+        .collect();
+
+    let unlockables: Vec<ExportedUnlockable> = save_file
+        .unlockable_items
+        .iter()
+        .map(|unlockable| {
+            ExportedUnlockable::new(clean_sgd_name(&unlockable.name), unlockable.index)
+        })
+        .collect();
+
+    SaveExport::new(
+        save_file.path.clone(),
+        save_file.game_version.clone(),
+        inventory,
+        skills,
+        unlockables,
+    )
+}
+
+/// Represents a method for exporting the inventory, skills and unlockables of a save.
+/// The CSV export creates one file per section, the JSON export a single structured file.
+/// All files are prefixed with the name of the save.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+/// - `output_dir`: The directory the files are written to (created if missing).
+/// - `format`: The export format (`csv`, `json` or `all`).
+///
+/// ### Returns `ExportReport`
+/// The written files and the amount of exported entries.
+pub fn export_save_data(
+    save_file: &SaveFile,
+    output_dir: &str,
+    format: &str,
+) -> Result<ExportReport> {
+    let format: String = format.trim().to_lowercase();
+
+    if format != FORMAT_CSV && format != FORMAT_JSON && format != FORMAT_ALL {
+        return Err(format!(
+            "The export format [{}] is not supported (use csv, json or all).",
+            format
+        )
+        .into());
+    }
+
+    fs::create_dir_all(output_dir)?;
+
+    let export: SaveExport = create_save_export(save_file);
+    let save_name: String = Path::new(&save_file.path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or("save".to_string());
+    let mut files: Vec<String> = Vec::new();
+
+    if format == FORMAT_CSV || format == FORMAT_ALL {
+        let inventory_path: PathBuf =
+            Path::new(output_dir).join(format!("{}_inventory.csv", save_name));
+        write_inventory_csv(&inventory_path, &export.inventory)?;
+        files.push(inventory_path.display().to_string());
+
+        let skills_path: PathBuf = Path::new(output_dir).join(format!("{}_skills.csv", save_name));
+        write_skills_csv(&skills_path, &export.skills)?;
+        files.push(skills_path.display().to_string());
+
+        let unlockables_path: PathBuf =
+            Path::new(output_dir).join(format!("{}_unlockables.csv", save_name));
+        write_unlockables_csv(&unlockables_path, &export.unlockables)?;
+        files.push(unlockables_path.display().to_string());
+    }
+
+    if format == FORMAT_JSON || format == FORMAT_ALL {
+        let json_path: PathBuf = Path::new(output_dir).join(format!("{}.json", save_name));
+        fs::write(&json_path, serde_json::to_string_pretty(&export)?)?;
+        files.push(json_path.display().to_string());
+    }
+
+    Ok(ExportReport::new(
+        output_dir.to_string(),
+        files,
+        export.inventory.len(),
+        export.skills.len(),
+        export.unlockables.len(),
+    ))
+}
+
+/// Represents a method for writing the inventory items to a CSV file.
+///
+/// ### Parameter
+/// - `path`: The path of the CSV file.
+/// - `items`: The exported inventory items.
+fn write_inventory_csv(path: &Path, items: &[ExportedItem]) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path)?;
+    writer.write_record([
+        "tab",
+        "container",
+        "id",
+        "display_name",
+        "category",
+        "index",
+        "chunk_index",
+        "level",
+        "seed",
+        "amount",
+        "durability",
+        "mods",
+    ])?;
+
+    for item in items.iter() {
+        writer.write_record([
+            item.tab.clone(),
+            item.container.clone(),
+            item.id.clone(),
+            item.display_name.clone(),
+            item.category.clone(),
+            item.index.to_string(),
+            item.chunk_index.to_string(),
+            item.level.to_string(),
+            item.seed.to_string(),
+            item.amount.to_string(),
+            item.durability.clone(),
+            item.mods.join(MOD_SEPARATOR),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Represents a method for writing the skills to a CSV file.
+///
+/// ### Parameter
+/// - `path`: The path of the CSV file.
+/// - `skills`: The exported skills.
+fn write_skills_csv(path: &Path, skills: &[ExportedSkill]) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path)?;
+    writer.write_record(["tree", "name", "index", "points"])?;
+
+    for skill in skills.iter() {
+        writer.write_record([
+            skill.tree.clone(),
+            skill.name.clone(),
+            skill.index.to_string(),
+            skill.points.to_string(),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Represents a method for writing the unlockables to a CSV file.
+///
+/// ### Parameter
+/// - `path`: The path of the CSV file.
+/// - `unlockables`: The exported unlockables.
+fn write_unlockables_csv(path: &Path, unlockables: &[ExportedUnlockable]) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path)?;
+    writer.write_record(["name", "index"])?;
+
+    for unlockable in unlockables.iter() {
+        writer.write_record([unlockable.name.clone(), unlockable.index.to_string()])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
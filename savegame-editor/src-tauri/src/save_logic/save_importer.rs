@@ -0,0 +1,678 @@
+//! Rebuilds a save from its JSON export (created by `save_exporter::create_save_export`).
+//!
+//! The JSON export is the editable representation of a save:
+//!
+//! ```json
+//! {
+//!     "path": "C:/.../save_main_0.sav",
+//!     "game_version": "1.16.0",
+//!     "inventory": [
+//!         {
+//!             "tab": "Weapons", "container": "...", "id": "wpn_axe_fire_common", "display_name": "...", "category": "...",
+//!             "index": 123456, "chunk_index": 234567, "level": 9216, "seed": 1, "amount": 1, "durability": "100.0",
+//!             "mods": ["wpn_mod_fire_tier1"]
+//!         }
+//!     ],
+//!     "skills": [{ "tree": "base", "name": "height_jump_skill", "index": 345678, "points": 1 }],
+//!     "unlockables": [{ "name": "Craftplan_Medkit_Small", "index": 456789 }]
+//! }
+//! ```
+//!
+//! Every entry is identified by its `index` (the offset inside the save), so the JSON can only be applied to the save
+//! it was exported from. The editable fields are:
+//! - inventory items: `id`, `level`, `seed`, `amount`, `durability` and `mods`. Every ID has to fit into the current ID
+//!   and the amount of mods cannot change. Items that are missing in the JSON are removed.
+//! - skills: `points`.
+//!
+//! The remaining fields (`tab`, `container`, `chunk_index`, the tree and name of a skill, the unlockables and the
+//! `game_version`) have to match the save, `path`, `display_name` and `category` are ignored.
+//! Only changed values are written, therefore an unchanged JSON rebuilds a byte-identical save. An edited save is
+//! analysed again and rejected if it does not contain the values of the JSON (for example if the analyser cannot
+//! find a shortened ID anymore).
+
+use crate::logger::ConsoleLogger;
+use crate::save_logic::file_analyser::{
+    clean_sgd_name, edit_inventory_item_chunk, edit_inventory_mod, edit_skill, find_byte_changes,
+    load_save_file, remove_inventory_item,
+};
+use crate::save_logic::item_rules::ItemRuleEngine;
+use crate::save_logic::save_exporter::create_save_export;
+use crate::save_logic::struct_data::{
+    ExportedItem, InventoryItem, InventoryItemRow, InventoryTabMapping, ItemCatalog, SaveExport,
+    SaveFile, SaveImportChange, SaveImportReport, SkillItem,
+};
+use std::collections::{HashMap, HashSet};
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the sections of the changes.
+static INVENTORY_SECTION: &str = "inventory";
+static SKILLS_SECTION: &str = "skills";
+
+// Defines the skill trees (as written by the export).
+static BASE_SKILL_TREE: &str = "base";
+static LEGEND_SKILL_TREE: &str = "legend";
+
+// Defines the field of a removed inventory item.
+static REMOVED_FIELD: &str = "removed";
+
+// Defines the separator of multiple mods inside a change.
+static MOD_SEPARATOR: &str = "|";
+
+/// Represents a method for reading the JSON export of a save.
+///
+/// ### Parameter
+/// - `json_path`: The path of the JSON file.
+///
+/// ### Returns `SaveExport`
+/// The (possibly edited) export.
+pub fn fetch_save_export(json_path: &str) -> Result<SaveExport> {
+    let content: String = fs::read_to_string(json_path)
+        .map_err(|e| format!("Error reading the save export [{}]: {}", json_path, e))?;
+
+    let export: SaveExport = serde_json::from_str(&content)
+        .map_err(|e| format!("Error parsing the save export: {}", e))?;
+
+    Ok(export)
+}
+
+/// Represents a method for previewing the changes of an edited JSON export.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save the JSON was exported from.
+/// - `export`: The edited export.
+///
+/// ### Returns `SaveImportReport`
+/// The changed fields, the resulting byte changes and every invalid entry.
+pub fn preview_save_import(save_file: &SaveFile, export: &SaveExport) -> SaveImportReport {
+    let (report, _) = create_save_import(save_file, export);
+
+    report
+}
+
+/// Represents a method for applying an edited JSON export to the content of the save it was exported from.
+/// Nothing is applied if any entry of the export is invalid.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save the JSON was exported from.
+/// - `export`: The edited export.
+///
+/// ### Returns `(SaveImportReport, Vec<u8>)`
+/// The changes of the import and the new content of the save.
+pub fn build_save_from_export(
+    save_file: &SaveFile,
+    export: &SaveExport,
+) -> Result<(SaveImportReport, Vec<u8>)> {
+    let (report, content) = create_save_import(save_file, export);
+
+    if let Some(issue) = report.issues.first() {
+        return Err(format!(
+            "The save export contains {} invalid entries ({}).",
+            report.issues.len(),
+            issue
+        )
+        .into());
+    }
+
+    Ok((report, content))
+}
+
+/// Represents a method for applying an edited JSON export and analysing the rebuilt save again.
+/// The import is rejected if the rebuilt save does not contain the values of the export.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save the JSON was exported from.
+/// - `export`: The edited export.
+/// - `catalog`: The item catalog with a record for every known ID.
+/// - `tab_mapping`: The mapping from save containers and ID categories to inventory tabs.
+/// - `item_rules`: The rules for classifying matches as item, mod or transmog.
+/// - `logger`: The console logger that logs every event.
+///
+/// ### Returns `(SaveImportReport, SaveFile)`
+/// The changes of the import and the rebuilt save.
+pub fn rebuild_save_from_export(
+    save_file: &SaveFile,
+    export: &SaveExport,
+    catalog: ItemCatalog,
+    tab_mapping: InventoryTabMapping,
+    item_rules: ItemRuleEngine,
+    logger: &mut ConsoleLogger,
+) -> Result<(SaveImportReport, SaveFile)> {
+    let (report, content) = build_save_from_export(save_file, export)?;
+
+    // An unchanged export results in the original save.
+    if report.byte_changes.is_empty() {
+        return Ok((report, save_file.clone()));
+    }
+
+    let mut new_save_file: SaveFile = load_save_file(
+        &save_file.path,
+        content,
+        catalog,
+        tab_mapping,
+        item_rules,
+        logger,
+        false,
+        save_file.is_compressed,
+    )
+    .map_err(|e| format!("The save could not be analysed after the import: {}", e))?;
+    new_save_file.file_hash = save_file.file_hash.clone();
+    new_save_file.gzip_profile = save_file.gzip_profile.clone();
+
+    verify_save_import(&new_save_file, export)?;
+
+    Ok((report, new_save_file))
+}
+
+/// Represents a method for validating the entries of an export and applying them to a copy of the save content.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save the JSON was exported from.
+/// - `export`: The edited export.
+///
+/// ### Returns `(SaveImportReport, Vec<u8>)`
+/// The report of the import and the edited content.
+fn create_save_import(save_file: &SaveFile, export: &SaveExport) -> (SaveImportReport, Vec<u8>) {
+    let mut report: SaveImportReport = SaveImportReport::new(save_file.path.clone());
+    let mut content: Vec<u8> = save_file.file_content.clone();
+
+    if export.game_version != save_file.game_version {
+        report.issues.push(format!(
+            "The export was created for the game version [{}], but the save has the version [{}].",
+            export.game_version, save_file.game_version
+        ));
+    }
+
+    // Maps the offset of every inventory item to the item and its tab.
+    let items: HashMap<usize, (&InventoryItemRow, &InventoryItem)> = save_file
+        .items
+        .iter()
+        .flat_map(|row| {
+            row.inventory_items
+                .iter()
+                .map(move |item| (item.index, (row, item)))
+        })
+        .collect();
+    let mut seen_items: HashSet<usize> = HashSet::new();
+
+    for exported in export.inventory.iter() {
+        let (row, item) = match items.get(&exported.index) {
+            Some(entry) => *entry,
+            None => {
+                report.issues.push(format!(
+                    "There is no inventory item [{}] at offset [{}].",
+                    exported.id, exported.index
+                ));
+                continue;
+            }
+        };
+
+        if !seen_items.insert(item.index) {
+            report.issues.push(format!(
+                "The item at offset [{}] is listed twice.",
+                item.index
+            ));
+            continue;
+        }
+
+        if exported.tab != row.name
+            || exported.container != row.container
+            || exported.chunk_index != item.chunk_data.index
+        {
+            report.issues.push(format!(
+                "The tab, container or chunk offset of the item at offset [{}] was changed (items cannot be moved).",
+                item.index
+            ));
+            continue;
+        }
+
+        match import_inventory_item(item, exported, content) {
+            Ok((changes, new_content)) => {
+                content = new_content;
+                for (field, old_value, new_value) in changes {
+                    report.changes.push(SaveImportChange::new(
+                        INVENTORY_SECTION.to_string(),
+                        item.index,
+                        item.name.clone(),
+                        field.to_string(),
+                        old_value,
+                        new_value,
+                    ));
+                }
+            }
+            Err((message, old_content)) => {
+                content = old_content;
+                report.issues.push(message);
+            }
+        }
+    }
+
+    // Items that are missing in the export are removed.
+    let exported_items: HashSet<usize> = export.inventory.iter().map(|item| item.index).collect();
+    for row in save_file.items.iter() {
+        for item in row
+            .inventory_items
+            .iter()
+            .filter(|item| !exported_items.contains(&item.index))
+        {
+            content = remove_inventory_item(
+                item.index,
+                item.index + item.size,
+                item.chunk_data.index,
+                content,
+            );
+            report.changes.push(SaveImportChange::new(
+                INVENTORY_SECTION.to_string(),
+                item.index,
+                item.name.clone(),
+                REMOVED_FIELD.to_string(),
+                item.name.clone(),
+                String::new(),
+            ));
+        }
+    }
+
+    // Lists every skill with its tree and maps the offset of every skill to it.
+    let skill_list: Vec<(&str, &SkillItem)> = save_file
+        .skills
+        .base_skills
+        .iter()
+        .map(|skill| (BASE_SKILL_TREE, skill))
+        .chain(
+            save_file
+                .skills
+                .legend_skills
+                .iter()
+                .map(|skill| (LEGEND_SKILL_TREE, skill)),
+        )
+        .collect();
+    let skills: HashMap<usize, (&str, &SkillItem)> = skill_list
+        .iter()
+        .map(|(tree, skill)| (skill.index, (*tree, *skill)))
+        .collect();
+    let mut seen_skills: HashSet<usize> = HashSet::new();
+
+    for exported in export.skills.iter() {
+        let (tree, skill) = match skills.get(&exported.index) {
+            Some(entry) => *entry,
+            None => {
+                report.issues.push(format!(
+                    "There is no skill [{}] at offset [{}].",
+                    exported.name, exported.index
+                ));
+                continue;
+            }
+        };
+        let name: String = clean_sgd_name(&skill.name);
+
+        if !seen_skills.insert(skill.index) {
+            report.issues.push(format!(
+                "The skill at offset [{}] is listed twice.",
+                skill.index
+            ));
+            continue;
+        }
+
+        if exported.tree != tree || exported.name != name {
+            report.issues.push(format!(
+                "The skill [{} {}] does not match the skill [{} {}] at offset [{}].",
+                exported.tree, exported.name, tree, name, skill.index
+            ));
+            continue;
+        }
+
+        if exported.points != skill.points_value {
+            content = edit_skill(skill.size, skill.index, exported.points, content);
+            report.changes.push(SaveImportChange::new(
+                SKILLS_SECTION.to_string(),
+                skill.index,
+                name,
+                "points".to_string(),
+                skill.points_value.to_string(),
+                exported.points.to_string(),
+            ));
+        }
+    }
+
+    for (tree, skill) in skill_list
+        .iter()
+        .filter(|(_, skill)| !seen_skills.contains(&skill.index))
+    {
+        report.issues.push(format!(
+            "The skill [{} {}] at offset [{}] is missing (skills cannot be removed).",
+            tree,
+            clean_sgd_name(&skill.name),
+            skill.index
+        ));
+    }
+
+    // The unlockables are read-only, they can only be added by the progression merge.
+    let unlockables: HashSet<(String, usize)> = save_file
+        .unlockable_items
+        .iter()
+        .map(|unlockable| (clean_sgd_name(&unlockable.name), unlockable.index))
+        .collect();
+    let exported_unlockables: HashSet<(String, usize)> = export
+        .unlockables
+        .iter()
+        .map(|unlockable| (unlockable.name.clone(), unlockable.index))
+        .collect();
+
+    if unlockables != exported_unlockables {
+        report.issues.push(
+            "The unlockables do not match the save (unlockables cannot be edited by the import)."
+                .to_string(),
+        );
+    }
+
+    report.byte_changes = find_byte_changes(&save_file.file_content, &content);
+
+    (report, content)
+}
+
+/// Represents a method for validating and applying the exported values of a single inventory item.
+///
+/// ### Parameter
+/// - `item`: The inventory item of the save.
+/// - `exported`: The exported values of the item.
+/// - `content`: The content of the save.
+///
+/// ### Returns `(Vec<(&str, String, String)>, Vec<u8>)`
+/// The changed fields (field, old value, new value) and the new content,
+/// the error message and the unchanged content if a value is invalid.
+#[allow(clippy::type_complexity)]
+fn import_inventory_item(
+    item: &InventoryItem,
+    exported: &ExportedItem,
+    mut content: Vec<u8>,
+) -> std::result::Result<(Vec<(&'static str, String, String)>, Vec<u8>), (String, Vec<u8>)> {
+    let chunk = &item.chunk_data;
+    let current_durability: f32 =
+        f32::from_le_bytes(chunk.durability.clone().try_into().unwrap_or_default());
+    let current_mods: Vec<String> = item
+        .mod_data
+        .iter()
+        .map(|mod_item| clean_sgd_name(&mod_item.name))
+        .collect();
+    let mut messages: Vec<String> = Vec::new();
+
+    // The IDs are written in front of the existing "SGDs", therefore they have to fit into the old IDs.
+    if !is_valid_id(&exported.id) {
+        messages.push(format!("The ID [{}] is not a valid ID.", exported.id));
+    } else if exported.id.len() > item.size.saturating_sub(4) {
+        messages.push(format!(
+            "The ID [{}] is longer than the ID [{}] at offset [{}].",
+            exported.id, item.name, item.index
+        ));
+    }
+
+    let durability: Option<f32> = exported
+        .durability
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|value| value.is_finite());
+    if durability.is_none() {
+        messages.push(format!(
+            "The durability [{}] is not a valid number.",
+            exported.durability
+        ));
+    }
+
+    if exported.mods.len() != item.mod_data.len() {
+        messages.push(format!(
+            "The item at offset [{}] has [{}] mods, but the export lists [{}] mods.",
+            item.index,
+            item.mod_data.len(),
+            exported.mods.len()
+        ));
+    } else {
+        for (mod_name, mod_item) in exported.mods.iter().zip(item.mod_data.iter()) {
+            if !is_valid_id(mod_name) {
+                messages.push(format!("The mod [{}] is not a valid ID.", mod_name));
+            } else if mod_name.len() > mod_item.name.len().saturating_sub(4) {
+                messages.push(format!(
+                    "The mod [{}] is longer than the mod [{}] at offset [{}].",
+                    mod_name,
+                    clean_sgd_name(&mod_item.name),
+                    mod_item.index
+                ));
+            }
+        }
+    }
+
+    if !messages.is_empty() {
+        return Err((messages.join(" "), content));
+    }
+
+    // The export rounds the durability, so the rounded value counts as unchanged.
+    let mut new_durability: f32 = durability.unwrap_or(current_durability);
+    if format!("{:.1}", new_durability) == chunk.durability_value {
+        new_durability = current_durability;
+    }
+
+    let mut changes: Vec<(&'static str, String, String)> = vec![
+        ("id", item.name.clone(), exported.id.clone()),
+        (
+            "level",
+            chunk.level_value.to_string(),
+            exported.level.to_string(),
+        ),
+        (
+            "seed",
+            chunk.seed_value.to_string(),
+            exported.seed.to_string(),
+        ),
+        (
+            "amount",
+            chunk.amount_value.to_string(),
+            exported.amount.to_string(),
+        ),
+        (
+            "durability",
+            chunk.durability_value.clone(),
+            format!("{:.1}", new_durability),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, old_value, new_value)| old_value != new_value)
+    .collect();
+
+    if !changes.is_empty() {
+        content = edit_inventory_item_chunk(
+            item.index,
+            exported.id.clone(),
+            chunk.index,
+            item.size,
+            exported.level,
+            exported.seed,
+            exported.amount,
+            new_durability,
+            content,
+        );
+    }
+
+    if exported.mods != current_mods {
+        for ((mod_name, current_mod), mod_item) in exported
+            .mods
+            .iter()
+            .zip(current_mods.iter())
+            .zip(item.mod_data.iter())
+        {
+            if mod_name != current_mod {
+                content = edit_inventory_mod(
+                    mod_item.index,
+                    mod_item.name.len(),
+                    mod_name.clone(),
+                    content,
+                );
+            }
+        }
+
+        changes.push((
+            "mods",
+            current_mods.join(MOD_SEPARATOR),
+            exported.mods.join(MOD_SEPARATOR),
+        ));
+    }
+
+    Ok((changes, content))
+}
+
+/// Represents a method for verifying that a rebuilt save contains the values of the export.
+///
+/// ### Parameter
+/// - `save_file`: The rebuilt save.
+/// - `export`: The applied export.
+fn verify_save_import(save_file: &SaveFile, export: &SaveExport) -> Result<()> {
+    let rebuilt: SaveExport = create_save_export(save_file);
+
+    let items: HashMap<usize, &ExportedItem> = rebuilt
+        .inventory
+        .iter()
+        .map(|item| (item.index, item))
+        .collect();
+
+    for exported in export.inventory.iter() {
+        let durability: String = exported
+            .durability
+            .trim()
+            .parse::<f32>()
+            .map(|value| format!("{:.1}", value))
+            .unwrap_or_default();
+        let is_matching: bool = items.get(&exported.index).is_some_and(|item| {
+            item.id == exported.id
+                && item.level == exported.level
+                && item.seed == exported.seed
+                && item.amount == exported.amount
+                && item.durability == durability
+                && item.mods == exported.mods
+        });
+
+        if !is_matching {
+            return Err(format!(
+                "The item [{}] at offset [{}] does not match the export after the import.",
+                exported.id, exported.index
+            )
+            .into());
+        }
+    }
+
+    if rebuilt.inventory.len() != export.inventory.len() {
+        return Err(format!(
+            "The rebuilt save contains [{}] inventory items instead of [{}].",
+            rebuilt.inventory.len(),
+            export.inventory.len()
+        )
+        .into());
+    }
+
+    for exported in export.skills.iter() {
+        let is_matching: bool = rebuilt
+            .skills
+            .iter()
+            .any(|skill| skill.index == exported.index && skill.points == exported.points);
+
+        if !is_matching {
+            return Err(format!(
+                "The skill [{}] at offset [{}] does not match the export after the import.",
+                exported.name, exported.index
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Represents a method for checking whether a text can be written as an ID.
+///
+/// ### Parameter
+/// - `id_name`: The ID.
+///
+/// ### Returns `bool`
+/// True if the ID is not empty and only contains visible ASCII characters.
+fn is_valid_id(id_name: &str) -> bool {
+    !id_name.is_empty() && id_name.chars().all(|c| c.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save_logic::save_exporter::export_save_data;
+    use crate::save_logic::struct_data::ExportReport;
+    use crate::save_logic::test_support::{load_outpost_save, load_resources};
+    use std::path::PathBuf;
+
+    // Defines the saves of the Hawks Outpost that are exported and imported again.
+    static OUTPOST_SAVES: [&str; 3] = [
+        "Endgame/endgame.sav",
+        "Ghost/ghost.sav",
+        "Spooky/christmas.sav",
+    ];
+
+    /// Writes the JSON export of a save into a temporary directory (named after the label) and reads it again.
+    fn export_and_fetch(save_file: &SaveFile, label: &str) -> SaveExport {
+        let output_dir: PathBuf = std::env::temp_dir().join(format!(
+            "dl2_save_import_{}_{}",
+            std::process::id(),
+            label.replace('/', "_")
+        ));
+        let report: ExportReport =
+            export_save_data(save_file, &output_dir.display().to_string(), "json").unwrap();
+        let export: SaveExport = fetch_save_export(&report.files[0]).unwrap();
+
+        fs::remove_dir_all(&output_dir).unwrap();
+        export
+    }
+
+    #[test]
+    fn rebuilds_identical_outpost_saves_from_unchanged_exports() {
+        for name in OUTPOST_SAVES {
+            let save_file: SaveFile = load_outpost_save(name);
+            let export: SaveExport = export_and_fetch(&save_file, name);
+
+            let (report, content) = build_save_from_export(&save_file, &export).unwrap();
+
+            assert!(report.issues.is_empty(), "{}: {:?}", name, report.issues);
+            assert!(report.changes.is_empty(), "{}", name);
+            assert!(report.byte_changes.is_empty(), "{}", name);
+            assert!(content == save_file.file_content, "{}", name);
+        }
+    }
+
+    #[test]
+    fn rebuilds_the_original_save_after_reverting_an_edit() {
+        let save_file: SaveFile = load_outpost_save(OUTPOST_SAVES[0]);
+        let mut export: SaveExport = export_and_fetch(&save_file, "original");
+        let points: u16 = export.skills[0].points;
+        export.skills[0].points = points + 1;
+
+        let (catalog, tab_mapping, item_rules) = load_resources();
+        let (report, edited_save) = rebuild_save_from_export(
+            &save_file,
+            &export,
+            catalog,
+            tab_mapping,
+            item_rules,
+            &mut ConsoleLogger::new(),
+        )
+        .unwrap();
+
+        assert_eq!(report.changes.len(), 1);
+        assert!(edited_save.file_content != save_file.file_content);
+        assert_eq!(
+            create_save_export(&edited_save).skills[0].points,
+            points + 1
+        );
+
+        // Exporting the edited save and reverting the edit results in the original bytes.
+        let mut edited_export: SaveExport = export_and_fetch(&edited_save, "edited");
+        edited_export.skills[0].points = points;
+        let (_, content) = build_save_from_export(&edited_save, &edited_export).unwrap();
+
+        assert!(content == save_file.file_content);
+    }
+}
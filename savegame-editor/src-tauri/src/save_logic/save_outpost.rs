@@ -1,98 +1,156 @@
-use tauri::AppHandle;
+//! Lists the saves of the Hawks Outpost (the bundled community saves).
+//!
+//! Every directory of the outpost contains a `save.json` with the description of the save and the save itself (a
+//! `.sav` file). The index is built from the `save.json` files only, the saves are parsed once a user opens them.
+//! A directory that cannot be read is listed with its error, so one broken entry does not hide the other saves.
 
-use crate::load_save;
 use crate::save_logic::struct_data::OutpostSave;
-use std::fs::{DirEntry, ReadDir};
-use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{error::Error, fs};
 
-use super::struct_data::SaveFile;
-
 // Define global result definition for easier readability.
 type Result<T> = std::result::Result<T, Box<dyn Error>>;
 
-/// Represents a method for fetching all saves from the dedicated outpost directory.
+// Defines the file names inside the directory of an outpost save.
+static DESCRIPTION_FILE: &str = "save.json";
+static SAVE_EXTENSION: &str = "sav";
+
+/// Represents a method for fetching the index of all saves from the dedicated outpost directory.
+///
+/// ### Parameter
+/// - `outpost_path`: The path of the outpost directory.
 ///
 /// ### Returns `Vec<OutpostSave>`
-/// A list of all fetched outpost saves.
-pub fn fetch_outpost_saves(
-    app_handle: AppHandle,
-    outpost_path: &String,
-) -> Result<Vec<OutpostSave>> {
-    // Initialize the vector for the outpost saves.
-    let mut outpost_saves: Vec<OutpostSave> = Vec::new();
-    // Read the directory entries.
-    let entries: ReadDir = fs::read_dir(outpost_path).unwrap();
-
-    // Iterate over each entry in the outpost directory.
-    for save_dir in entries {
-        // Get the entry
-        let entry: DirEntry =
-            save_dir.map_err(|e: io::Error| format!("Error reading directory entry: {}", e))?;
-
-        // Check if the entry is a directory.
-        if entry.file_type().map_or(false, |t| t.is_dir()) {
-            // get file inside the directory.
-            let entries: ReadDir =
-                fs::read_dir(entry.path().to_str().ok_or("Invalid file path")?).unwrap();
-            // get the 2 needed files (the save.json and the file that ends with .sav).
-            let mut save_file: Option<PathBuf> = None;
-            let mut save_json: Option<PathBuf> = None;
-            // iterate over all entries in the directory.
-            for entry in entries {
-                let entry: DirEntry = entry
-                    .map_err(|e: io::Error| format!("Error reading directory entry: {}", e))?;
-                // check if the entry is a file.
-                if entry.file_type().map_or(false, |t| t.is_file()) {
-                    let path = entry.path();
-                    // check if the file is a save file or a save.json file.
-                    if path.extension().map_or(false, |e| e == "sav") {
-                        save_file = Some(path);
-                    } else if path.file_name().map_or(false, |f| f == "save.json") {
-                        save_json = Some(path);
-                    }
-                }
-            }
-
-            // check if both files are found
-            if save_file.is_none() || save_json.is_none() {
-                return Err("Missing save file or save.json".into());
-            }
-
-            // read the save.json file
-            let save_json = fs::read_to_string(save_json.unwrap())?;
-            // convert to json
-            let save_json: serde_json::Value = serde_json::from_str(&save_json)?;
-
-            // get the path from the save file and call the read_save_file function
-            let save_file: PathBuf = save_file.unwrap();
-            let save_file: std::result::Result<SaveFile, String> = load_save(
-                app_handle.clone(),
-                save_file.to_str().ok_or("Invalid file path")?,
-                false,
-                false,
-            );
-
-            // return the OutpostSave struct
-            match save_file {
-                Ok(save_file) => outpost_saves.push(OutpostSave::new(
-                    save_json["name"].as_str().unwrap_or("").to_string(),
-                    save_json["owner"].as_str().unwrap_or("").to_string(),
-                    save_json["description"].as_str().unwrap_or("").to_string(),
-                    save_json["features"]
-                        .as_array()
-                        .unwrap_or(&vec![])
-                        .iter()
-                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                        .collect::<Vec<String>>(),
-                    save_json["version"].as_str().unwrap_or("").to_string(),
-                    save_file,
-                )),
-                Err(e) => return Err(e.into()),
-            }
-        }
-    }
+/// A list of all outpost saves (entries that cannot be read contain an error).
+pub fn fetch_outpost_saves(outpost_path: &str) -> Result<Vec<OutpostSave>> {
+    let mut save_directories: Vec<PathBuf> = fs::read_dir(outpost_path)
+        .map_err(|e| {
+            format!(
+                "Error reading the outpost directory [{}]: {}",
+                outpost_path, e
+            )
+        })?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.path())
+        .collect();
+
+    save_directories.sort();
+
+    let outpost_saves: Vec<OutpostSave> = save_directories
+        .iter()
+        .map(|save_directory| {
+            read_outpost_save(save_directory).unwrap_or_else(|err| {
+                let directory: String = get_directory_name(save_directory);
+
+                OutpostSave::new(
+                    directory.clone(),
+                    directory,
+                    String::new(),
+                    String::new(),
+                    Vec::new(),
+                    String::new(),
+                    String::new(),
+                    err.to_string(),
+                )
+            })
+        })
+        .collect();
 
     Ok(outpost_saves)
 }
+
+/// Represents a method for checking that a save belongs to the outpost before it is opened.
+///
+/// ### Parameter
+/// - `outpost_path`: The path of the outpost directory.
+/// - `save_path`: The path of the outpost save (`OutpostSave::save_path`).
+///
+/// ### Returns `String`
+/// The absolute path of the save.
+pub fn get_outpost_save_path(outpost_path: &str, save_path: &str) -> Result<String> {
+    let outpost_path: PathBuf = fs::canonicalize(outpost_path).map_err(|e| {
+        format!(
+            "Error reading the outpost directory [{}]: {}",
+            outpost_path, e
+        )
+    })?;
+    let file_path: PathBuf = fs::canonicalize(save_path)
+        .map_err(|e| format!("Error reading the outpost save [{}]: {}", save_path, e))?;
+
+    if !file_path.starts_with(&outpost_path) || !file_path.is_file() {
+        return Err(format!("The file [{}] is not an outpost save.", save_path).into());
+    }
+
+    Ok(file_path.display().to_string())
+}
+
+/// Represents a method for reading the index entry of an outpost save.
+///
+/// ### Parameter
+/// - `save_directory`: The directory of the outpost save.
+///
+/// ### Returns `OutpostSave`
+/// The description and the path of the save.
+fn read_outpost_save(save_directory: &Path) -> Result<OutpostSave> {
+    let mut save_files: Vec<PathBuf> = fs::read_dir(save_directory)
+        .map_err(|e| format!("Error reading the directory: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case(SAVE_EXTENSION))
+        })
+        .collect();
+
+    save_files.sort();
+
+    let save_file: PathBuf = match save_files.len() {
+        0 => return Err("The directory does not contain a save file.".into()),
+        1 => save_files.remove(0),
+        _ => return Err("The directory contains more than one save file.".into()),
+    };
+
+    let save_json: String = fs::read_to_string(save_directory.join(DESCRIPTION_FILE))
+        .map_err(|e| format!("Error reading the {}: {}", DESCRIPTION_FILE, e))?;
+    let save_json: serde_json::Value = serde_json::from_str(&save_json)
+        .map_err(|e| format!("Error parsing the {}: {}", DESCRIPTION_FILE, e))?;
+
+    let directory: String = get_directory_name(save_directory);
+    // A save without a name is listed with the name of its directory.
+    let name: String = match save_json["name"].as_str() {
+        Some(name) if !name.trim().is_empty() => name.to_string(),
+        _ => directory.clone(),
+    };
+
+    Ok(OutpostSave::new(
+        directory,
+        name,
+        save_json["owner"].as_str().unwrap_or("").to_string(),
+        save_json["description"].as_str().unwrap_or("").to_string(),
+        save_json["features"]
+            .as_array()
+            .unwrap_or(&vec![])
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect::<Vec<String>>(),
+        save_json["version"].as_str().unwrap_or("").to_string(),
+        save_file.display().to_string(),
+        String::new(),
+    ))
+}
+
+/// Represents a method for getting the name of a directory.
+///
+/// ### Parameter
+/// - `directory`: The directory.
+///
+/// ### Returns `String`
+/// The last component of the path.
+fn get_directory_name(directory: &Path) -> String {
+    directory
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,347 @@
+//! Finds, validates and inserts the records of a save.
+//!
+//! The save is a stream of records, every record looks like `SGDs [u32 version] [u32 size] [u64 handle] [u64 type]
+//! [content]`. Records refer to each other by their handles: the handles of a list of records (for example the
+//! unlockables or the items of an inventory container) are stored in an array (`[u32 count] [u64 handle]...`) of
+//! another record, and a record can contain the handles of the records it owns (for example the ID and the mods of
+//! an item). The records are grouped into sections with their own sizes and the file header contains the size of
+//! the whole content. Inserting records therefore:
+//! - copies the records with new handles and rewrites the handles they use to refer to each other,
+//! - appends the new handles of the listed records to the handle array and increases its count and the size of the
+//!   surrounding record,
+//! - increases the sizes of the surrounding sections and the size in the file header.
+//!
+//! Every size is validated before it is changed, a save with an unknown layout is not changed at all.
+
+use crate::save_logic::struct_data::NewRecord;
+use std::collections::HashSet;
+use std::error::Error;
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the bytes inserted into a save (the offset inside the content before the insert and the length).
+pub type Insertions = Vec<(usize, usize)>;
+
+// Defines the start of every record (the SGDs marker and the version).
+pub static RECORD_MARKER: [u8; 8] = [0x53, 0x47, 0x44, 0x73, 0x01, 0x00, 0x00, 0x00];
+
+// Defines the start of every section.
+pub static SECTION_MARKER: [u8; 13] = [
+    0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+];
+
+// Defines the sizes inside the section header (the offset of the size and the offset its size is counted from).
+pub const SECTION_SIZES: [(usize, usize); 3] = [(13, 8), (32, 36), (37, 41)];
+
+// Defines the offset of the size of the file content inside the file header.
+pub const FILE_SIZE_OFFSET: usize = 0x30;
+
+// Defines the offsets inside a record (relative to the SGDs marker).
+pub const RECORD_SIZE_OFFSET: usize = 8;
+pub const RECORD_HANDLE_OFFSET: usize = 12;
+pub const RECORD_TYPE_OFFSET: usize = 20;
+pub const RECORD_CONTENT_OFFSET: usize = 28;
+pub const RECORD_HEADER_SIZE: usize = 12;
+
+// Defines the distance between two handles that are created by the editor.
+const HANDLE_STEP: u64 = 0x20;
+
+/// Represents a method for inserting records with new handles behind the last record of their kind.
+///
+/// ### Parameter
+/// - `content`: The content of the target save.
+/// - `first_record`: The start of the first record of the kind (its handle leads to the handle array).
+/// - `insert_index`: The end of the last record of the kind.
+/// - `records`: The inserted records (the handles and the references are replaced).
+/// - `kind`: The name of the records for the error messages.
+///
+/// ### Returns `(Vec<u8>, Insertions)`
+/// The new content of the target save and the inserted bytes (offset inside the given content and length).
+pub fn insert_records(
+    mut content: Vec<u8>,
+    first_record: usize,
+    insert_index: usize,
+    records: Vec<NewRecord>,
+    kind: &str,
+) -> Result<(Vec<u8>, Insertions)> {
+    // Validates the layout of the target save before anything is changed.
+    let save_records: Vec<(usize, usize)> = find_records(&content);
+    let mut handles: HashSet<u64> = save_records
+        .iter()
+        .map(|(start, _)| read_u64(&content, start + RECORD_HANDLE_OFFSET))
+        .collect();
+
+    // The last record is followed by the next record or by the next section.
+    let is_marker =
+        |marker: &[u8]| content.get(insert_index..insert_index + marker.len()) == Some(marker);
+    if !is_marker(&RECORD_MARKER) && !is_marker(&SECTION_MARKER) {
+        return Err(format!(
+            "The record behind the last {} of the target save is unknown.",
+            kind
+        )
+        .into());
+    }
+
+    let (count_index, array_end) = find_handle_array(
+        &content,
+        &handles,
+        read_u64(&content, first_record + RECORD_HANDLE_OFFSET),
+        first_record,
+    )?;
+    let array_record: usize = save_records
+        .iter()
+        .rev()
+        .find(|(start, end)| *start < count_index && array_end <= *end)
+        .map(|(start, _)| *start)
+        .ok_or(format!(
+            "The handles of the {} are not part of a record.",
+            kind
+        ))?;
+
+    if array_end > insert_index {
+        return Err(format!("The handles of the {} are stored behind the records.", kind).into());
+    }
+
+    let file_size: usize = read_u32(&content, FILE_SIZE_OFFSET) as usize;
+    if file_size + FILE_SIZE_OFFSET + 4 != content.len() {
+        return Err("The size inside the file header does not match the save.".into());
+    }
+
+    let array_section: usize = find_section(&content, array_end)?;
+    let insert_section: usize = find_section(&content, insert_index)?;
+
+    // Creates unique handles for the new records.
+    let mut next_handle: u64 = handles.iter().max().copied().unwrap_or_default();
+    let new_record_handles: Vec<u64> = records
+        .iter()
+        .map(|_| {
+            loop {
+                next_handle += HANDLE_STEP;
+                if handles.insert(next_handle) {
+                    break;
+                }
+            }
+            next_handle
+        })
+        .collect();
+
+    let mut new_records: Vec<u8> = Vec::new();
+    let mut new_handles: Vec<u8> = Vec::new();
+
+    for (record, handle) in records.into_iter().zip(new_record_handles.iter()) {
+        let mut record_content: Vec<u8> = record.content;
+        record_content[RECORD_HANDLE_OFFSET..RECORD_HANDLE_OFFSET + 8]
+            .copy_from_slice(&handle.to_le_bytes());
+
+        for (offset, referenced_record) in record.references.iter() {
+            let referenced_handle: u64 = *new_record_handles.get(*referenced_record).ok_or(
+                format!("A new record of the {} refers to an unknown record.", kind),
+            )?;
+            record_content[*offset..offset + 8].copy_from_slice(&referenced_handle.to_le_bytes());
+        }
+
+        if record.is_listed {
+            new_handles.extend(handle.to_le_bytes());
+        }
+
+        new_records.extend(record_content);
+    }
+
+    // The sizes are changed before anything is inserted, so every offset still points to the original content.
+    let count: u32 = read_u32(&content, count_index) + (new_handles.len() / 8) as u32;
+    content[count_index..count_index + 4].copy_from_slice(&count.to_le_bytes());
+    add_to_u32(
+        &mut content,
+        array_record + RECORD_SIZE_OFFSET,
+        new_handles.len(),
+    );
+
+    for (offset, _) in SECTION_SIZES.iter() {
+        add_to_u32(&mut content, array_section + offset, new_handles.len());
+        add_to_u32(&mut content, insert_section + offset, new_records.len());
+    }
+
+    add_to_u32(
+        &mut content,
+        FILE_SIZE_OFFSET,
+        new_handles.len() + new_records.len(),
+    );
+
+    // Inserts the records first, since they are stored behind the handle array.
+    content.splice(insert_index..insert_index, new_records.iter().cloned());
+    content.splice(array_end..array_end, new_handles.iter().cloned());
+
+    Ok((
+        content,
+        vec![
+            (array_end, new_handles.len()),
+            (insert_index, new_records.len()),
+        ],
+    ))
+}
+
+/// Represents a method for finding every record of a save whose size matches the start of the next record.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+///
+/// ### Returns `Vec<(usize, usize)>`
+/// The start (SGDs marker) and the end of every record.
+pub fn find_records(content: &[u8]) -> Vec<(usize, usize)> {
+    let mut records: Vec<(usize, usize)> = Vec::new();
+    let mut index: usize = 0;
+
+    while index + RECORD_HEADER_SIZE + 8 <= content.len() {
+        if content[index..index + RECORD_MARKER.len()] != RECORD_MARKER {
+            index += 1;
+            continue;
+        }
+
+        let end: usize =
+            index + RECORD_HEADER_SIZE + read_u32(content, index + RECORD_SIZE_OFFSET) as usize;
+
+        if end <= content.len() {
+            records.push((index, end));
+        }
+
+        index += RECORD_MARKER.len();
+    }
+
+    records
+}
+
+/// Represents a method for shifting an offset of the original content behind the bytes that were inserted before it.
+///
+/// ### Parameter
+/// - `index`: The offset inside the original content.
+/// - `insertions`: The inserted bytes (offset inside the original content and length).
+///
+/// ### Returns `usize`
+/// The offset inside the new content.
+pub fn shift_index(index: usize, insertions: &[(usize, usize)]) -> usize {
+    index
+        + insertions
+            .iter()
+            .filter(|(position, _)| *position <= index)
+            .map(|(_, length)| length)
+            .sum::<usize>()
+}
+
+/// Represents a method for finding the array that lists the handle of a record.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+/// - `handles`: The handles of every record.
+/// - `handle`: The handle of the record.
+/// - `record`: The start of the record (which contains the handle too).
+///
+/// ### Returns `(usize, usize)`
+/// The index of the count in front of the array and the end of the array.
+pub fn find_handle_array(
+    content: &[u8],
+    handles: &HashSet<u64>,
+    handle: u64,
+    record: usize,
+) -> Result<(usize, usize)> {
+    let handle_bytes: [u8; 8] = handle.to_le_bytes();
+    let references: Vec<usize> = content
+        .windows(8)
+        .enumerate()
+        .filter(|(index, window)| {
+            *window == handle_bytes && *index != record + RECORD_HANDLE_OFFSET
+        })
+        .map(|(index, _)| index)
+        .collect();
+
+    let reference: usize = match references.as_slice() {
+        [reference] => *reference,
+        _ => {
+            return Err(format!(
+                "The handle of the first record is referenced [{}] times instead of once.",
+                references.len()
+            )
+            .into())
+        }
+    };
+
+    let is_handle =
+        |index: usize| index + 8 <= content.len() && handles.contains(&read_u64(content, index));
+
+    let mut start: usize = reference;
+    while start >= 12 && is_handle(start - 8) {
+        start -= 8;
+    }
+
+    let mut end: usize = reference;
+    while is_handle(end) {
+        end += 8;
+    }
+
+    let count_index: usize = start - 4;
+    if read_u32(content, count_index) as usize != (end - start) / 8 {
+        return Err("The count of the handle array does not match the handles.".into());
+    }
+
+    Ok((count_index, end))
+}
+
+/// Represents a method for finding and validating the section that contains an index.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+/// - `index`: The index inside the section.
+///
+/// ### Returns `usize`
+/// The start of the section.
+pub fn find_section(content: &[u8], index: usize) -> Result<usize> {
+    let sections: Vec<usize> = content
+        .windows(SECTION_MARKER.len())
+        .enumerate()
+        .filter(|(_, window)| *window == SECTION_MARKER)
+        .map(|(position, _)| position)
+        .collect();
+
+    let position: usize = sections
+        .iter()
+        .rposition(|start| *start < index)
+        .ok_or(format!("The section of the offset [{}] is unknown.", index))?;
+    let start: usize = sections[position];
+    let end: usize = sections.get(position + 1).copied().unwrap_or(content.len());
+
+    for (offset, counted_from) in SECTION_SIZES.iter() {
+        if start + offset + 4 > content.len()
+            || start + counted_from + read_u32(content, start + offset) as usize != end
+        {
+            return Err(format!("The section at offset [{}] has an unknown layout.", start).into());
+        }
+    }
+
+    Ok(start)
+}
+
+/// Represents a method for increasing a size inside the save.
+///
+/// ### Parameter
+/// - `content`: The content of the save.
+/// - `index`: The index of the size.
+/// - `value`: The added value.
+fn add_to_u32(content: &mut [u8], index: usize, value: usize) {
+    let size: u32 = read_u32(content, index) + value as u32;
+    content[index..index + 4].copy_from_slice(&size.to_le_bytes());
+}
+
+/// Represents a method for reading a little endian u16.
+pub fn read_u16(content: &[u8], index: usize) -> u16 {
+    u16::from_le_bytes(content[index..index + 2].try_into().unwrap())
+}
+
+/// Represents a method for reading a little endian u32.
+pub fn read_u32(content: &[u8], index: usize) -> u32 {
+    u32::from_le_bytes(content[index..index + 4].try_into().unwrap())
+}
+
+/// Represents a method for reading a little endian u64.
+pub fn read_u64(content: &[u8], index: usize) -> u64 {
+    u64::from_le_bytes(content[index..index + 8].try_into().unwrap())
+}
@@ -0,0 +1,281 @@
+//! Watches the open save for changes of other programs (e.g. the game saving during play testing).
+//!
+//! The save is polled in a background thread. A change is only reported once the size and the modification time of
+//! the file stayed the same for one interval, so a save that is still written by the game is not read half written.
+//! Every change is compared to the hash of the last known content (`SaveFile::file_hash` or the last reported
+//! change), so touching the file without changing it and writes of the editor itself (see
+//! `SaveWatcher::acknowledge`) are not reported. The watcher only reports changes, it never reloads the save.
+
+use crate::save_logic::file_analyser::{get_content_hash, get_contents_from_file};
+use crate::save_logic::save_writer::is_same_file;
+use crate::save_logic::struct_data::SaveChangeEvent;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the interval the save is checked in.
+pub const WATCH_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// The watcher of the open save (at most one save is watched at a time).
+pub struct SaveWatcher {
+    watch: Option<SaveWatch>,
+}
+
+/// The background thread that watches a single save.
+struct SaveWatch {
+    path: String,
+    known_hash: Arc<Mutex<String>>,
+    is_running: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+impl SaveWatcher {
+    pub fn new() -> Self {
+        SaveWatcher { watch: None }
+    }
+
+    /// Represents a method for watching a save. A previously watched save is no longer watched.
+    ///
+    /// ### Parameter
+    /// - `file_path`: The path of the save.
+    /// - `file_hash`: The hash of the loaded file (`SaveFile::file_hash`).
+    /// - `interval`: The interval the save is checked in.
+    /// - `on_change`: The callback that is called for every change of the save.
+    pub fn watch<F>(
+        &mut self,
+        file_path: &str,
+        file_hash: &str,
+        interval: Duration,
+        on_change: F,
+    ) -> Result<()>
+    where
+        F: Fn(SaveChangeEvent) + Send + 'static,
+    {
+        self.stop();
+
+        if !Path::new(file_path).is_file() {
+            return Err(format!("The save [{}] does not exist.", file_path).into());
+        }
+
+        let path: String = file_path.to_string();
+        let known_hash: Arc<Mutex<String>> = Arc::new(Mutex::new(file_hash.to_string()));
+        let is_running: Arc<AtomicBool> = Arc::new(AtomicBool::new(true));
+
+        let thread: JoinHandle<()> = {
+            let path: String = path.clone();
+            let known_hash: Arc<Mutex<String>> = known_hash.clone();
+            let is_running: Arc<AtomicBool> = is_running.clone();
+
+            thread::Builder::new()
+                .name("save-watcher".to_string())
+                .spawn(move || poll_save(&path, &known_hash, &is_running, interval, on_change))?
+        };
+
+        self.watch = Some(SaveWatch {
+            path,
+            known_hash,
+            is_running,
+            thread,
+        });
+
+        Ok(())
+    }
+
+    /// Represents a method for accepting a content of the watched save as known, so it is not reported as change.
+    /// Has to be called whenever the editor writes or reloads the watched save.
+    ///
+    /// ### Parameter
+    /// - `file_path`: The path of the save.
+    /// - `file_hash`: The hash of the file on disk.
+    pub fn acknowledge(&self, file_path: &str, file_hash: &str) {
+        if let Some(watch) = self.watch.as_ref() {
+            if is_same_file(&watch.path, file_path) {
+                if let Ok(mut known_hash) = watch.known_hash.lock() {
+                    *known_hash = file_hash.to_string();
+                }
+            }
+        }
+    }
+
+    /// Represents a method for stopping the watcher.
+    pub fn stop(&mut self) {
+        if let Some(watch) = self.watch.take() {
+            watch.is_running.store(false, Ordering::SeqCst);
+            watch.thread.thread().unpark();
+            let _ = watch.thread.join();
+        }
+    }
+}
+
+impl Default for SaveWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SaveWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Represents a method for polling a save until the watcher is stopped.
+///
+/// ### Parameter
+/// - `file_path`: The path of the save.
+/// - `known_hash`: The hash of the last known content.
+/// - `is_running`: Indicates whether the watcher is still running.
+/// - `interval`: The interval the save is checked in.
+/// - `on_change`: The callback that is called for every change of the save.
+fn poll_save<F>(
+    file_path: &str,
+    known_hash: &Mutex<String>,
+    is_running: &AtomicBool,
+    interval: Duration,
+    on_change: F,
+) where
+    F: Fn(SaveChangeEvent),
+{
+    let mut last_state: Option<(u64, u64)> = read_file_state(file_path);
+    let mut is_pending: bool = false;
+
+    loop {
+        // The thread is unparked when the watcher is stopped.
+        thread::park_timeout(interval);
+
+        if !is_running.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let state: Option<(u64, u64)> = read_file_state(file_path);
+
+        // The game replaces the save while writing it, therefore a missing file is checked again later.
+        if state != last_state || state.is_none() {
+            is_pending = is_pending || state != last_state;
+            last_state = state;
+            continue;
+        }
+
+        if !is_pending {
+            continue;
+        }
+
+        is_pending = false;
+
+        let file_content: Vec<u8> = match get_contents_from_file(file_path) {
+            Ok(file_content) => file_content,
+            Err(_) => continue,
+        };
+        let file_hash: String = get_content_hash(&file_content);
+
+        let previous_hash: String = match known_hash.lock() {
+            Ok(mut known_hash) if *known_hash != file_hash => {
+                std::mem::replace(&mut *known_hash, file_hash.clone())
+            }
+            _ => continue,
+        };
+
+        on_change(SaveChangeEvent::new(
+            file_path.to_string(),
+            previous_hash,
+            file_hash,
+            file_content.len(),
+            state
+                .map(|(modified_at, _)| modified_at / 1000)
+                .unwrap_or(0),
+        ));
+    }
+}
+
+/// Represents a method for reading the modification time and the size of a file.
+///
+/// ### Parameter
+/// - `file_path`: The path of the file.
+///
+/// ### Returns `Option<(u64, u64)>`
+/// The modification time (milliseconds since the unix epoch) and the size, `None` if the file does not exist.
+fn read_file_state(file_path: &str) -> Option<(u64, u64)> {
+    let metadata: fs::Metadata = fs::metadata(file_path).ok()?;
+    let modified_at: u64 = metadata
+        .modified()
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0);
+
+    Some((modified_at, metadata.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save_logic::save_writer::write_file_atomically;
+    use std::path::PathBuf;
+    use std::sync::mpsc::{self, Receiver};
+
+    // Defines the interval of the tested watchers and how long a test waits for an event.
+    const TEST_INTERVAL: Duration = Duration::from_millis(20);
+    const EVENT_TIMEOUT: Duration = Duration::from_millis(500);
+
+    /// Creates a save in a temporary directory (named after the label) and watches it.
+    fn watch_test_save(label: &str) -> (SaveWatcher, PathBuf, Receiver<SaveChangeEvent>) {
+        let directory: PathBuf =
+            std::env::temp_dir().join(format!("dl2_save_watcher_{}_{}", std::process::id(), label));
+        fs::create_dir_all(&directory).unwrap();
+
+        let path: PathBuf = directory.join("save_main_0.sav");
+        let content: Vec<u8> = b"loaded save".to_vec();
+        fs::write(&path, &content).unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher: SaveWatcher = SaveWatcher::new();
+        watcher
+            .watch(
+                &path.display().to_string(),
+                &get_content_hash(&content),
+                TEST_INTERVAL,
+                move |event: SaveChangeEvent| {
+                    let _ = sender.send(event);
+                },
+            )
+            .unwrap();
+
+        (watcher, path, receiver)
+    }
+
+    #[test]
+    fn ignores_acknowledged_writes_of_the_editor() {
+        let (mut watcher, path, receiver) = watch_test_save("acknowledged");
+        let content: Vec<u8> = b"save written by the editor".to_vec();
+
+        // The same steps as the write_save command: write the save and acknowledge its hash.
+        write_file_atomically(&path, &content).unwrap();
+        watcher.acknowledge(&path.display().to_string(), &get_content_hash(&content));
+
+        assert!(receiver.recv_timeout(EVENT_TIMEOUT).is_err());
+
+        watcher.stop();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn reports_writes_of_other_programs() {
+        let (mut watcher, path, receiver) = watch_test_save("external");
+        let content: Vec<u8> = b"save written by the game".to_vec();
+
+        write_file_atomically(&path, &content).unwrap();
+        let event: SaveChangeEvent = receiver.recv_timeout(EVENT_TIMEOUT).unwrap();
+
+        assert_eq!(event.file_hash, get_content_hash(&content));
+        assert_eq!(event.size, content.len());
+
+        watcher.stop();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
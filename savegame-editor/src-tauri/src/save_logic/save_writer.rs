@@ -0,0 +1,150 @@
+//! Writes edited saves to disk.
+//!
+//! The save is written in the format it was loaded in (compressed PC saves are compressed again, see
+//! `save_compression`). The content is
+//! written to a temporary file next to the target first and then renamed, so the target is never left half written.
+//! Before the loaded save is overwritten, its hash is compared to the hash of the file that was loaded
+//! (`SaveFile::file_hash`). If the game or another tool changed the file in the meantime, the save is only
+//! overwritten if the write is forced.
+
+use crate::save_logic::file_analyser::{get_content_hash, get_contents_from_file};
+use crate::save_logic::save_compression::compress_save_content;
+use crate::save_logic::struct_data::{SaveFile, SaveWriteReport};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the extension of the temporary file.
+static TEMP_EXTENSION: &str = "tmp";
+
+/// Represents a method for writing a save in the format it was loaded in.
+///
+/// ### Parameter
+/// - `save_file`: The save with the edited content.
+/// - `output_path`: The path of the written save.
+/// - `is_forced`: Indicates whether the loaded save is overwritten even if it was changed on disk.
+///
+/// ### Returns `SaveWriteReport`
+/// The written file and its new hash (the new `SaveFile::file_hash`).
+pub fn write_save_file(
+    save_file: &SaveFile,
+    output_path: &str,
+    is_forced: bool,
+) -> Result<SaveWriteReport> {
+    let has_conflict: bool = has_save_conflict(save_file, output_path)?;
+
+    if has_conflict && !is_forced {
+        return Err(format!(
+            "The save [{}] was changed on disk since it was loaded. Reload the save or force the write to overwrite it.",
+            output_path
+        )
+        .into());
+    }
+
+    // Compressed PC saves are written compressed again (with the header and level they were loaded with).
+    let content: Vec<u8> = match save_file.is_compressed {
+        true => get_compressed_content(save_file)?,
+        false => save_file.file_content.clone(),
+    };
+
+    write_file_atomically(Path::new(output_path), &content)?;
+
+    Ok(SaveWriteReport::new(
+        output_path.to_string(),
+        save_file.is_compressed,
+        content.len(),
+        get_content_hash(&content),
+        has_conflict,
+    ))
+}
+
+/// Represents a method for checking whether the loaded save was changed on disk since it was loaded.
+/// Only the path the save was loaded from is checked, other paths are chosen by the user.
+///
+/// ### Parameter
+/// - `save_file`: The loaded save.
+/// - `output_path`: The path of the written save.
+///
+/// ### Returns `bool`
+/// Indicates whether the file on disk differs from the loaded file.
+pub fn has_save_conflict(save_file: &SaveFile, output_path: &str) -> Result<bool> {
+    if !Path::new(output_path).exists() || !is_same_file(&save_file.path, output_path) {
+        return Ok(false);
+    }
+
+    let current_content: Vec<u8> = get_contents_from_file(output_path)
+        .map_err(|e| format!("Error reading the save [{}]: {}", output_path, e))?;
+
+    // A save without hash cannot be verified, therefore it counts as changed.
+    Ok(save_file.file_hash.is_empty() || get_content_hash(&current_content) != save_file.file_hash)
+}
+
+/// Represents a method for compressing a PC save. If the content was not edited and the loaded file is unchanged
+/// on disk, the loaded file is reused, so even saves from an unknown compressor are written unchanged.
+///
+/// ### Parameter
+/// - `save_file`: The save with the (edited) content.
+///
+/// ### Returns `Vec<u8>`
+/// The compressed content.
+fn get_compressed_content(save_file: &SaveFile) -> Result<Vec<u8>> {
+    let is_unedited: bool = save_file
+        .gzip_profile
+        .as_ref()
+        .is_some_and(|profile| profile.content_hash == get_content_hash(&save_file.file_content));
+
+    if is_unedited && !save_file.file_hash.is_empty() {
+        if let Ok(loaded_content) = get_contents_from_file(&save_file.path) {
+            if get_content_hash(&loaded_content) == save_file.file_hash {
+                return Ok(loaded_content);
+            }
+        }
+    }
+
+    compress_save_content(&save_file.file_content, save_file.gzip_profile.as_ref())
+}
+
+/// Represents a method for writing a file through a temporary file and a rename.
+///
+/// ### Parameter
+/// - `path`: The path of the file.
+/// - `content`: The content of the file.
+pub fn write_file_atomically(path: &Path, content: &[u8]) -> Result<()> {
+    let file_name: String = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or(format!("The path [{}] is not a file.", path.display()))?;
+    let temp_path: PathBuf = path.with_file_name(format!("{}.{}", file_name, TEMP_EXTENSION));
+
+    let result: Result<()> = fs::File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(content)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, path))
+        .map_err(|e| format!("Error writing the file [{}]: {}", path.display(), e).into());
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result
+}
+
+/// Represents a method for checking whether two paths point to the same file.
+///
+/// ### Parameter
+/// - `first_path`: The first path.
+/// - `second_path`: The second path.
+///
+/// ### Returns `bool`
+/// Indicates whether both paths point to the same file.
+pub fn is_same_file(first_path: &str, second_path: &str) -> bool {
+    match (fs::canonicalize(first_path), fs::canonicalize(second_path)) {
+        (Ok(first), Ok(second)) => first == second,
+        _ => Path::new(first_path) == Path::new(second_path),
+    }
+}
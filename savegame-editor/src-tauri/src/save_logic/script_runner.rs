@@ -0,0 +1,560 @@
+//! Runs user scripts (Rhai) that transform a parsed save.
+//!
+//! Scripts read the save through `items()`, `skills()`, `unlockables()` and `game_version()` and change it through
+//! the edit operations of the file analyser, for example:
+//!
+//! ```rhai
+//! for item in items() {
+//!     if item.tab == "Weapons" && item.level < 10 {
+//!         item.set_level(10);
+//!         item.set_seed(random_seed());
+//!     }
+//! }
+//! ```
+//!
+//! Available edit operations: `set_level`, `set_seed`, `set_amount`, `set_durability`, `remove_item` (items)
+//! and `set_skill_points` (skills). Scripts have no access to the file system except `read_file` and `write_file`,
+//! which are limited to the sandbox directory. Modules (`import`) and `eval` are disabled.
+
+use crate::save_logic::file_analyser::{
+    clean_sgd_name, edit_inventory_item_chunk, edit_skill, find_byte_changes, remove_inventory_item,
+};
+use crate::save_logic::struct_data::{InventoryItem, SaveFile, ScriptReport, SkillItem};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the result of functions that are called by scripts.
+type ScriptResult<T> = std::result::Result<T, Box<EvalAltResult>>;
+
+// Defines the limits of a script run.
+const MAX_OPERATIONS: u64 = 50_000_000;
+const MAX_CALL_LEVELS: usize = 64;
+const MAX_STRING_SIZE: usize = 16 * 1024 * 1024;
+const MAX_COLLECTION_SIZE: usize = 1_000_000;
+
+/// The save data and edits that are shared between the bindings of a script run.
+struct ScriptState {
+    content: Vec<u8>,
+    items: HashMap<usize, InventoryItem>,
+    skills: HashMap<usize, SkillItem>,
+    item_values: HashMap<usize, (u16, u16, u32, f32)>,
+    removed_items: HashSet<usize>,
+    changed_skills: HashSet<usize>,
+    output: Vec<String>,
+    files_written: Vec<String>,
+    random_state: u64,
+}
+
+impl ScriptState {
+    /// Represents a method for changing the chunk values of an item through the chunk edit of the file analyser.
+    ///
+    /// ### Parameter
+    /// - `item_map`: The item as seen by the script.
+    /// - `edit`: The change of the values (level, seed, amount, durability).
+    fn edit_item(
+        &mut self,
+        item_map: &Map,
+        edit: impl FnOnce(&mut (u16, u16, u32, f32)),
+    ) -> ScriptResult<()> {
+        let item: InventoryItem = self.find_item(item_map)?;
+        let chunk = &item.chunk_data;
+        let values: &mut (u16, u16, u32, f32) =
+            self.item_values.entry(item.index).or_insert_with(|| {
+                (
+                    chunk.level_value,
+                    chunk.seed_value,
+                    chunk.amount_value,
+                    f32::from_le_bytes(chunk.durability.clone().try_into().unwrap_or_default()),
+                )
+            });
+
+        edit(values);
+        let values: (u16, u16, u32, f32) = *values;
+
+        self.content = edit_inventory_item_chunk(
+            item.index,
+            item.name.clone(),
+            chunk.index,
+            item.size,
+            values.0,
+            values.1,
+            values.2,
+            values.3,
+            std::mem::take(&mut self.content),
+        );
+
+        Ok(())
+    }
+
+    /// Represents a method for finding the inventory item of a script value.
+    ///
+    /// ### Parameter
+    /// - `item_map`: The item as seen by the script.
+    ///
+    /// ### Returns `InventoryItem`
+    /// The item of the save (removed items cannot be edited anymore).
+    fn find_item(&self, item_map: &Map) -> ScriptResult<InventoryItem> {
+        let index: usize = read_index(item_map)?;
+
+        if self.removed_items.contains(&index) {
+            return Err(format!("The item at offset [{}] was already removed.", index).into());
+        }
+
+        self.items
+            .get(&index)
+            .cloned()
+            .ok_or(format!("There is no inventory item at offset [{}].", index).into())
+    }
+
+    /// Represents a method for creating a random seed (xorshift).
+    ///
+    /// ### Returns `u16`
+    /// The random seed.
+    fn next_seed(&mut self) -> u16 {
+        self.random_state ^= self.random_state << 13;
+        self.random_state ^= self.random_state >> 7;
+        self.random_state ^= self.random_state << 17;
+
+        (self.random_state >> 32) as u16
+    }
+}
+
+/// Represents a method for running a script on a save.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+/// - `script_path`: The path of the script.
+/// - `sandbox_path`: The only directory the script can read and write files in (`None` disables file access).
+///
+/// ### Returns `(ScriptReport, Vec<u8>)`
+/// The output and the changes of the script and the new content of the save file.
+pub fn run_save_script(
+    save_file: &SaveFile,
+    script_path: &str,
+    sandbox_path: Option<&str>,
+) -> Result<(ScriptReport, Vec<u8>)> {
+    let script: String = fs::read_to_string(script_path)
+        .map_err(|e| format!("Error reading the script [{}]: {}", script_path, e))?;
+
+    let sandbox: Option<PathBuf> = match sandbox_path {
+        Some(path) => Some(
+            fs::canonicalize(path)
+                .map_err(|e| format!("The sandbox directory [{}] is not available: {}", path, e))?,
+        ),
+        None => None,
+    };
+
+    let state: Rc<RefCell<ScriptState>> = Rc::new(RefCell::new(ScriptState {
+        content: save_file.file_content.clone(),
+        items: save_file
+            .items
+            .iter()
+            .flat_map(|row| row.inventory_items.iter())
+            .map(|item| (item.index, item.clone()))
+            .collect(),
+        skills: save_file
+            .skills
+            .base_skills
+            .iter()
+            .chain(save_file.skills.legend_skills.iter())
+            .map(|skill| (skill.index, skill.clone()))
+            .collect(),
+        item_values: HashMap::new(),
+        removed_items: HashSet::new(),
+        changed_skills: HashSet::new(),
+        output: Vec::new(),
+        files_written: Vec::new(),
+        random_state: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0x2545F4914F6CDD1D, |d| d.as_nanos() as u64)
+            | 1,
+    }));
+
+    let engine: Engine = create_script_engine(save_file, state.clone(), sandbox);
+    let result = engine.run(&script);
+
+    // The bindings of the engine hold the state, so the engine is dropped first.
+    drop(engine);
+    let state: ScriptState = match Rc::try_unwrap(state) {
+        Ok(state) => state.into_inner(),
+        Err(_) => return Err("The script state is still in use.".into()),
+    };
+
+    result.map_err(|e| {
+        format!(
+            "Error running the script: {}{}",
+            e,
+            match state.output.is_empty() {
+                true => String::new(),
+                false => format!("\nOutput:\n{}", state.output.join("\n")),
+            }
+        )
+    })?;
+
+    let mut report: ScriptReport = ScriptReport::new(script_path.to_string());
+    report.changed_items = state
+        .item_values
+        .keys()
+        .filter(|index| !state.removed_items.contains(index))
+        .count();
+    report.removed_items = state.removed_items.len();
+    report.changed_skills = state.changed_skills.len();
+    report.byte_changes = find_byte_changes(&save_file.file_content, &state.content).len();
+    report.output = state.output;
+    report.files_written = state.files_written;
+
+    Ok((report, state.content))
+}
+
+/// Represents a method for creating the sandboxed script engine with all bindings.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+/// - `state`: The shared state of the script run.
+/// - `sandbox`: The directory the script can read and write files in.
+///
+/// ### Returns `Engine`
+/// The script engine.
+fn create_script_engine(
+    save_file: &SaveFile,
+    state: Rc<RefCell<ScriptState>>,
+    sandbox: Option<PathBuf>,
+) -> Engine {
+    let mut engine: Engine = Engine::new();
+
+    // Limits the resources of a script and disables every access outside of the bindings.
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_array_size(MAX_COLLECTION_SIZE);
+    engine.set_max_map_size(MAX_COLLECTION_SIZE);
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+
+    let print_state = state.clone();
+    engine.on_print(move |text| print_state.borrow_mut().output.push(text.to_string()));
+    let debug_state = state.clone();
+    engine.on_debug(move |text, _, position| {
+        debug_state
+            .borrow_mut()
+            .output
+            .push(format!("[{}] {}", position, text))
+    });
+
+    // Read access to the save.
+    let items: Array = create_item_array(save_file);
+    engine.register_fn("items", move || items.clone());
+    let skills: Array = create_skill_array(save_file);
+    engine.register_fn("skills", move || skills.clone());
+    let unlockables: Array = save_file
+        .unlockable_items
+        .iter()
+        .map(|unlockable| {
+            let mut map: Map = Map::new();
+            map.insert("name".into(), clean_sgd_name(&unlockable.name).into());
+            map.insert("index".into(), (unlockable.index as i64).into());
+            Dynamic::from_map(map)
+        })
+        .collect();
+    engine.register_fn("unlockables", move || unlockables.clone());
+    let game_version: String = save_file.game_version.clone();
+    engine.register_fn("game_version", move || game_version.clone());
+
+    // Edit operations of the inventory items.
+    let level_state = state.clone();
+    engine.register_fn(
+        "set_level",
+        move |item: &mut Map, value: i64| -> ScriptResult<()> {
+            let value: u16 = to_u16(value, "level")?;
+            level_state
+                .borrow_mut()
+                .edit_item(item, |values| values.0 = value)?;
+            item.insert("level".into(), (value as i64).into());
+            Ok(())
+        },
+    );
+
+    let seed_state = state.clone();
+    engine.register_fn(
+        "set_seed",
+        move |item: &mut Map, value: i64| -> ScriptResult<()> {
+            let value: u16 = to_u16(value, "seed")?;
+            seed_state
+                .borrow_mut()
+                .edit_item(item, |values| values.1 = value)?;
+            item.insert("seed".into(), (value as i64).into());
+            Ok(())
+        },
+    );
+
+    let amount_state = state.clone();
+    engine.register_fn(
+        "set_amount",
+        move |item: &mut Map, value: i64| -> ScriptResult<()> {
+            let value: u32 = u32::try_from(value)
+                .map_err(|_| format!("The amount [{}] is out of range.", value))?;
+            amount_state
+                .borrow_mut()
+                .edit_item(item, |values| values.2 = value)?;
+            item.insert("amount".into(), (value as i64).into());
+            Ok(())
+        },
+    );
+
+    let durability_state = state.clone();
+    engine.register_fn(
+        "set_durability",
+        move |item: &mut Map, value: f64| -> ScriptResult<()> {
+            let value: f32 = value as f32;
+            if !value.is_finite() {
+                return Err("The durability is not a valid number.".into());
+            }
+            durability_state
+                .borrow_mut()
+                .edit_item(item, |values| values.3 = value)?;
+            item.insert("durability".into(), (value as f64).into());
+            Ok(())
+        },
+    );
+
+    let remove_state = state.clone();
+    engine.register_fn("remove_item", move |item: &mut Map| -> ScriptResult<()> {
+        let mut state = remove_state.borrow_mut();
+        let inventory_item: InventoryItem = state.find_item(item)?;
+
+        state.content = remove_inventory_item(
+            inventory_item.index,
+            inventory_item.index + inventory_item.size,
+            inventory_item.chunk_data.index,
+            std::mem::take(&mut state.content),
+        );
+        state.removed_items.insert(inventory_item.index);
+        item.insert("removed".into(), true.into());
+        Ok(())
+    });
+
+    // Edit operations of the skills.
+    let skill_state = state.clone();
+    engine.register_fn(
+        "set_skill_points",
+        move |skill: &mut Map, value: i64| -> ScriptResult<()> {
+            let value: u16 = to_u16(value, "skill points")?;
+            let mut state = skill_state.borrow_mut();
+            let index: usize = read_index(skill)?;
+            let skill_item: SkillItem = state
+                .skills
+                .get(&index)
+                .cloned()
+                .ok_or(format!("There is no skill at offset [{}].", index))?;
+
+            state.content = edit_skill(
+                skill_item.size,
+                skill_item.index,
+                value,
+                std::mem::take(&mut state.content),
+            );
+            state.changed_skills.insert(index);
+            skill.insert("points".into(), (value as i64).into());
+            Ok(())
+        },
+    );
+
+    let random_state = state.clone();
+    engine.register_fn("random_seed", move || {
+        random_state.borrow_mut().next_seed() as i64
+    });
+
+    // Sandboxed file access.
+    let read_sandbox: Option<PathBuf> = sandbox.clone();
+    engine.register_fn("read_file", move |name: &str| -> ScriptResult<String> {
+        let path: PathBuf = resolve_sandbox_path(&read_sandbox, name)?;
+
+        if fs::metadata(&path).map_or(0, |m| m.len()) as usize > MAX_STRING_SIZE {
+            return Err(format!("The file [{}] is too large.", name).into());
+        }
+
+        fs::read_to_string(&path)
+            .map_err(|e| format!("Error reading the file [{}]: {}", name, e).into())
+    });
+
+    let write_state = state.clone();
+    engine.register_fn(
+        "write_file",
+        move |name: &str, text: &str| -> ScriptResult<()> {
+            let path: PathBuf = resolve_sandbox_path(&sandbox, name)?;
+
+            fs::write(&path, text)
+                .map_err(|e| format!("Error writing the file [{}]: {}", name, e))?;
+            write_state
+                .borrow_mut()
+                .files_written
+                .push(path.display().to_string());
+            Ok(())
+        },
+    );
+
+    engine
+}
+
+/// Represents a method for converting the inventory items into script values.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+///
+/// ### Returns `Array`
+/// Every inventory item as map.
+fn create_item_array(save_file: &SaveFile) -> Array {
+    save_file
+        .items
+        .iter()
+        .flat_map(|row| {
+            row.inventory_items.iter().map(|item| {
+                let chunk = &item.chunk_data;
+                let mods: Array = item
+                    .mod_data
+                    .iter()
+                    .map(|mod_item| clean_sgd_name(&mod_item.name).into())
+                    .collect();
+
+                let mut map: Map = Map::new();
+                map.insert("tab".into(), row.name.clone().into());
+                map.insert("container".into(), row.container.clone().into());
+                map.insert("id".into(), item.name.clone().into());
+                map.insert(
+                    "display_name".into(),
+                    item.catalog.display_name.clone().into(),
+                );
+                map.insert("category".into(), item.catalog.category.clone().into());
+                map.insert("dropable".into(), item.catalog.dropable.into());
+                map.insert("shareable".into(), item.catalog.shareable.into());
+                map.insert("index".into(), (item.index as i64).into());
+                map.insert("chunk_index".into(), (chunk.index as i64).into());
+                map.insert("level".into(), (chunk.level_value as i64).into());
+                map.insert("seed".into(), (chunk.seed_value as i64).into());
+                map.insert("amount".into(), (chunk.amount_value as i64).into());
+                map.insert(
+                    "durability".into(),
+                    (f32::from_le_bytes(chunk.durability.clone().try_into().unwrap_or_default())
+                        as f64)
+                        .into(),
+                );
+                map.insert("mods".into(), mods.into());
+                map.insert("removed".into(), false.into());
+                Dynamic::from_map(map)
+            })
+        })
+        .collect()
+}
+
+/// Represents a method for converting the skills into script values.
+///
+/// ### Parameter
+/// - `save_file`: The parsed save.
+///
+/// ### Returns `Array`
+/// Every base and legend skill as map.
+fn create_skill_array(save_file: &SaveFile) -> Array {
+    save_file
+        .skills
+        .base_skills
+        .iter()
+        .map(|skill| ("base", skill))
+        .chain(
+            save_file
+                .skills
+                .legend_skills
+                .iter()
+                .map(|skill| ("legend", skill)),
+        )
+        .map(|(tree, skill)| {
+            let mut map: Map = Map::new();
+            map.insert("tree".into(), tree.into());
+            map.insert("name".into(), clean_sgd_name(&skill.name).into());
+            map.insert("index".into(), (skill.index as i64).into());
+            map.insert("points".into(), (skill.points_value as i64).into());
+            Dynamic::from_map(map)
+        })
+        .collect()
+}
+
+/// Represents a method for reading the offset of an item or skill that was passed by a script.
+///
+/// ### Parameter
+/// - `map`: The item or skill as seen by the script.
+///
+/// ### Returns `usize`
+/// The offset inside the save.
+fn read_index(map: &Map) -> ScriptResult<usize> {
+    map.get("index")
+        .and_then(|index| index.as_int().ok())
+        .and_then(|index| usize::try_from(index).ok())
+        .ok_or("The value is not an item or skill of the save.".into())
+}
+
+/// Represents a method for converting a script number into a 2 byte value.
+///
+/// ### Parameter
+/// - `value`: The script number.
+/// - `name`: The name of the value (for the error message).
+///
+/// ### Returns `u16`
+/// The converted value.
+fn to_u16(value: i64, name: &str) -> ScriptResult<u16> {
+    u16::try_from(value).map_err(|_| format!("The {} [{}] is out of range.", name, value).into())
+}
+
+/// Represents a method for resolving a file path of a script inside the sandbox directory.
+/// Absolute paths, `..`, links and linked directories that leave the sandbox are rejected.
+///
+/// ### Parameter
+/// - `sandbox`: The sandbox directory (`None` if file access is disabled).
+/// - `name`: The relative path used by the script.
+///
+/// ### Returns `PathBuf`
+/// The path inside the sandbox.
+fn resolve_sandbox_path(sandbox: &Option<PathBuf>, name: &str) -> ScriptResult<PathBuf> {
+    let sandbox: &PathBuf = sandbox
+        .as_ref()
+        .ok_or("File access is disabled for this script.")?;
+    let relative: &Path = Path::new(name);
+
+    if name.is_empty()
+        || !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(format!("The path [{}] is outside of the sandbox.", name).into());
+    }
+
+    let path: PathBuf = sandbox.join(relative);
+
+    // A link is never followed, writing to a dangling link would create its target outside of the sandbox.
+    if fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_symlink()) {
+        return Err(format!("The path [{}] is a link.", name).into());
+    }
+
+    // Links of the directories inside the sandbox could still point outside of it.
+    let resolved: Option<PathBuf> = match path.exists() {
+        true => fs::canonicalize(&path).ok(),
+        false => path
+            .parent()
+            .and_then(|parent| fs::canonicalize(parent).ok())
+            .zip(path.file_name())
+            .map(|(parent, file_name)| parent.join(file_name)),
+    };
+
+    match resolved {
+        Some(resolved) if resolved.starts_with(sandbox) => Ok(resolved),
+        Some(_) => Err(format!("The path [{}] is outside of the sandbox.", name).into()),
+        None => Err(format!("The directory of [{}] does not exist.", name).into()),
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::save_logic::file_analyser::format_bytes_to_string;
 
@@ -109,6 +110,7 @@ pub struct InventoryItem {
     pub sgd_data: Vec<u8>,
     pub chunk_data: InventoryChunk,
     pub mod_data: Vec<Mod>,
+    pub catalog: CatalogItem,
 }
 
 impl InventoryItem {
@@ -119,6 +121,7 @@ impl InventoryItem {
         sgd_data: Vec<u8>,
         chunk_data: InventoryChunk,
         mod_data: Vec<Mod>,
+        catalog: CatalogItem,
     ) -> Self {
         InventoryItem {
             name,
@@ -127,6 +130,7 @@ impl InventoryItem {
             sgd_data,
             chunk_data: chunk_data,
             mod_data,
+            catalog,
         }
     }
 }
@@ -134,18 +138,99 @@ impl InventoryItem {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct InventoryItemRow {
     pub name: String,
+    pub container: String,
     pub inventory_items: Vec<InventoryItem>,
 }
 
 impl InventoryItemRow {
-    pub fn new(name: String, inventory_items: Vec<InventoryItem>) -> Self {
+    pub fn new(name: String, container: String, inventory_items: Vec<InventoryItem>) -> Self {
         InventoryItemRow {
             name,
+            container,
             inventory_items,
         }
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryContainer {
+    pub name: String,
+    pub container_type: String,
+    pub slot_count: usize,
+    pub index: usize,
+}
+
+impl InventoryContainer {
+    pub fn new(name: String, container_type: String, slot_count: usize, index: usize) -> Self {
+        InventoryContainer {
+            name,
+            container_type,
+            slot_count,
+            index,
+        }
+    }
+
+    /// The marker that identifies the container inside the tab mapping, for example `Token` or `Main/Other`.
+    pub fn marker(&self) -> String {
+        if self.container_type == "Unknown" {
+            self.name.clone()
+        } else {
+            format!("{}/{}", self.name, self.container_type)
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewRecord {
+    pub content: Vec<u8>,
+    pub is_listed: bool,
+    pub references: Vec<(usize, usize)>,
+}
+
+impl NewRecord {
+    pub fn new(content: Vec<u8>, is_listed: bool, references: Vec<(usize, usize)>) -> Self {
+        NewRecord {
+            content,
+            is_listed,
+            references,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryTab {
+    pub name: String,
+    pub containers: Vec<String>,
+    pub categories: Vec<String>,
+}
+
+impl InventoryTab {
+    pub fn new(name: String, containers: Vec<String>, categories: Vec<String>) -> Self {
+        InventoryTab {
+            name,
+            containers,
+            categories,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryTabMapping {
+    pub version: u32,
+    pub default_tab: String,
+    pub tabs: Vec<InventoryTab>,
+}
+
+impl InventoryTabMapping {
+    pub fn new(version: u32, default_tab: String, tabs: Vec<InventoryTab>) -> Self {
+        InventoryTabMapping {
+            version,
+            default_tab,
+            tabs,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SkillItem {
     pub name: String,
@@ -218,8 +303,14 @@ pub struct SaveFile {
     pub unlockable_items: Vec<UnlockableItem>,
     pub items: Vec<InventoryItemRow>,
     pub log_history: Vec<String>,
+    pub classification_report: Vec<ClassificationEntry>,
+    pub unknown_ids: Vec<UnknownId>,
     pub is_compressed: bool,
     pub game_version: String,
+    #[serde(default)]
+    pub file_hash: String,
+    #[serde(default)]
+    pub gzip_profile: Option<GzipProfile>,
 }
 
 impl SaveFile {
@@ -230,6 +321,8 @@ impl SaveFile {
         unlockable_items: Vec<UnlockableItem>,
         items: Vec<InventoryItemRow>,
         log_history: Vec<String>,
+        classification_report: Vec<ClassificationEntry>,
+        unknown_ids: Vec<UnknownId>,
         is_compressed: bool,
         game_version: String,
     ) -> Self {
@@ -241,38 +334,1343 @@ impl SaveFile {
             unlockable_items,
             skills,
             log_history,
+            classification_report,
+            unknown_ids,
             is_compressed,
             game_version,
+            file_hash: String::new(),
+            gzip_profile: None,
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OutpostSave {
+    pub directory: String,
     pub name: String,
     pub owner: String,
     pub description: String,
     pub features: Vec<String>,
     pub version: String,
-    pub save_file: SaveFile,
+    pub save_path: String,
+    pub error: String,
 }
 
 impl OutpostSave {
     pub fn new(
+        directory: String,
         name: String,
         owner: String,
         description: String,
         features: Vec<String>,
         version: String,
-        save_file: SaveFile,
+        save_path: String,
+        error: String,
     ) -> Self {
         OutpostSave {
+            directory,
             name,
             owner,
             description,
             features,
             version,
-            save_file,
+            save_path,
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LayoutProfile {
+    pub game_version: String,
+    pub data_offset: usize,
+    pub level_position: usize,
+    pub seed_position: usize,
+    pub amount_position: usize,
+    pub durability_position: usize,
+    pub counter_stats_position: usize,
+    pub chunk_strides: Vec<usize>,
+    pub size_position: usize,
+    pub header_size: usize,
+}
+
+impl LayoutProfile {
+    pub fn new(
+        game_version: String,
+        data_offset: usize,
+        level_position: usize,
+        seed_position: usize,
+        amount_position: usize,
+        durability_position: usize,
+        counter_stats_position: usize,
+        chunk_strides: Vec<usize>,
+        size_position: usize,
+        header_size: usize,
+    ) -> Self {
+        LayoutProfile {
+            game_version,
+            data_offset,
+            level_position,
+            seed_position,
+            amount_position,
+            durability_position,
+            counter_stats_position,
+            chunk_strides,
+            size_position,
+            header_size,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LayoutFieldCandidate {
+    pub field: String,
+    pub distance: usize,
+    pub hits: usize,
+    pub samples: usize,
+    pub confidence: f32,
+}
+
+impl LayoutFieldCandidate {
+    pub fn new(field: String, distance: usize, hits: usize, samples: usize) -> Self {
+        LayoutFieldCandidate {
+            field,
+            distance,
+            hits,
+            samples,
+            confidence: if samples == 0 {
+                0.0
+            } else {
+                hits as f32 / samples as f32
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LayoutReport {
+    pub path: String,
+    pub named_sgd_count: usize,
+    pub known_item_count: usize,
+    pub chunk_marker_count: usize,
+    pub chunk_run_count: usize,
+    pub known_chunk_run_count: usize,
+    pub stride_confidence: f32,
+    pub header_confidence: f32,
+    pub fields: Vec<LayoutFieldCandidate>,
+    pub confidence: f32,
+    pub candidate: LayoutProfile,
+    pub current: LayoutProfile,
+    pub matches_current: bool,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ItemRulePattern {
+    pub pattern: String,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl ItemRulePattern {
+    pub fn new(pattern: String, exclude: Vec<String>) -> Self {
+        ItemRulePattern { pattern, exclude }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ItemRule {
+    pub name: String,
+    pub description: String,
+    pub ids: Vec<String>,
+    pub patterns: Vec<ItemRulePattern>,
+}
+
+impl ItemRule {
+    pub fn new(
+        name: String,
+        description: String,
+        ids: Vec<String>,
+        patterns: Vec<ItemRulePattern>,
+    ) -> Self {
+        ItemRule {
+            name,
+            description,
+            ids,
+            patterns,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ItemRuleSet {
+    pub version: u32,
+    pub rules: Vec<ItemRule>,
+}
+
+impl ItemRuleSet {
+    pub fn new(version: u32, rules: Vec<ItemRule>) -> Self {
+        ItemRuleSet { version, rules }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClassificationEntry {
+    pub name: String,
+    pub index: usize,
+    pub classification: String,
+    pub rule: String,
+}
+
+impl ClassificationEntry {
+    pub fn new(name: String, index: usize, classification: String, rule: String) -> Self {
+        ClassificationEntry {
+            name,
+            index,
+            classification,
+            rule,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CatalogItem {
+    pub id: String,
+    pub display_name: String,
+    pub category: String,
+    pub dropable: bool,
+    pub shareable: bool,
+    pub dlc: bool,
+}
+
+impl CatalogItem {
+    pub fn new(
+        id: String,
+        display_name: String,
+        category: String,
+        dropable: bool,
+        shareable: bool,
+        dlc: bool,
+    ) -> Self {
+        CatalogItem {
+            id,
+            display_name,
+            category,
+            dropable,
+            shareable,
+            dlc,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ItemCatalog {
+    pub items: HashMap<String, CatalogItem>,
+}
+
+impl ItemCatalog {
+    pub fn new(items: HashMap<String, CatalogItem>) -> Self {
+        ItemCatalog { items }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CoopSafetyIssue {
+    pub tab: String,
+    pub item_name: String,
+    pub display_name: String,
+    pub index: usize,
+    pub chunk_index: usize,
+    pub dropable: bool,
+    pub shareable: bool,
+    pub risk: String,
+}
+
+impl CoopSafetyIssue {
+    pub fn new(
+        tab: String,
+        item_name: String,
+        display_name: String,
+        index: usize,
+        chunk_index: usize,
+        dropable: bool,
+        shareable: bool,
+        risk: String,
+    ) -> Self {
+        CoopSafetyIssue {
+            tab,
+            item_name,
+            display_name,
+            index,
+            chunk_index,
+            dropable,
+            shareable,
+            risk,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CoopSafetyReport {
+    pub path: String,
+    pub checked_items: usize,
+    pub issues: Vec<CoopSafetyIssue>,
+    pub is_safe: bool,
+}
+
+impl CoopSafetyReport {
+    pub fn new(path: String, checked_items: usize, issues: Vec<CoopSafetyIssue>) -> Self {
+        CoopSafetyReport {
+            path,
+            checked_items,
+            is_safe: issues.is_empty(),
+            issues,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdSnapshot {
+    pub version: u32,
+    pub created_at: u64,
+    pub source: String,
+    pub id_count: usize,
+}
+
+impl IdSnapshot {
+    pub fn new(version: u32, created_at: u64, source: String, id_count: usize) -> Self {
+        IdSnapshot {
+            version,
+            created_at,
+            source,
+            id_count,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdDatabaseManifest {
+    pub current_version: u32,
+    pub snapshots: Vec<IdSnapshot>,
+    #[serde(default)]
+    pub bundled_hash: String,
+}
+
+impl IdDatabaseManifest {
+    pub fn new(current_version: u32, snapshots: Vec<IdSnapshot>, bundled_hash: String) -> Self {
+        IdDatabaseManifest {
+            current_version,
+            snapshots,
+            bundled_hash,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdConflict {
+    pub id: String,
+    pub existing_category: String,
+    pub imported_category: String,
+}
+
+impl IdConflict {
+    pub fn new(id: String, existing_category: String, imported_category: String) -> Self {
+        IdConflict {
+            id,
+            existing_category,
+            imported_category,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdMergeReport {
+    pub version: u32,
+    pub added: usize,
+    pub added_display_names: usize,
+    pub duplicates: usize,
+    pub new_categories: Vec<String>,
+    pub conflicts: Vec<IdConflict>,
+}
+
+impl IdMergeReport {
+    pub fn new(
+        version: u32,
+        added: usize,
+        added_display_names: usize,
+        duplicates: usize,
+        new_categories: Vec<String>,
+        conflicts: Vec<IdConflict>,
+    ) -> Self {
+        IdMergeReport {
+            version,
+            added,
+            added_display_names,
+            duplicates,
+            new_categories,
+            conflicts,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdBundleCategory {
+    pub name: String,
+    pub file: String,
+    pub sha256: String,
+}
+
+impl IdBundleCategory {
+    pub fn new(name: String, file: String, sha256: String) -> Self {
+        IdBundleCategory { name, file, sha256 }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdBundleManifest {
+    pub game_version: String,
+    pub categories: Vec<IdBundleCategory>,
+}
+
+impl IdBundleManifest {
+    pub fn new(game_version: String, categories: Vec<IdBundleCategory>) -> Self {
+        IdBundleManifest {
+            game_version,
+            categories,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdImport {
+    pub source: String,
+    pub game_version: String,
+    pub categories: Vec<IdData>,
+    pub display_names: HashMap<String, String>,
+}
+
+impl IdImport {
+    pub fn new(
+        source: String,
+        game_version: String,
+        categories: Vec<IdData>,
+        display_names: HashMap<String, String>,
+    ) -> Self {
+        IdImport {
+            source,
+            game_version,
+            categories,
+            display_names,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnknownId {
+    pub name: String,
+    pub index: usize,
+    pub tab: String,
+    pub is_mod: bool,
+    pub occurrences: usize,
+    pub suggested_category: String,
+}
+
+impl UnknownId {
+    pub fn new(
+        name: String,
+        index: usize,
+        tab: String,
+        is_mod: bool,
+        suggested_category: String,
+    ) -> Self {
+        UnknownId {
+            name,
+            index,
+            tab,
+            is_mod,
+            occurrences: 1,
+            suggested_category,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdExtractionReport {
+    pub install_path: String,
+    pub archives: Vec<String>,
+    pub skipped_archives: Vec<String>,
+    pub script_count: usize,
+    pub item_count: usize,
+    pub category_counts: HashMap<String, usize>,
+    pub merge_report: Option<IdMergeReport>,
+}
+
+impl IdExtractionReport {
+    pub fn new(install_path: String) -> Self {
+        IdExtractionReport {
+            install_path,
+            archives: Vec::new(),
+            skipped_archives: Vec::new(),
+            script_count: 0,
+            item_count: 0,
+            category_counts: HashMap::new(),
+            merge_report: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdSearchResult {
+    pub item: CatalogItem,
+    pub score: u32,
+    pub matched_field: String,
+}
+
+impl IdSearchResult {
+    pub fn new(item: CatalogItem, score: u32, matched_field: String) -> Self {
+        IdSearchResult {
+            item,
+            score,
+            matched_field,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdSearchPage {
+    pub query: String,
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+    pub results: Vec<IdSearchResult>,
+}
+
+impl IdSearchPage {
+    pub fn new(
+        query: String,
+        total: usize,
+        page: usize,
+        page_size: usize,
+        results: Vec<IdSearchResult>,
+    ) -> Self {
+        IdSearchPage {
+            query,
+            total,
+            page,
+            page_size,
+            results,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportedItem {
+    pub tab: String,
+    pub container: String,
+    pub id: String,
+    pub display_name: String,
+    pub category: String,
+    pub index: usize,
+    pub chunk_index: usize,
+    pub level: u16,
+    pub seed: u16,
+    pub amount: u32,
+    pub durability: String,
+    pub mods: Vec<String>,
+}
+
+impl ExportedItem {
+    pub fn new(tab: String, container: String, item: &InventoryItem, mods: Vec<String>) -> Self {
+        ExportedItem {
+            tab,
+            container,
+            id: item.name.clone(),
+            display_name: item.catalog.display_name.clone(),
+            category: item.catalog.category.clone(),
+            index: item.index,
+            chunk_index: item.chunk_data.index,
+            level: item.chunk_data.level_value,
+            seed: item.chunk_data.seed_value,
+            amount: item.chunk_data.amount_value,
+            durability: item.chunk_data.durability_value.clone(),
+            mods,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportedSkill {
+    pub tree: String,
+    pub name: String,
+    pub index: usize,
+    pub points: u16,
+}
+
+impl ExportedSkill {
+    pub fn new(tree: String, name: String, index: usize, points: u16) -> Self {
+        ExportedSkill {
+            tree,
+            name,
+            index,
+            points,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportedUnlockable {
+    pub name: String,
+    pub index: usize,
+}
+
+impl ExportedUnlockable {
+    pub fn new(name: String, index: usize) -> Self {
+        ExportedUnlockable { name, index }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveExport {
+    pub path: String,
+    pub game_version: String,
+    pub inventory: Vec<ExportedItem>,
+    pub skills: Vec<ExportedSkill>,
+    pub unlockables: Vec<ExportedUnlockable>,
+}
+
+impl SaveExport {
+    pub fn new(
+        path: String,
+        game_version: String,
+        inventory: Vec<ExportedItem>,
+        skills: Vec<ExportedSkill>,
+        unlockables: Vec<ExportedUnlockable>,
+    ) -> Self {
+        SaveExport {
+            path,
+            game_version,
+            inventory,
+            skills,
+            unlockables,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportReport {
+    pub output_dir: String,
+    pub files: Vec<String>,
+    pub item_count: usize,
+    pub skill_count: usize,
+    pub unlockable_count: usize,
+}
+
+impl ExportReport {
+    pub fn new(
+        output_dir: String,
+        files: Vec<String>,
+        item_count: usize,
+        skill_count: usize,
+        unlockable_count: usize,
+    ) -> Self {
+        ExportReport {
+            output_dir,
+            files,
+            item_count,
+            skill_count,
+            unlockable_count,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ByteChange {
+    pub offset: usize,
+    pub old_bytes: Vec<u8>,
+    pub new_bytes: Vec<u8>,
+}
+
+impl ByteChange {
+    pub fn new(offset: usize, old_bytes: Vec<u8>, new_bytes: Vec<u8>) -> Self {
+        ByteChange {
+            offset,
+            old_bytes,
+            new_bytes,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryEditChange {
+    pub line: u64,
+    pub index: usize,
+    pub id: String,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+impl InventoryEditChange {
+    pub fn new(
+        line: u64,
+        index: usize,
+        id: String,
+        field: String,
+        old_value: String,
+        new_value: String,
+    ) -> Self {
+        InventoryEditChange {
+            line,
+            index,
+            id,
+            field,
+            old_value,
+            new_value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryEditIssue {
+    pub line: u64,
+    pub message: String,
+}
+
+impl InventoryEditIssue {
+    pub fn new(line: u64, message: String) -> Self {
+        InventoryEditIssue { line, message }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryEditPreview {
+    pub csv_path: String,
+    pub matched_rows: usize,
+    pub changes: Vec<InventoryEditChange>,
+    pub byte_changes: Vec<ByteChange>,
+    pub issues: Vec<InventoryEditIssue>,
+}
+
+impl InventoryEditPreview {
+    pub fn new(csv_path: String) -> Self {
+        InventoryEditPreview {
+            csv_path,
+            matched_rows: 0,
+            changes: Vec::new(),
+            byte_changes: Vec::new(),
+            issues: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EditAction {
+    SetLevel { value: u16 },
+    SetSeed { value: u16 },
+    SetAmount { value: u32 },
+    SetDurability { value: f32 },
+    Remove,
+    SetSkillPoints { value: u16 },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EditSelector {
+    #[serde(default = "EditSelector::default_target")]
+    pub target: String,
+    #[serde(default)]
+    pub tabs: Vec<String>,
+    #[serde(default)]
+    pub id_pattern: Option<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+impl EditSelector {
+    pub fn new(
+        target: String,
+        tabs: Vec<String>,
+        id_pattern: Option<String>,
+        categories: Vec<String>,
+    ) -> Self {
+        EditSelector {
+            target,
+            tabs,
+            id_pattern,
+            categories,
+        }
+    }
+
+    fn default_target() -> String {
+        "inventory".to_string()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EditPlanStep {
+    pub selector: EditSelector,
+    pub action: EditAction,
+}
+
+impl EditPlanStep {
+    pub fn new(selector: EditSelector, action: EditAction) -> Self {
+        EditPlanStep { selector, action }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EditPlan {
+    pub version: u32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub steps: Vec<EditPlanStep>,
+}
+
+impl EditPlan {
+    pub fn new(version: u32, name: String, description: String, steps: Vec<EditPlanStep>) -> Self {
+        EditPlan {
+            version,
+            name,
+            description,
+            steps,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EditPlanSaveResult {
+    pub path: String,
+    pub output_path: String,
+    pub backup_id: Option<u32>,
+    pub changed_items: usize,
+    pub removed_items: usize,
+    pub changed_skills: usize,
+    pub byte_changes: usize,
+    pub error: Option<String>,
+}
+
+impl EditPlanSaveResult {
+    pub fn new(path: String) -> Self {
+        EditPlanSaveResult {
+            path,
+            output_path: String::new(),
+            backup_id: None,
+            changed_items: 0,
+            removed_items: 0,
+            changed_skills: 0,
+            byte_changes: 0,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EditPlanReport {
+    pub plan_name: String,
+    pub plan_version: u32,
+    pub dry_run: bool,
+    pub results: Vec<EditPlanSaveResult>,
+}
+
+impl EditPlanReport {
+    pub fn new(
+        plan_name: String,
+        plan_version: u32,
+        dry_run: bool,
+        results: Vec<EditPlanSaveResult>,
+    ) -> Self {
+        EditPlanReport {
+            plan_name,
+            plan_version,
+            dry_run,
+            results,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScriptReport {
+    pub script_path: String,
+    pub output: Vec<String>,
+    pub changed_items: usize,
+    pub removed_items: usize,
+    pub changed_skills: usize,
+    pub byte_changes: usize,
+    pub files_written: Vec<String>,
+}
+
+impl ScriptReport {
+    pub fn new(script_path: String) -> Self {
+        ScriptReport {
+            script_path,
+            output: Vec::new(),
+            changed_items: 0,
+            removed_items: 0,
+            changed_skills: 0,
+            byte_changes: 0,
+            files_written: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkEditChange {
+    pub index: usize,
+    pub id: String,
+    pub tab: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+impl BulkEditChange {
+    pub fn new(
+        index: usize,
+        id: String,
+        tab: String,
+        old_value: String,
+        new_value: String,
+    ) -> Self {
+        BulkEditChange {
+            index,
+            id,
+            tab,
+            old_value,
+            new_value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkEditReport {
+    pub filter: String,
+    pub field: String,
+    pub expression: String,
+    pub matched_items: usize,
+    pub changes: Vec<BulkEditChange>,
+    pub byte_changes: Vec<ByteChange>,
+}
+
+impl BulkEditReport {
+    pub fn new(filter: String, field: String, expression: String) -> Self {
+        BulkEditReport {
+            filter,
+            field,
+            expression,
+            matched_items: 0,
+            changes: Vec::new(),
+            byte_changes: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ItemTransferReport {
+    pub source_path: String,
+    pub target_path: String,
+    pub id: String,
+    pub source_index: usize,
+    pub target_tab: String,
+    pub target_index: usize,
+    pub mods: Vec<String>,
+    pub added_records: usize,
+    pub added_bytes: usize,
+}
+
+impl ItemTransferReport {
+    pub fn new(
+        source_path: String,
+        target_path: String,
+        id: String,
+        source_index: usize,
+        target_tab: String,
+        target_index: usize,
+        mods: Vec<String>,
+        added_records: usize,
+        added_bytes: usize,
+    ) -> Self {
+        ItemTransferReport {
+            source_path,
+            target_path,
+            id,
+            source_index,
+            target_tab,
+            target_index,
+            mods,
+            added_records,
+            added_bytes,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SkillMergeChange {
+    pub tree: String,
+    pub name: String,
+    pub old_points: u16,
+    pub new_points: u16,
+}
+
+impl SkillMergeChange {
+    pub fn new(tree: String, name: String, old_points: u16, new_points: u16) -> Self {
+        SkillMergeChange {
+            tree,
+            name,
+            old_points,
+            new_points,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProgressionMergePreview {
+    pub source_path: String,
+    pub target_path: String,
+    pub skill_changes: Vec<SkillMergeChange>,
+    pub added_skills: Vec<String>,
+    pub added_unlockables: Vec<String>,
+    pub added_bytes: usize,
+}
+
+impl ProgressionMergePreview {
+    pub fn new(source_path: String, target_path: String) -> Self {
+        ProgressionMergePreview {
+            source_path,
+            target_path,
+            skill_changes: Vec::new(),
+            added_skills: Vec::new(),
+            added_unlockables: Vec::new(),
+            added_bytes: 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveImportChange {
+    pub section: String,
+    pub index: usize,
+    pub name: String,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+impl SaveImportChange {
+    pub fn new(
+        section: String,
+        index: usize,
+        name: String,
+        field: String,
+        old_value: String,
+        new_value: String,
+    ) -> Self {
+        SaveImportChange {
+            section,
+            index,
+            name,
+            field,
+            old_value,
+            new_value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveImportReport {
+    pub path: String,
+    pub changes: Vec<SaveImportChange>,
+    pub byte_changes: Vec<ByteChange>,
+    pub issues: Vec<String>,
+}
+
+impl SaveImportReport {
+    pub fn new(path: String) -> Self {
+        SaveImportReport {
+            path,
+            changes: Vec::new(),
+            byte_changes: Vec::new(),
+            issues: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveWriteReport {
+    pub path: String,
+    pub is_compressed: bool,
+    pub size: usize,
+    pub file_hash: String,
+    pub has_overwritten_changes: bool,
+}
+
+impl SaveWriteReport {
+    pub fn new(
+        path: String,
+        is_compressed: bool,
+        size: usize,
+        file_hash: String,
+        has_overwritten_changes: bool,
+    ) -> Self {
+        SaveWriteReport {
+            path,
+            is_compressed,
+            size,
+            file_hash,
+            has_overwritten_changes,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GzipProfile {
+    pub header: Vec<u8>,
+    pub flags: u8,
+    pub mtime: u32,
+    pub extra_flags: u8,
+    pub operating_system: u8,
+    pub filename: String,
+    pub comment: String,
+    pub level: u32,
+    pub is_reproducible: bool,
+    pub content_hash: String,
+}
+
+impl GzipProfile {
+    pub fn new(
+        header: Vec<u8>,
+        flags: u8,
+        mtime: u32,
+        extra_flags: u8,
+        operating_system: u8,
+        filename: String,
+        comment: String,
+        level: u32,
+        is_reproducible: bool,
+        content_hash: String,
+    ) -> Self {
+        GzipProfile {
+            header,
+            flags,
+            mtime,
+            extra_flags,
+            operating_system,
+            filename,
+            comment,
+            level,
+            is_reproducible,
+            content_hash,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveFingerprint {
+    pub path: String,
+    pub format: String,
+    pub is_compressed: bool,
+    pub file_size: usize,
+    pub content_size: usize,
+    pub content_hash: String,
+    pub save_header: String,
+    pub game_version: String,
+    pub gzip_profile: Option<GzipProfile>,
+}
+
+impl SaveFingerprint {
+    pub fn new(
+        path: String,
+        format: String,
+        is_compressed: bool,
+        file_size: usize,
+        content_size: usize,
+        content_hash: String,
+        save_header: String,
+        game_version: String,
+        gzip_profile: Option<GzipProfile>,
+    ) -> Self {
+        SaveFingerprint {
+            path,
+            format,
+            is_compressed,
+            file_size,
+            content_size,
+            content_hash,
+            save_header,
+            game_version,
+            gzip_profile,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveConversionReport {
+    pub source: SaveFingerprint,
+    pub target: SaveFingerprint,
+    pub output_path: String,
+}
+
+impl SaveConversionReport {
+    pub fn new(source: SaveFingerprint, target: SaveFingerprint, output_path: String) -> Self {
+        SaveConversionReport {
+            source,
+            target,
+            output_path,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveBackup {
+    pub id: u32,
+    pub save_path: String,
+    pub created_at: u64,
+    pub file_hash: String,
+    pub size: usize,
+    pub reason: String,
+}
+
+impl SaveBackup {
+    pub fn new(
+        id: u32,
+        save_path: String,
+        created_at: u64,
+        file_hash: String,
+        size: usize,
+        reason: String,
+    ) -> Self {
+        SaveBackup {
+            id,
+            save_path,
+            created_at,
+            file_hash,
+            size,
+            reason,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupManifest {
+    pub backups: Vec<SaveBackup>,
+}
+
+impl BackupManifest {
+    pub fn new(backups: Vec<SaveBackup>) -> Self {
+        BackupManifest { backups }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveMetadata {
+    pub path: String,
+    pub slot: String,
+    pub source: String,
+    pub steam_user_id: String,
+    pub is_compressed: bool,
+    pub size: usize,
+    pub modified_at: u64,
+    pub game_version: String,
+}
+
+impl SaveMetadata {
+    pub fn new(
+        path: String,
+        slot: String,
+        source: String,
+        steam_user_id: String,
+        is_compressed: bool,
+        size: usize,
+        modified_at: u64,
+        game_version: String,
+    ) -> Self {
+        SaveMetadata {
+            path,
+            slot,
+            source,
+            steam_user_id,
+            is_compressed,
+            size,
+            modified_at,
+            game_version,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveChangeEvent {
+    pub path: String,
+    pub previous_hash: String,
+    pub file_hash: String,
+    pub size: usize,
+    pub modified_at: u64,
+}
+
+impl SaveChangeEvent {
+    pub fn new(
+        path: String,
+        previous_hash: String,
+        file_hash: String,
+        size: usize,
+        modified_at: u64,
+    ) -> Self {
+        SaveChangeEvent {
+            path,
+            previous_hash,
+            file_hash,
+            size,
+            modified_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveDiffEntry {
+    pub section: String,
+    pub name: String,
+    pub field: String,
+    pub session_value: String,
+    pub disk_value: String,
+}
+
+impl SaveDiffEntry {
+    pub fn new(
+        section: String,
+        name: String,
+        field: String,
+        session_value: String,
+        disk_value: String,
+    ) -> Self {
+        SaveDiffEntry {
+            section,
+            name,
+            field,
+            session_value,
+            disk_value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaveDiffReport {
+    pub path: String,
+    pub has_pending_edits: bool,
+    pub disk_hash: String,
+    pub entries: Vec<SaveDiffEntry>,
+}
+
+impl SaveDiffReport {
+    pub fn new(
+        path: String,
+        has_pending_edits: bool,
+        disk_hash: String,
+        entries: Vec<SaveDiffEntry>,
+    ) -> Self {
+        SaveDiffReport {
+            path,
+            has_pending_edits,
+            disk_hash,
+            entries,
         }
     }
 }
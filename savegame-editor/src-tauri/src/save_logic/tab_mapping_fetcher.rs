@@ -0,0 +1,31 @@
+use crate::save_logic::struct_data::InventoryTabMapping;
+use std::path::Path;
+use std::{error::Error, fs};
+
+// Define global result definition for easier readability.
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+// Defines the file name of the inventory tab mapping.
+static TAB_MAPPING_FILE: &str = "inventory_tabs.json";
+
+/// Represents a method for fetching the mapping from save containers and ID categories to inventory tabs.
+///
+/// ### Parameter
+/// - `mapping_path`: The directory where the mapping files are stored.
+///
+/// ### Returns `InventoryTabMapping`
+/// The tab model of the inventory.
+pub fn fetch_tab_mapping(mapping_path: &String) -> Result<InventoryTabMapping> {
+    let file_path = Path::new(mapping_path).join(TAB_MAPPING_FILE);
+    let file_content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Error reading the tab mapping: {}", e))?;
+
+    let mapping: InventoryTabMapping = serde_json::from_str(&file_content)
+        .map_err(|e| format!("Error parsing the tab mapping: {}", e))?;
+
+    if mapping.default_tab.is_empty() {
+        return Err("The tab mapping does not define a default tab.".into());
+    }
+
+    Ok(mapping)
+}
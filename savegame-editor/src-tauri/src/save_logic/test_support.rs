@@ -0,0 +1,39 @@
+//! Loads the bundled resources and the saves of the Hawks Outpost for the tests of the save logic.
+
+use crate::logger::ConsoleLogger;
+use crate::save_logic::file_analyser::load_save_from_path;
+use crate::save_logic::item_catalog::fetch_item_catalog;
+use crate::save_logic::item_rules::{fetch_item_rules, ItemRuleEngine};
+use crate::save_logic::struct_data::{InventoryTabMapping, ItemCatalog, SaveFile};
+use crate::save_logic::tab_mapping_fetcher::fetch_tab_mapping;
+
+/// Loads the bundled catalog, tab mapping and item rules.
+pub fn load_resources() -> (ItemCatalog, InventoryTabMapping, ItemRuleEngine) {
+    let resource_path: &str = env!("CARGO_MANIFEST_DIR");
+    let mapping_path: String = format!("{}/Mappings", resource_path);
+
+    (
+        fetch_item_catalog(
+            &format!("{}/IDs", resource_path),
+            &format!("{}/Patched_Items", resource_path),
+        )
+        .unwrap(),
+        fetch_tab_mapping(&mapping_path).unwrap(),
+        fetch_item_rules(&mapping_path).unwrap(),
+    )
+}
+
+/// Loads a save of the Hawks Outpost.
+pub fn load_outpost_save(name: &str) -> SaveFile {
+    let (catalog, tab_mapping, item_rules) = load_resources();
+
+    load_save_from_path(
+        &format!("{}/Hawks_Outpost/{}", env!("CARGO_MANIFEST_DIR"), name),
+        catalog,
+        tab_mapping,
+        item_rules,
+        &mut ConsoleLogger::new(),
+        false,
+    )
+    .unwrap()
+}
@@ -12,6 +12,8 @@ import {
     IdData,
     OutpostSave,
     PatchedItems,
+    SaveChangeEvent,
+    SaveDiffReport,
     SaveFile,
 } from "./models/save-models";
 import { SettingsPage } from "./pages/settings-page";
@@ -35,6 +37,10 @@ import {
 import { HawksOutpostPage } from "./pages/outpost-page";
 import { PatchedItemsPage } from "./pages/patched-items-page";
 import { Toaster } from "@/components/ui/sonner";
+import { Button } from "@/components/ui/button";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
 
 function App() {
     // Declare app settings manager
@@ -57,6 +63,107 @@ function App() {
         setValue: setCurrentSaveFile,
     };
 
+    // Watch the open save for changes of the game (the session is never reloaded without asking).
+    useEffect(() => {
+        if (currentSaveFileValue == undefined) {
+            invoke("unwatch_save").catch(() => {});
+            return;
+        }
+
+        invoke("watch_save", {
+            file_path: currentSaveFileValue.path,
+            file_hash: currentSaveFileValue.file_hash,
+        }).catch((err) => {
+            console.log("The save cannot be watched: " + err);
+        });
+    }, [currentSaveFileValue?.path, currentSaveFileValue?.file_hash]);
+
+    useEffect(() => {
+        const unlisten = listen<SaveChangeEvent>("save-changed", (event) => {
+            handleChangedSave(event.payload);
+        });
+
+        return () => {
+            unlisten.then((unlistenFn) => unlistenFn());
+        };
+    }, [currentSaveFileValue]);
+
+    async function handleChangedSave(change: SaveChangeEvent) {
+        if (currentSaveFileValue == undefined || change.path != currentSaveFileValue.path) {
+            return;
+        }
+
+        let report = await invoke<SaveDiffReport>("diff_save_with_disk", {
+            save_file: currentSaveFileValue,
+        }).catch(() => undefined);
+
+        toast.warning("The save was changed outside of the Editor.", {
+            description:
+                (report
+                    ? report.entries.length + " differences to the current session."
+                    : "The changed save could not be compared to the current session.") +
+                (report == undefined || report.has_pending_edits
+                    ? " Reloading discards your unsaved edits."
+                    : ""),
+            duration: Infinity,
+            action: (
+                <Button variant="outline" onClick={() => reloadChangedSave(change.path)}>
+                    {report == undefined || report.has_pending_edits
+                        ? "Reload and discard edits"
+                        : "Reload"}
+                </Button>
+            ),
+            cancel: report && report.entries.length > 0 && (
+                <Button variant="outline" onClick={() => showSaveDifferences(report)}>
+                    Show differences
+                </Button>
+            ),
+        });
+    }
+
+    async function reloadChangedSave(filePath: string) {
+        let newSave = await invoke<SaveFile>("load_save", {
+            file_path: filePath,
+            is_debugging: appSettings.isDebugging.value,
+            has_automatic_backup: appSettings.hasAutomaticBackup.value,
+        }).catch((err) => {
+            toast.error("Uh oh! Something went wrong.", {
+                description: "The Editor stumbled accross the following error: " + err,
+                duration: 8000,
+            });
+            return;
+        });
+
+        if (newSave) {
+            setCurrentSaveFile(newSave);
+        }
+    }
+
+    function showSaveDifferences(report: SaveDiffReport) {
+        const lines = report.entries
+            .slice(0, 10)
+            .map(
+                (entry) =>
+                    entry.section +
+                    " " +
+                    entry.name +
+                    ": " +
+                    (entry.session_value || "-") +
+                    " (session) / " +
+                    (entry.disk_value || "-") +
+                    " (disk)"
+            );
+
+        if (report.entries.length > lines.length) {
+            lines.push("... and " + (report.entries.length - lines.length) + " more.");
+        }
+
+        toast.info("Differences between the session and the save on disk", {
+            description: <div className="whitespace-pre-line">{lines.join("\n")}</div>,
+            duration: 20000,
+        });
+    }
+
     // Declare IDs state
     const [idDataValue, setIdData] = useState<IdData[]>();
     const idData: SettingState<IdData[] | undefined> = {
@@ -22,8 +22,10 @@ import {
     DialogHeader,
     DialogTitle,
 } from "@/components/ui/dialog";
+import { invoke } from "@tauri-apps/api/core";
 import { useState } from "react";
 import { useNavigate } from "react-router-dom";
+import { toast } from "sonner";
 
 type OutpostProps = {
     currentSaveFile: SettingState<SaveFile | undefined>;
@@ -38,10 +40,26 @@ export const OutPostCarouselComponent = ({
     const [currentSelectedOutpostSave, setCurrentSelectedOutpostSave] = useState<
         OutpostSave | undefined
     >(undefined);
+    const [isOpeningSave, setIsOpeningSave] = useState(false);
     const navigate = useNavigate();
-    const handleSelectTemplate = (saveFile: SaveFile) => {
-        currentSaveFile.setValue(saveFile);
-        navigate("/main");
+    const handleSelectTemplate = async (outpostSave: OutpostSave) => {
+        // The save is only parsed once it is opened.
+        setIsOpeningSave(true);
+        const saveFile = await invoke<SaveFile>("load_outpost_save", {
+            save_path: outpostSave.save_path,
+        }).catch((err) => {
+            console.error("Error loading outpost save:", err);
+            toast.error("Uh oh! Something went wrong.", {
+                description: `Error details: ${err}`,
+            });
+            return;
+        });
+        setIsOpeningSave(false);
+
+        if (saveFile) {
+            currentSaveFile.setValue(saveFile);
+            navigate("/main");
+        }
     };
 
     return (
@@ -53,7 +71,7 @@ export const OutPostCarouselComponent = ({
                     <CarouselContent className="h-[80vh]">
                         {outpostSaves.map((save, index) => (
                             <CarouselItem
-                                key={save.save_file.path}
+                                key={save.directory}
                                 className="flex items-center justify-center overflow-hidden"
                             >
                                 <Tooltip>
@@ -89,6 +107,11 @@ export const OutPostCarouselComponent = ({
                                                 @{currentSelectedOutpostSave?.owner}
                                             </DialogDescription>
                                             <div className="space-y-4">
+                                                {currentSelectedOutpostSave?.error && (
+                                                    <p className="text-sm text-destructive">
+                                                        <strong>Error:</strong> {currentSelectedOutpostSave.error}
+                                                    </p>
+                                                )}
                                                 <p className="text-sm text-muted-foreground">
                                                     <strong>Description:</strong> {currentSelectedOutpostSave?.description}
                                                 </p>
@@ -102,11 +125,10 @@ export const OutPostCarouselComponent = ({
                                         </DialogHeader>
                                         <DialogFooter>
                                             <Button
+                                                disabled={isOpeningSave || !!currentSelectedOutpostSave?.error}
                                                 onClick={() => {
                                                     if (currentSelectedOutpostSave) {
-                                                        handleSelectTemplate(
-                                                            currentSelectedOutpostSave.save_file
-                                                        );
+                                                        handleSelectTemplate(currentSelectedOutpostSave);
                                                     }
                                                 }}
                                             >
@@ -35,10 +35,12 @@ export interface InventoryItem {
   sgd_data: Uint8Array;
   chunk_data: InventoryChunk;
   mod_data: Mod[];
+  catalog: CatalogItem;
 }
 
 export interface InventoryItemRow {
   name: string;
+  container: string;
   inventory_items: InventoryItem[];
 }
 
@@ -71,15 +73,431 @@ export interface SaveFile {
   unlockable_items: UnlockableItem[];
   items: InventoryItemRow[];
   log_history: string[];
+  classification_report: ClassificationEntry[];
+  unknown_ids: UnknownId[];
   is_compressed: boolean;
   game_version: string;
+  file_hash: string;
+  gzip_profile: GzipProfile | null;
 }
 
 export interface OutpostSave {
+  directory: string;
   name: string;
   owner: string;
   description: string;
   features: string[];
   version: string;
-  save_file: SaveFile;
+  save_path: string;
+  error: string;
+}
+
+export interface LayoutProfile {
+  game_version: string;
+  data_offset: number;
+  level_position: number;
+  seed_position: number;
+  amount_position: number;
+  durability_position: number;
+  counter_stats_position: number;
+  chunk_strides: number[];
+  size_position: number;
+  header_size: number;
+}
+
+export interface LayoutFieldCandidate {
+  field: string;
+  distance: number;
+  hits: number;
+  samples: number;
+  confidence: number;
+}
+
+export interface LayoutReport {
+  path: string;
+  named_sgd_count: number;
+  known_item_count: number;
+  chunk_marker_count: number;
+  chunk_run_count: number;
+  known_chunk_run_count: number;
+  stride_confidence: number;
+  header_confidence: number;
+  fields: LayoutFieldCandidate[];
+  confidence: number;
+  candidate: LayoutProfile;
+  current: LayoutProfile;
+  matches_current: boolean;
+  notes: string[];
+}
+
+export interface ClassificationEntry {
+  name: string;
+  index: number;
+  classification: string;
+  rule: string;
+}
+
+export interface CatalogItem {
+  id: string;
+  display_name: string;
+  category: string;
+  dropable: boolean;
+  shareable: boolean;
+  dlc: boolean;
+}
+
+export interface ItemCatalog {
+  items: Record<string, CatalogItem>;
+}
+
+export interface CoopSafetyIssue {
+  tab: string;
+  item_name: string;
+  display_name: string;
+  index: number;
+  chunk_index: number;
+  dropable: boolean;
+  shareable: boolean;
+  risk: string;
+}
+
+export interface CoopSafetyReport {
+  path: string;
+  checked_items: number;
+  issues: CoopSafetyIssue[];
+  is_safe: boolean;
+}
+
+export interface IdSnapshot {
+  version: number;
+  created_at: number;
+  source: string;
+  id_count: number;
+}
+
+export interface IdDatabaseManifest {
+  current_version: number;
+  snapshots: IdSnapshot[];
+  bundled_hash: string;
+}
+
+export interface IdConflict {
+  id: string;
+  existing_category: string;
+  imported_category: string;
+}
+
+export interface IdMergeReport {
+  version: number;
+  added: number;
+  added_display_names: number;
+  duplicates: number;
+  new_categories: string[];
+  conflicts: IdConflict[];
+}
+
+export interface UnknownId {
+  name: string;
+  index: number;
+  tab: string;
+  is_mod: boolean;
+  occurrences: number;
+  suggested_category: string;
+}
+
+export interface IdExtractionReport {
+  install_path: string;
+  archives: string[];
+  skipped_archives: string[];
+  script_count: number;
+  item_count: number;
+  category_counts: Record<string, number>;
+  merge_report?: IdMergeReport;
+}
+
+export interface IdSearchResult {
+  item: CatalogItem;
+  score: number;
+  matched_field: string;
+}
+
+export interface IdSearchPage {
+  query: string;
+  total: number;
+  page: number;
+  page_size: number;
+  results: IdSearchResult[];
+}
+
+export interface ExportedItem {
+  tab: string;
+  container: string;
+  id: string;
+  display_name: string;
+  category: string;
+  index: number;
+  chunk_index: number;
+  level: number;
+  seed: number;
+  amount: number;
+  durability: string;
+  mods: string[];
+}
+
+export interface ExportedSkill {
+  tree: string;
+  name: string;
+  index: number;
+  points: number;
+}
+
+export interface ExportedUnlockable {
+  name: string;
+  index: number;
+}
+
+export interface SaveExport {
+  path: string;
+  game_version: string;
+  inventory: ExportedItem[];
+  skills: ExportedSkill[];
+  unlockables: ExportedUnlockable[];
+}
+
+export interface ExportReport {
+  output_dir: string;
+  files: string[];
+  item_count: number;
+  skill_count: number;
+  unlockable_count: number;
+}
+
+export interface ByteChange {
+  offset: number;
+  old_bytes: number[];
+  new_bytes: number[];
+}
+
+export interface InventoryEditChange {
+  line: number;
+  index: number;
+  id: string;
+  field: string;
+  old_value: string;
+  new_value: string;
+}
+
+export interface InventoryEditIssue {
+  line: number;
+  message: string;
+}
+
+export interface InventoryEditPreview {
+  csv_path: string;
+  matched_rows: number;
+  changes: InventoryEditChange[];
+  byte_changes: ByteChange[];
+  issues: InventoryEditIssue[];
+}
+
+export type EditAction =
+  | { type: "set_level"; value: number }
+  | { type: "set_seed"; value: number }
+  | { type: "set_amount"; value: number }
+  | { type: "set_durability"; value: number }
+  | { type: "remove" }
+  | { type: "set_skill_points"; value: number };
+
+export interface EditSelector {
+  target: "inventory" | "skills";
+  tabs: string[];
+  id_pattern?: string;
+  categories: string[];
+}
+
+export interface EditPlanStep {
+  selector: EditSelector;
+  action: EditAction;
+}
+
+export interface EditPlan {
+  version: number;
+  name: string;
+  description: string;
+  steps: EditPlanStep[];
+}
+
+export interface EditPlanSaveResult {
+  path: string;
+  output_path: string;
+  backup_id?: number;
+  changed_items: number;
+  removed_items: number;
+  changed_skills: number;
+  byte_changes: number;
+  error?: string;
+}
+
+export interface EditPlanReport {
+  plan_name: string;
+  plan_version: number;
+  dry_run: boolean;
+  results: EditPlanSaveResult[];
+}
+
+export interface ScriptReport {
+  script_path: string;
+  output: string[];
+  changed_items: number;
+  removed_items: number;
+  changed_skills: number;
+  byte_changes: number;
+  files_written: string[];
+}
+
+export interface BulkEditChange {
+  index: number;
+  id: string;
+  tab: string;
+  old_value: string;
+  new_value: string;
+}
+
+export interface BulkEditReport {
+  filter: string;
+  field: string;
+  expression: string;
+  matched_items: number;
+  changes: BulkEditChange[];
+  byte_changes: ByteChange[];
+}
+
+export interface ItemTransferReport {
+  source_path: string;
+  target_path: string;
+  id: string;
+  source_index: number;
+  target_tab: string;
+  target_index: number;
+  mods: string[];
+  added_records: number;
+  added_bytes: number;
+}
+
+export interface SkillMergeChange {
+  tree: string;
+  name: string;
+  old_points: number;
+  new_points: number;
+}
+
+export interface ProgressionMergePreview {
+  source_path: string;
+  target_path: string;
+  skill_changes: SkillMergeChange[];
+  added_skills: string[];
+  added_unlockables: string[];
+  added_bytes: number;
+}
+
+export interface SaveImportChange {
+  section: string;
+  index: number;
+  name: string;
+  field: string;
+  old_value: string;
+  new_value: string;
+}
+
+export interface SaveImportReport {
+  path: string;
+  changes: SaveImportChange[];
+  byte_changes: ByteChange[];
+  issues: string[];
+}
+
+export interface SaveWriteReport {
+  path: string;
+  is_compressed: boolean;
+  size: number;
+  file_hash: string;
+  has_overwritten_changes: boolean;
+}
+
+export interface GzipProfile {
+  header: number[];
+  flags: number;
+  mtime: number;
+  extra_flags: number;
+  operating_system: number;
+  filename: string;
+  comment: string;
+  level: number;
+  is_reproducible: boolean;
+  content_hash: string;
+}
+
+export interface SaveFingerprint {
+  path: string;
+  format: string;
+  is_compressed: boolean;
+  file_size: number;
+  content_size: number;
+  content_hash: string;
+  save_header: string;
+  game_version: string;
+  gzip_profile: GzipProfile | null;
+}
+
+export interface SaveConversionReport {
+  source: SaveFingerprint;
+  target: SaveFingerprint;
+  output_path: string;
+}
+
+export interface SaveBackup {
+  id: number;
+  save_path: string;
+  created_at: number;
+  file_hash: string;
+  size: number;
+  reason: string;
+}
+
+export interface BackupManifest {
+  backups: SaveBackup[];
+}
+
+export interface SaveMetadata {
+  path: string;
+  slot: string;
+  source: string;
+  steam_user_id: string;
+  is_compressed: boolean;
+  size: number;
+  modified_at: number;
+  game_version: string;
+}
+
+export interface SaveChangeEvent {
+  path: string;
+  previous_hash: string;
+  file_hash: string;
+  size: number;
+  modified_at: number;
+}
+
+export interface SaveDiffEntry {
+  section: string;
+  name: string;
+  field: string;
+  session_value: string;
+  disk_value: string;
+}
+
+export interface SaveDiffReport {
+  path: string;
+  has_pending_edits: boolean;
+  disk_hash: string;
+  entries: SaveDiffEntry[];
 }
@@ -21,7 +21,12 @@ import {
 import { Progress } from "@/components/ui/progress";
 import { Separator } from "@/components/ui/separator";
 import { NavbarComponent } from "@/components/custom/custom-navbar-component";
-import { IdData, PatchedItems, SaveFile } from "@/models/save-models";
+import {
+    IdData,
+    PatchedItems,
+    SaveFile,
+    SaveWriteReport,
+} from "@/models/save-models";
 import {
     Tooltip,
     TooltipContent,
@@ -44,7 +49,6 @@ import {
 } from "@/components/ui/drawer";
 import { listen, TauriEvent } from "@tauri-apps/api/event";
 import { invoke } from "@tauri-apps/api/core";
-import { writeFile } from "@tauri-apps/plugin-fs";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import { AppSettings, SettingState } from "@/models/settings-model";
 import {
@@ -240,15 +244,7 @@ export function MainPage({
         }
     }
 
-    function handleSavingCurrentSaveFile() {
-        if (currentSaveFile.value?.is_compressed == true) {
-            saveCurrentSaveFileCompressed();
-        } else {
-            saveCurrentSaveFileDecompressed();
-        }
-    }
-
-    async function saveCurrentSaveFileDecompressed() {
+    async function handleSavingCurrentSaveFile() {
         let filePath = await save({
             defaultPath: "/save_main_0",
             filters: [
@@ -260,54 +256,57 @@ export function MainPage({
         });
 
         if (filePath != null && currentSaveFile.value != undefined) {
-            // Save data to file
-            await writeFile(filePath, new Uint8Array(currentSaveFile.value.file_content)).catch(
-                (err) => {
-                    toast.error("Uh oh! Something went wrong. :/", {
-                        description:
-                            "The Editor stumbled accross the following error: " + err,
-                        duration: 8000,
-                    });
-                    return;
-                }
-            );
+            writeCurrentSaveFile(currentSaveFile.value, filePath, false);
         }
     }
 
-    async function saveCurrentSaveFileCompressed() {
-        let filePath = await save({
-            defaultPath: "/save_main_0",
-            filters: [
-                {
-                    name: "SAV File",
-                    extensions: ["sav"],
-                },
-            ],
-        });
-
-        if (filePath != null && currentSaveFile.value != undefined) {
-            // Save data to file
-            let compressed = await invoke<Uint8Array>("compress_save", {
-                data: currentSaveFile.value.file_content,
-            }).catch((err) => {
-                toast.error("Uh oh! Something went wrong. :/", {
+    async function writeCurrentSaveFile(
+        saveFile: SaveFile,
+        filePath: string,
+        isForced: boolean
+    ) {
+        // The save is written in the format it was loaded in (compressed PC saves are compressed again).
+        let report = await invoke<SaveWriteReport>("write_save", {
+            save_file: saveFile,
+            file_path: filePath,
+            is_forced: isForced,
+        }).catch(async (err) => {
+            let hasConflict = await invoke<boolean>("check_save_conflict", {
+                save_file: saveFile,
+                file_path: filePath,
+            }).catch(() => false);
+
+            if (hasConflict && !isForced) {
+                toast.warning("The save was changed on disk since it was loaded.", {
                     description:
-                        "The Editor stumbled accross the following error: " + err,
-                    duration: 8000,
+                        "The game or another program changed the file. Overwriting it discards these changes.",
+                    duration: Infinity,
+                    action: (
+                        <Button
+                            variant="outline"
+                            onClick={() => writeCurrentSaveFile(saveFile, filePath, true)}
+                        >
+                            Overwrite
+                        </Button>
+                    ),
                 });
                 return;
+            }
+
+            toast.error("Uh oh! Something went wrong. :/", {
+                description: "The Editor stumbled accross the following error: " + err,
+                duration: 8000,
             });
+            return;
+        });
 
-            if (compressed) {
-                await writeFile(filePath, new Uint8Array(compressed)).catch((err) => {
-                    toast.error("Uh oh! Something went wrong. :/", {
-                        description:
-                            "The Editor stumbled accross the following error: " + err,
-                        duration: 8000,
-                    });
-                    return;
-                });
-            }
+        if (report) {
+            // The session continues with the written file, so the next write is checked against it.
+            currentSaveFile.setValue({
+                ...saveFile,
+                path: report.path,
+                file_hash: report.file_hash,
+            });
         }
     }
 
@@ -323,17 +322,18 @@ export function MainPage({
         });
 
         if (filePath != null && currentSaveFile.value != undefined) {
-            // Save data to file
-            await writeFile(filePath, new Uint8Array(currentSaveFile.value.file_content)).catch(
-                (err) => {
-                    toast.error("Uh oh! Something went wrong. :/", {
-                        description:
-                            "The Editor stumbled accross the following error: " + err,
-                        duration: 8000,
-                    });
-                    return;
-                }
-            );
+            // Backups are written decompressed.
+            await invoke<SaveWriteReport>("write_save", {
+                save_file: { ...currentSaveFile.value, is_compressed: false },
+                file_path: filePath,
+                is_forced: false,
+            }).catch((err) => {
+                toast.error("Uh oh! Something went wrong. :/", {
+                    description: "The Editor stumbled accross the following error: " + err,
+                    duration: 8000,
+                });
+                return;
+            });
         }
     }
 
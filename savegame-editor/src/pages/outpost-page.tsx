@@ -33,8 +33,8 @@ export const HawksOutpostPage = ({
 
             if (result) {
                 outpostSaves.setValue(result);
-                setIsLoading(false);
             }
+            setIsLoading(false);
         };
 
         // Check if outpostSaves has been set